@@ -4,17 +4,34 @@ use std::f32::consts::{PI, TAU};
 use std::fmt;
 use std::str::FromStr;
 
+#[cfg(feature = "rand")]
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
 #[cfg(feature = "rust-rgb")]
 use rgb::{RGB, RGBA};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{parse, ParseColorError};
+use crate::color_space::*;
+#[cfg(feature = "okhsl")]
+use crate::ok_color::{linear_srgb_to_okhsl, okhsl_to_linear_srgb};
+#[cfg(feature = "okhsv")]
+use crate::ok_color::{linear_srgb_to_okhsv, okhsv_to_linear_srgb};
+#[cfg(feature = "named-colors")]
+use crate::parser::NAMED_COLORS;
+use crate::parser::{parse_hex, parse_stop, parse_with_options, ColorFunction};
+use crate::{
+    parse, parse_svg_color_attribute, Color64, ColorSpace, CssParseOptions, GradientStop,
+    HueInterpolationMode, ParseColorContextError, ParseColorError, SvgColor,
+};
 
 #[cfg(feature = "lab")]
 const PI_3: f32 = PI * 3.0;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[repr(C)]
 /// The color
 pub struct Color {
     /// Red
@@ -27,7 +44,102 @@ pub struct Color {
     pub a: f32,
 }
 
+/// Output format for [`Color::to_css_string_with_options()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CssFormat {
+    /// `#rrggbb` or `#rrggbbaa`
+    Hex,
+    /// `rgb(r,g,b)` or `rgba(r,g,b,a)`
+    Rgb,
+    /// `hsl(h,s%,l%)` or `hsla(h,s%,l%,a)`
+    Hsl,
+    /// `oklab(l a b)` or `oklab(l a b / alpha)`
+    Oklab,
+    /// `oklch(l c h)` or `oklch(l c h / alpha)`
+    Oklch,
+}
+
+/// Options for [`Color::to_css_string_with_options()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CssStringOptions {
+    /// Output format
+    pub format: CssFormat,
+    /// Maximum number of decimal places for fractional values. Trailing zeros are removed.
+    pub precision: u8,
+    /// Use uppercase hexadecimal digits (only used by [`CssFormat::Hex`]).
+    pub uppercase_hex: bool,
+    /// Include the alpha component even if the color is fully opaque.
+    pub always_include_alpha: bool,
+}
+
+/// Blend modes, as defined in [Compositing and Blending Level 1](https://www.w3.org/TR/compositing-1/#blending).
+///
+/// Used by [`Color::blend_alpha_compositing()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// The source color replaces the backdrop.
+    #[default]
+    Normal,
+    /// Multiply the source and backdrop colors.
+    Multiply,
+    /// Multiply the complements of the source and backdrop colors, then complement the result.
+    Screen,
+    /// Multiply or screen, depending on the backdrop color.
+    Overlay,
+    /// The darker of the source and backdrop colors.
+    Darken,
+    /// The lighter of the source and backdrop colors.
+    Lighten,
+    /// Brighten the backdrop color to reflect the source color.
+    ColorDodge,
+    /// Darken the backdrop color to reflect the source color.
+    ColorBurn,
+    /// Multiply or screen, depending on the source color.
+    HardLight,
+    /// Darken or lighten, depending on the source color.
+    SoftLight,
+    /// Subtract the darker of the two colors from the lighter one.
+    Difference,
+    /// Similar to `Difference` but with lower contrast.
+    Exclusion,
+    /// The hue of the source with the saturation and luminosity of the backdrop.
+    Hue,
+    /// The saturation of the source with the hue and luminosity of the backdrop.
+    Saturation,
+    /// The hue and saturation of the source with the luminosity of the backdrop.
+    Color,
+    /// The luminosity of the source with the hue and saturation of the backdrop.
+    Luminosity,
+}
+
+impl Default for CssStringOptions {
+    fn default() -> Self {
+        CssStringOptions {
+            format: CssFormat::Hex,
+            precision: 3,
+            uppercase_hex: false,
+            always_include_alpha: false,
+        }
+    }
+}
+
 impl Color {
+    /// Opaque black
+    pub const BLACK: Color = Color {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    };
+
+    /// Opaque white
+    pub const WHITE: Color = Color {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    };
+
     /// Arguments:
     ///
     /// * `r`: Red value [0..1]
@@ -47,6 +159,29 @@ impl Color {
         Color { r, g, b, a }
     }
 
+    /// Create color from `[r, g, b, a]`, each in the range [0..1].
+    pub fn from_array(arr: [f32; 4]) -> Color {
+        let [r, g, b, a] = arr;
+        Color { r, g, b, a }
+    }
+
+    /// Create color from premultiplied alpha components, i.e. `r`, `g` and `b` already
+    /// multiplied by `a`. Fully transparent input gives transparent black.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba_with_premultiplied_alpha(0.5, 0.25, 0.0, 0.5);
+    /// assert_eq!(c.rgba(), (1.0, 0.5, 0.0, 0.5));
+    /// ```
+    pub fn from_rgba_with_premultiplied_alpha(r: f32, g: f32, b: f32, a: f32) -> Color {
+        if a <= 0.0 {
+            return Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+        }
+        Color::from_rgba(r / a, g / a, b / a, a)
+    }
+
     /// Arguments:
     ///
     /// * `r`: Red value [0..255]
@@ -76,6 +211,21 @@ impl Color {
         }
     }
 
+    /// Create opaque color from the lowest 24 bits of `rgb` as `0xRRGGBB`, higher bits are
+    /// ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_24bit_integer(0xff6600);
+    /// assert_eq!(c.rgba_u8(), (255, 102, 0, 255));
+    /// ```
+    pub fn from_24bit_integer(rgb: u32) -> Color {
+        let [_, r, g, b] = rgb.to_be_bytes();
+        Color::from_rgb_u8(r, g, b)
+    }
+
     /// Arguments:
     ///
     /// * `r`: Red value [0..1]
@@ -92,13 +242,45 @@ impl Color {
     /// * `b`: Blue value [0..1]
     /// * `a`: Alpha value [0..1]
     pub fn from_linear_rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
-        fn from_linear(x: f32) -> f32 {
-            if x >= 0.0031308 {
-                return 1.055 * x.powf(1.0 / 2.4) - 0.055;
-            }
-            12.92 * x
-        }
-        Color::from_rgba(from_linear(r), from_linear(g), from_linear(b), a)
+        Color::from_rgba(
+            srgb_compression(r),
+            srgb_compression(g),
+            srgb_compression(b),
+            a,
+        )
+    }
+
+    /// Create color from `[r, g, b, a]` IEEE 754 half-precision float bits, as commonly read back
+    /// from GPU textures. The color components are linear sRGB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// // 1.0, 0.0, 0.2141, 0.5
+    /// let c = Color::from_rgba_f16_array([0x3c00, 0x0000, 0x32da, 0x3800]);
+    /// assert_eq!(c.rgba_u8(), (255, 0, 128, 128));
+    /// ```
+    pub fn from_rgba_f16_array(arr: [u16; 4]) -> Color {
+        let [r, g, b, a] = arr.map(f16_to_f32);
+        Color::from_linear_rgba(r, g, b, a)
+    }
+
+    /// Create color from `[r, g, b, a]` IEEE 754 half-precision float bits. Unlike
+    /// [`Color::from_rgba_f16_array()`] the components are used as is, without converting from
+    /// linear sRGB. Infinity and NaN are decoded unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_f16_array([0x3c00, 0x0000, 0x3800, 0x3c00]);
+    /// assert_eq!(c.rgba(), (1.0, 0.0, 0.5, 1.0));
+    /// ```
+    pub fn from_f16_array(arr: [u16; 4]) -> Color {
+        Color::from_array(arr.map(f16_to_f32))
     }
 
     /// Arguments:
@@ -200,6 +382,7 @@ impl Color {
     /// * `a`: How green/red the color is
     /// * `b`: How blue/yellow the color is
     /// * `alpha`: Alpha [0..1]
+    #[allow(clippy::excessive_precision)]
     pub fn from_oklaba(l: f32, a: f32, b: f32, alpha: f32) -> Color {
         let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
         let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
@@ -212,6 +395,106 @@ impl Color {
         Color::from_linear_rgba(r, g, b, alpha)
     }
 
+    /// Create color from Oklch with the hue angle in radians, e.g. as returned by `atan2()`.
+    /// Note that [`Color::convert_to()`] with [`ColorSpace::OkLch`] returns the hue in degrees.
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Perceived lightness
+    /// * `c`: Chroma
+    /// * `h_rad`: Hue angle in radians
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_oklch_polar(0.628, 0.258, 29.23_f32.to_radians());
+    /// assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+    /// ```
+    pub fn from_oklch_polar(l: f32, c: f32, h_rad: f32) -> Color {
+        Color::from_oklcha_polar(l, c, h_rad, 1.0)
+    }
+
+    /// Create color from Oklch with the hue angle in radians, see
+    /// [`Color::from_oklch_polar()`].
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Perceived lightness
+    /// * `c`: Chroma
+    /// * `h_rad`: Hue angle in radians
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_oklcha_polar(l: f32, c: f32, h_rad: f32, alpha: f32) -> Color {
+        Color::from_oklaba(l, c * h_rad.cos(), c * h_rad.sin(), alpha)
+    }
+
+    #[cfg(feature = "okhsl")]
+    /// Create color from [Okhsl](https://bottosson.github.io/posts/colorpicker/#hsl-2), a
+    /// perceptually uniform hue, saturation and lightness color space based on Oklab.
+    ///
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_okhsl(29.23, 1.0, 0.568);
+    /// assert_eq!(c.to_hex_string(), "#ff0000");
+    /// ```
+    pub fn from_okhsl(h: f32, s: f32, l: f32) -> Color {
+        Color::from_okhsla(h, s, l, 1.0)
+    }
+
+    #[cfg(feature = "okhsl")]
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_okhsla(h: f32, s: f32, l: f32, alpha: f32) -> Color {
+        let [r, g, b] = okhsl_to_linear_srgb(normalize_angle(h), clamp0_1(s), clamp0_1(l));
+        Color::from_linear_rgba(clamp0_1(r), clamp0_1(g), clamp0_1(b), clamp0_1(alpha))
+    }
+
+    #[cfg(feature = "okhsv")]
+    /// Create color from [Okhsv](https://bottosson.github.io/posts/colorpicker/#hsv-2), a hue,
+    /// saturation and value color space based on Oklab. `s = 1, v = 1` is the most vivid sRGB
+    /// color of the hue.
+    ///
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `v`: Value [0..1]
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_okhsv(29.23, 1.0, 1.0);
+    /// assert_eq!(c.to_hex_string(), "#ff0000");
+    /// ```
+    pub fn from_okhsv(h: f32, s: f32, v: f32) -> Color {
+        Color::from_okhsva(h, s, v, 1.0)
+    }
+
+    #[cfg(feature = "okhsv")]
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `v`: Value [0..1]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_okhsva(h: f32, s: f32, v: f32, alpha: f32) -> Color {
+        let [r, g, b] = okhsv_to_linear_srgb(normalize_angle(h), clamp0_1(s), clamp0_1(v));
+        Color::from_linear_rgba(clamp0_1(r), clamp0_1(g), clamp0_1(b), clamp0_1(alpha))
+    }
+
     #[cfg(feature = "lab")]
     /// Arguments:
     ///
@@ -220,20 +503,118 @@ impl Color {
     /// * `b`: Distance along the `b` axis
     /// * `alpha`: Alpha [0..1]
     pub fn from_lab(l: f32, a: f32, b: f32, alpha: f32) -> Color {
-        let [r, g, b] = lab::Lab {
-            l: l as f32,
-            a: a as f32,
-            b: b as f32,
-        }
-        .to_rgb_normalized();
-        Color::from_rgba(r as f32, g as f32, b as f32, alpha)
+        let [r, g, b] = lab::Lab { l, a, b }.to_rgb_normalized();
+        Color::from_rgba(r, g, b, alpha)
     }
 
     #[cfg(feature = "lab")]
     /// Returns: `(l, a, b, alpha)`
     pub fn to_lab(&self) -> (f32, f32, f32, f32) {
-        let lab = lab::Lab::from_rgb_normalized(&[self.r as f32, self.g as f32, self.b as f32]);
-        (lab.l as f32, lab.a as f32, lab.b as f32, self.a)
+        let lab = lab::Lab::from_rgb_normalized(&[self.r, self.g, self.b]);
+        (lab.l, lab.a, lab.b, self.a)
+    }
+
+    /// Create color from CIE Luv (D65 white point).
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Lightness [0..100]
+    /// * `u`: Distance along the `u` axis
+    /// * `v`: Distance along the `v` axis
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_luv(l: f32, u: f32, v: f32, alpha: f32) -> Color {
+        let (x, y, z) = luv_to_xyz(l, u, v);
+        Color::from_xyz_d65(x, y, z, alpha)
+    }
+
+    /// Returns: `(l, u, v, alpha)`, CIE Luv (D65 white point), lightness [0..100]
+    pub fn to_luv(&self) -> (f32, f32, f32, f32) {
+        let (x, y, z) = self.to_xyz_d65();
+        let (l, u, v) = xyz_to_luv(x, y, z);
+        (l, u, v, self.a)
+    }
+
+    #[cfg(feature = "wide-gamut")]
+    /// Create color from [Adobe RGB (1998)](https://www.w3.org/TR/css-color-4/#predefined-a98-rgb).
+    ///
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..1]
+    /// * `g`: Green value [0..1]
+    /// * `b`: Blue value [0..1]
+    pub fn from_a98_rgb(r: f32, g: f32, b: f32) -> Color {
+        Color::from_a98_rgba(r, g, b, 1.0)
+    }
+
+    #[cfg(feature = "wide-gamut")]
+    /// Create color from [Adobe RGB (1998)](https://www.w3.org/TR/css-color-4/#predefined-a98-rgb).
+    ///
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..1]
+    /// * `g`: Green value [0..1]
+    /// * `b`: Blue value [0..1]
+    /// * `a`: Alpha value [0..1]
+    pub fn from_a98_rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color::convert_from(ColorSpace::A98Rgb, r, g, b, a)
+    }
+
+    #[cfg(feature = "wide-gamut")]
+    /// Returns: `(r, g, b, a)`, Adobe RGB (1998)
+    ///
+    /// Colors are not clamped; sRGB colors are always inside the Adobe RGB gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let (r, g, b, _) = Color::from_rgb(1.0, 0.0, 0.0).to_a98_rgb();
+    /// assert_eq!([r, g, b].map(|t| (t * 1e3).round() / 1e3), [0.859, 0.0, 0.0]);
+    /// ```
+    pub fn to_a98_rgb(&self) -> (f32, f32, f32, f32) {
+        self.convert_to(ColorSpace::A98Rgb)
+    }
+
+    #[cfg(feature = "lab")]
+    /// Create color from the values of Photoshop's Lab sliders.
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Lightness [0..100]
+    /// * `a`: Distance along the `a` axis [-128..127]
+    /// * `b`: Distance along the `b` axis [-128..127]
+    ///
+    /// Values outside of the slider ranges are clamped. Photoshop's Lab uses the D50 white
+    /// point, unlike [`from_lab()`](#method.from_lab) which uses D65.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_photoshop_lab(54.29, 80.8, 69.89);
+    /// assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+    /// ```
+    pub fn from_photoshop_lab(l: f32, a: f32, b: f32) -> Color {
+        let (x, y, z) = lab_to_xyz_d50(
+            l.clamp(0.0, 100.0),
+            a.clamp(-128.0, 127.0),
+            b.clamp(-128.0, 127.0),
+        );
+        Color::convert_from(ColorSpace::XyzD50, x, y, z, 1.0)
+    }
+
+    #[cfg(feature = "lab")]
+    /// Returns: `(l, a, b, alpha)` in the ranges of Photoshop's Lab sliders, `l` [0..100],
+    /// `a` and `b` [-128..127], using the D50 white point.
+    pub fn to_photoshop_lab(&self) -> (f32, f32, f32, f32) {
+        let (x, y, z, alpha) = self.convert_to(ColorSpace::XyzD50);
+        let (l, a, b) = xyz_d50_to_lab(x, y, z);
+        (
+            l.clamp(0.0, 100.0),
+            a.clamp(-128.0, 127.0),
+            b.clamp(-128.0, 127.0),
+            alpha,
+        )
     }
 
     #[cfg(feature = "lab")]
@@ -257,25 +638,15 @@ impl Color {
     /// * `h`: Hue angle in radians
     /// * `alpha`: Alpha [0..1]
     pub fn from_lch(l: f32, c: f32, h: f32, alpha: f32) -> Color {
-        let [r, g, b] = lab::LCh {
-            l: l as f32,
-            c: c as f32,
-            h: h as f32,
-        }
-        .to_lab()
-        .to_rgb_normalized();
-        Color::from_rgba(r as f32, g as f32, b as f32, alpha)
+        let [r, g, b] = lab::LCh { l, c, h }.to_lab().to_rgb_normalized();
+        Color::from_rgba(r, g, b, alpha)
     }
 
     #[cfg(feature = "lab")]
     /// Returns: `(l, c, h, alpha)`
     pub fn to_lch(&self) -> (f32, f32, f32, f32) {
-        let lch = lab::LCh::from_lab(lab::Lab::from_rgb_normalized(&[
-            self.r as f32,
-            self.g as f32,
-            self.b as f32,
-        ]));
-        (lch.l as f32, lch.c as f32, lch.h as f32, self.a)
+        let lch = lab::LCh::from_lab(lab::Lab::from_rgb_normalized(&[self.r, self.g, self.b]));
+        (lch.l, lch.c, lch.h, self.a)
     }
 
     #[cfg(feature = "lab")]
@@ -312,167 +683,2665 @@ impl Color {
         parse(s.as_ref())
     }
 
-    /// Returns: `(r, g, b, a)`
+    /// Create color from CSS color string in bytes, e.g. read directly from a file or network
+    /// buffer, without converting it to `&str` first.
     ///
-    /// * Red, green, blue and alpha in the range [0..1]
-    pub fn rgba(&self) -> (f32, f32, f32, f32) {
-        (self.r, self.g, self.b, self.a)
+    /// CSS color strings are ASCII, so anything else (including invalid UTF-8) is rejected.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::parse_from_bytes(b"#ff0000")?;
+    /// assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+    ///
+    /// assert!(Color::parse_from_bytes(b"#ff\xff00").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_from_bytes(s: &[u8]) -> Result<Color, ParseColorError> {
+        match std::str::from_utf8(s) {
+            Ok(s) if s.is_ascii() => parse(s),
+            _ => Err(ParseColorError::InvalidUnknown),
+        }
     }
 
-    /// Returns: `(r, g, b, a)`
+    /// Create color from a CSS color function name and its numeric arguments.
     ///
-    /// * Red, green, blue and alpha in the range [0..255]
-    pub fn rgba_u8(&self) -> (u8, u8, u8, u8) {
-        (
-            (self.r * 255.0).round() as u8,
-            (self.g * 255.0).round() as u8,
-            (self.b * 255.0).round() as u8,
-            (self.a * 255.0).round() as u8,
-        )
+    /// `args` has 3 components, optionally followed by alpha [0..1]:
+    ///
+    /// * `rgb`, `rgba`: red, green, blue [0..255]
+    /// * `hsl`, `hsla`: hue (degrees), saturation [0..1], lightness [0..1]
+    /// * `hwb`, `hwba`: hue (degrees), whiteness [0..1], blackness [0..1]
+    /// * `hsv`, `hsva`: hue (degrees), saturation [0..1], value [0..1]
+    /// * `lab`: lightness [0..100], a, b (requires the `lab` feature)
+    /// * `lch`: lightness [0..100], chroma, hue (degrees) (requires the `lab` feature)
+    /// * `oklab`: lightness [0..1], a, b
+    /// * `oklch`: lightness [0..1], chroma, hue (degrees)
+    ///
+    /// The function name is case-insensitive.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_css_function_name("rgb", &[255.0, 102.0, 0.0])?;
+    /// assert_eq!(c.to_hex_string(), "#ff6600");
+    ///
+    /// let c = Color::from_css_function_name("hsla", &[120.0, 1.0, 0.5, 0.5])?;
+    /// assert_eq!(c.rgba_u8(), (0, 255, 0, 128));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css_function_name(name: &str, args: &[f32]) -> Result<Color, ParseColorError> {
+        let f = ColorFunction::from_name(&name.trim().to_lowercase())
+            .ok_or(ParseColorError::InvalidFunction)?;
+
+        let (x, y, z, alpha) = match *args {
+            [x, y, z] => (x, y, z, 1.0),
+            [x, y, z, alpha] => (x, y, z, alpha),
+            _ => return Err(f.error()),
+        };
+
+        // rgb channels are in the range [0..255]
+        if f == ColorFunction::Rgb {
+            return Ok(f.to_color(x / 255.0, y / 255.0, z / 255.0, alpha));
+        }
+        Ok(f.to_color(x, y, z, alpha))
     }
 
-    /// Returns: `(h, s, v, a)`
+    /// Get the default value of a CSS [system color](https://www.w3.org/TR/css-color-4/#css-system-colors),
+    /// e.g. `Canvas` or `LinkText` (case-insensitive). The values match Chrome on a standard light theme.
     ///
-    /// * `h`: Hue angle [0..360]
-    /// * `s`: Saturation [0..1]
-    /// * `v`: Value [0..1]
-    /// * `a`: Alpha [0..1]
-    pub fn to_hsva(&self) -> (f32, f32, f32, f32) {
-        let (h, s, v) = rgb_to_hsv(self.r, self.g, self.b);
-        (h, s, v, self.a)
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_css_system_color("Canvas"), Some(Color::WHITE));
+    /// assert_eq!(Color::from_css_system_color("red"), None);
+    /// ```
+    #[cfg(feature = "system-colors")]
+    pub fn from_css_system_color(name: &str) -> Option<Color> {
+        crate::system_colors::default_system_color(&name.trim().to_lowercase())
     }
 
-    /// Returns: `(h, s, l, a)`
+    /// Parse CSS color string, leaving [system colors](https://www.w3.org/TR/css-color-4/#css-system-colors)
+    /// unresolved.
     ///
-    /// * `h`: Hue angle [0..360]
-    /// * `s`: Saturation [0..1]
-    /// * `l`: Lightness [0..1]
-    /// * `a`: Alpha [0..1]
-    pub fn to_hsla(&self) -> (f32, f32, f32, f32) {
-        let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
-        (h, s, l, self.a)
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, CssSystemColor};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// assert_eq!(
+    ///     Color::from_css_system_keyword("buttonface")?,
+    ///     CssSystemColor::System("ButtonFace".into())
+    /// );
+    /// assert_eq!(
+    ///     Color::from_css_system_keyword("#fff")?,
+    ///     CssSystemColor::Resolved(Color::WHITE)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "system-colors")]
+    pub fn from_css_system_keyword(
+        keyword: &str,
+    ) -> Result<crate::CssSystemColor, ParseColorError> {
+        let keyword = keyword.trim();
+        if let Some(name) = crate::system_colors::system_color_name(keyword) {
+            return Ok(crate::CssSystemColor::System(name.into()));
+        }
+        parse(keyword).map(crate::CssSystemColor::Resolved)
     }
 
-    /// Returns: `(h, w, b, a)`
+    /// Resolve a CSS [system color](https://www.w3.org/TR/css-color-4/#css-system-colors) using
+    /// `resolver`, falling back to the default value.
+    #[cfg(feature = "system-colors")]
+    pub fn from_css_system_color_with<R: crate::SystemColorResolver + ?Sized>(
+        name: &str,
+        resolver: &R,
+    ) -> Option<Color> {
+        let name = name.trim().to_lowercase();
+        resolver
+            .resolve(&name)
+            .or_else(|| crate::system_colors::default_system_color(&name))
+    }
+
+    /// Like [`Color::from_html()`], but the error includes the failed string and `context`,
+    /// a label describing where the string came from.
     ///
-    /// * `h`: Hue angle [0..360]
-    /// * `w`: Whiteness [0..1]
-    /// * `b`: Blackness [0..1]
-    /// * `a`: Alpha [0..1]
-    pub fn to_hwba(&self) -> (f32, f32, f32, f32) {
-        let (h, w, b) = rgb_to_hwb(self.r, self.g, self.b);
-        (h, w, b, self.a)
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let err = Color::from_html_with_error_context("bad_value", "background-color property")
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "Failed to parse color 'bad_value' in background-color property"
+    /// );
+    /// ```
+    pub fn from_html_with_error_context(
+        s: &str,
+        context: &str,
+    ) -> Result<Color, ParseColorContextError> {
+        parse(s).map_err(|kind| ParseColorContextError {
+            value: s.to_string(),
+            context: context.to_string(),
+            kind,
+        })
     }
 
-    /// Returns: `(r, g, b, a)`
+    #[cfg(feature = "css5")]
+    /// Create color using [CSS Color Level 5 relative color syntax](https://www.w3.org/TR/css-color-5/#relative-colors)
+    /// with `base` as the origin color.
     ///
-    /// * Red, green, blue and alpha in the range [0..1]
-    pub fn to_linear_rgba(&self) -> (f32, f32, f32, f32) {
-        fn to_linear(x: f32) -> f32 {
-            if x >= 0.04045 {
-                return ((x + 0.055) / 1.055).powf(2.4);
+    /// `expression` is a color function without the `from <color>` part, e.g.
+    /// `hsl(h s calc(l * 0.8))`. Channel keywords (`r`, `g`, `b`, `h`, `s`, `l`, `alpha`, etc.
+    /// depending on the function) take their values from `base`, and `calc()` supports
+    /// `+ - * /` and parentheses.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let base = Color::from_rgb(1.0, 0.0, 0.0);
+    ///
+    /// let c = Color::from_css_color_level5_relative(&base, "hsl(h s calc(l * 0.8))")?;
+    /// assert_eq!(c.rgba_u8(), (204, 0, 0, 255));
+    ///
+    /// let c = Color::from_css_color_level5_relative(&base, "rgb(r g 255 / calc(alpha / 2))")?;
+    /// assert_eq!(c.rgba_u8(), (255, 0, 255, 128));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css_color_level5_relative(
+        base: &Color,
+        expression: &str,
+    ) -> Result<Color, ParseColorError> {
+        crate::parser::parse_relative_to(base, expression)
+    }
+
+    /// Create color from an SVG color attribute value such as `fill` or `stroke`.
+    ///
+    /// `none` returns a fully transparent color, since nothing is painted. `inherit` can not be
+    /// resolved without the parent element and returns [`ParseColorError::InvalidUnknown`]; use
+    /// [`parse_svg_color_attribute()`](fn.parse_svg_color_attribute.html) to handle it.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_svg_color_attribute("#00f")?;
+    /// assert_eq!(c.rgba_u8(), (0, 0, 255, 255));
+    ///
+    /// let c = Color::from_svg_color_attribute("none")?;
+    /// assert_eq!(c.rgba_u8(), (0, 0, 0, 0));
+    ///
+    /// assert!(Color::from_svg_color_attribute("inherit").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_svg_color_attribute(s: &str) -> Result<Color, ParseColorError> {
+        match parse_svg_color_attribute(s)? {
+            SvgColor::Color(c) => Ok(c),
+            SvgColor::None => Ok(Color::from_rgba(0.0, 0.0, 0.0, 0.0)),
+            SvgColor::Inherit => Err(ParseColorError::InvalidUnknown),
+        }
+    }
+
+    /// Create color from a single item of a CSS gradient color stop list, e.g. `red` or
+    /// `red 40%`, ignoring its positions.
+    ///
+    /// A color hint (a bare percentage such as `40%`) returns
+    /// [`ParseColorError::UnexpectedColorHint`] rather than a parse error. Use
+    /// [`parse_gradient_stops()`](fn.parse_gradient_stops.html) to parse a whole list.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ParseColorError};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_css_image_color_hint("#f00 20% 40%")?;
+    /// assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+    ///
+    /// assert_eq!(
+    ///     Color::from_css_image_color_hint("40%"),
+    ///     Err(ParseColorError::UnexpectedColorHint)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css_image_color_hint(s: &str) -> Result<Color, ParseColorError> {
+        match parse_stop(s)?.0 {
+            GradientStop::Color(c, _) => Ok(c),
+            GradientStop::Hint(_) => Err(ParseColorError::UnexpectedColorHint),
+        }
+    }
+
+    /// Create color from a [CSS Color Level 4](https://www.w3.org/TR/css-color-4/#hex-notation)
+    /// hexadecimal string (the `#` prefix is optional).
+    ///
+    /// All four lengths are accepted: `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa`. Other lengths
+    /// return [`ParseColorError::InvalidHexLength`], invalid digits return
+    /// [`ParseColorError::InvalidInt`] with the integer parse error as its
+    /// [`source()`](std::error::Error::source).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ParseColorError};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_css4_hex("#f008")?;
+    /// assert_eq!(c.rgba_u8(), (255, 0, 0, 136));
+    ///
+    /// let c = Color::from_css4_hex("ff000080")?;
+    /// assert_eq!(c.rgba_u8(), (255, 0, 0, 128));
+    ///
+    /// assert_eq!(Color::from_css4_hex("#ff000"), Err(ParseColorError::InvalidHexLength(5)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css4_hex(s: &str) -> Result<Color, ParseColorError> {
+        let s = s.trim();
+        let s = s.strip_prefix('#').unwrap_or(s);
+
+        match s.chars().count() {
+            3 | 4 | 6 | 8 => parse_hex(s),
+            n => Err(ParseColorError::InvalidHexLength(n)),
+        }
+    }
+
+    /// Create color from a CSS 4-digit hexadecimal string `#rgba` (the `#` prefix is optional).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_css_4_digit_hex("#8fff")?;
+    /// assert_eq!(c.rgba_u8(), (136, 255, 255, 255));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css_4_digit_hex(s: &str) -> Result<Color, ParseColorError> {
+        let s = s.trim();
+        let s = s.strip_prefix('#').unwrap_or(s);
+        let digits = s
+            .chars()
+            .map(|c| c.to_digit(16))
+            .collect::<Option<Vec<_>>>();
+
+        match digits.as_deref() {
+            Some(&[r, g, b, a]) => {
+                let v = |d: u32| (d * 17) as u8;
+                Ok(Color::from_rgba_u8(v(r), v(g), v(b), v(a)))
             }
-            x / 12.92
+            _ => Err(ParseColorError::InvalidHex),
         }
-        (
-            to_linear(self.r),
-            to_linear(self.g),
-            to_linear(self.b),
-            self.a,
-        )
+    }
+
+    /// Create color from a 6-digit hexadecimal RGB string `rrggbb` (the `#` prefix is optional)
+    /// and a separate 2-digit hexadecimal alpha string `aa`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_rgba_hex_pair("ff6600", "80")?;
+    /// assert_eq!(c.rgba_u8(), (255, 102, 0, 128));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_rgba_hex_pair(rgb_hex: &str, alpha_hex: &str) -> Result<Color, ParseColorError> {
+        let rgb_hex = rgb_hex.trim();
+        let rgb_hex = rgb_hex.strip_prefix('#').unwrap_or(rgb_hex);
+        let alpha_hex = alpha_hex.trim();
+
+        if rgb_hex.len() != 6
+            || alpha_hex.len() != 2
+            || !rgb_hex
+                .chars()
+                .chain(alpha_hex.chars())
+                .all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(ParseColorError::InvalidHex);
+        }
+
+        let v = |s: &str| u8::from_str_radix(s, 16).map_err(|_| ParseColorError::InvalidHex);
+        Ok(Color::from_rgba_u8(
+            v(&rgb_hex[0..2])?,
+            v(&rgb_hex[2..4])?,
+            v(&rgb_hex[4..6])?,
+            v(alpha_hex)?,
+        ))
+    }
+
+    /// Create color from hexadecimal digit pairs, one `(high_nibble, low_nibble)` pair per channel.
+    ///
+    /// Each nibble is a digit value in the range [0..15]. Accepts 3 (RGB) or 4 (RGBA) pairs.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_css_hex_digit_pairs(&[(0xf, 0xf), (0x6, 0x6), (0x0, 0x0)])?;
+    /// assert_eq!(c.to_hex_string(), "#ff6600");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css_hex_digit_pairs(pairs: &[(u8, u8)]) -> Result<Color, ParseColorError> {
+        if (pairs.len() != 3 && pairs.len() != 4) || pairs.iter().any(|&(h, l)| h > 15 || l > 15) {
+            return Err(ParseColorError::InvalidHex);
+        }
+        let v = |i: usize| pairs.get(i).map_or(255, |&(h, l)| h << 4 | l);
+        Ok(Color::from_rgba_u8(v(0), v(1), v(2), v(3)))
+    }
+
+    /// Create color from CSS color string, accepting only the syntax forms enabled in `opts`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, CssParseOptions};
+    ///
+    /// let opts = CssParseOptions {
+    ///     allow_css4: false,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Color::from_css_string_with_options("rgb(255, 0, 0)", opts).is_ok());
+    /// assert!(Color::from_css_string_with_options("rgb(255 0 0 / 50%)", opts).is_err());
+    /// ```
+    pub fn from_css_string_with_options(
+        s: &str,
+        opts: CssParseOptions,
+    ) -> Result<Color, ParseColorError> {
+        parse_with_options(s, &opts)
+    }
+
+    #[cfg(feature = "rand")]
+    /// Generate a random opaque color, with red, green and blue uniformly distributed in
+    /// [0..1). Same as `rng.gen::<Color>()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::random(&mut rand::thread_rng());
+    /// assert_eq!(c.a, 1.0);
+    /// ```
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Color {
+        rng.gen()
+    }
+
+    #[cfg(feature = "rand")]
+    /// Generate a random color, with red, green, blue and alpha uniformly distributed in
+    /// [0..1).
+    pub fn random_with_alpha<R: Rng + ?Sized>(rng: &mut R) -> Color {
+        Color::from_rgba(rng.gen(), rng.gen(), rng.gen(), rng.gen())
+    }
+
+    /// Returns: `(r, g, b, a)`
+    ///
+    /// * Red, green, blue and alpha in the range [0..1]
+    pub fn rgba(&self) -> (f32, f32, f32, f32) {
+        (self.r, self.g, self.b, self.a)
     }
 
     /// Returns: `(r, g, b, a)`
     ///
     /// * Red, green, blue and alpha in the range [0..255]
-    pub fn to_linear_rgba_u8(&self) -> (u8, u8, u8, u8) {
-        let (r, g, b, a) = self.to_linear_rgba();
+    pub fn rgba_u8(&self) -> (u8, u8, u8, u8) {
         (
-            (r * 255.0).round() as u8,
-            (g * 255.0).round() as u8,
-            (b * 255.0).round() as u8,
-            (a * 255.0).round() as u8,
+            (self.r * 255.0).round() as u8,
+            (self.g * 255.0).round() as u8,
+            (self.b * 255.0).round() as u8,
+            (self.a * 255.0).round() as u8,
         )
     }
 
-    /// Returns: `(l, a, b, alpha)`
-    pub fn to_oklaba(&self) -> (f32, f32, f32, f32) {
-        let (r, g, b, _) = self.to_linear_rgba();
-        let l_ = (0.4121656120 * r + 0.5362752080 * g + 0.0514575653 * b).cbrt();
-        let m_ = (0.2118591070 * r + 0.6807189584 * g + 0.1074065790 * b).cbrt();
-        let s_ = (0.0883097947 * r + 0.2818474174 * g + 0.6302613616 * b).cbrt();
-        let l = 0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_;
-        let a = 1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_;
-        let b = 0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_;
-        (l, a, b, self.a)
+    /// Returns: `[r, g, b, a]`
+    ///
+    /// * Red, green, blue and alpha in the range [0..1]
+    pub fn to_array(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
     }
 
-    /// Get the RGB hexadecimal color string.
-    pub fn to_hex_string(&self) -> String {
-        let (r, g, b, a) = self.rgba_u8();
+    /// Convert to the double precision [`Color64`].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, Color64};
+    ///
+    /// let c = Color::from_rgb(1.0, 0.5, 0.0);
+    /// assert_eq!(c.to_color64(), Color64::from_rgb(1.0, 0.5, 0.0));
+    /// ```
+    pub fn to_color64(&self) -> Color64 {
+        Color64::from_rgba(self.r as f64, self.g as f64, self.b as f64, self.a as f64)
+    }
 
-        if a < 255 {
-            return format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+    /// Get a channel by index: `0` red, `1` green, `2` blue, `3` alpha.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 3.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba(0.1, 0.2, 0.3, 0.4);
+    /// let channels = (0..4).map(|i| c.channel(i)).collect::<Vec<_>>();
+    /// assert_eq!(channels, [0.1, 0.2, 0.3, 0.4]);
+    /// ```
+    pub fn channel(&self, index: usize) -> f32 {
+        match index {
+            0 => self.r,
+            1 => self.g,
+            2 => self.b,
+            3 => self.a,
+            _ => panic!("channel index out of bounds: {} (expected 0..4)", index),
         }
+    }
 
-        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    /// Returns a new color with the channel at `index` (see [`Color::channel()`]) set to `v`,
+    /// clamped to [0..1].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 3.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_channel(3, 0.5);
+    /// assert_eq!(c.rgba(), (1.0, 0.0, 0.0, 0.5));
+    /// ```
+    pub fn set_channel(&self, index: usize, v: f32) -> Color {
+        let mut arr = self.to_array();
+        match arr.get_mut(index) {
+            Some(c) => *c = clamp0_1(v),
+            None => panic!("channel index out of bounds: {} (expected 0..4)", index),
+        }
+        Color::from_array(arr)
     }
 
-    /// Get the CSS `rgb()` format string.
-    pub fn to_rgb_string(&self) -> String {
-        let (r, g, b, _) = self.rgba_u8();
+    /// Returns: `[r, g, b]`
+    ///
+    /// * Red, green and blue in the range [0..1]
+    pub fn to_rgb_array(&self) -> [f32; 3] {
+        [self.r, self.g, self.b]
+    }
 
-        if self.a < 1.0 {
-            return format!("rgba({},{},{},{})", r, g, b, self.a);
-        }
+    /// Returns: `(h, s, v, a)`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `v`: Value [0..1]
+    /// * `a`: Alpha [0..1]
+    ///
+    /// Achromatic colors (`r == g == b`) have hue and saturation 0.
+    pub fn to_hsva(&self) -> (f32, f32, f32, f32) {
+        let (h, s, v) = rgb_to_hsv(self.r, self.g, self.b);
+        (h, s, v, self.a)
+    }
 
-        format!("rgb({},{},{})", r, g, b)
+    /// Returns: `(h, s, l, a)`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    /// * `a`: Alpha [0..1]
+    ///
+    /// Achromatic colors (`r == g == b`) have hue and saturation 0.
+    pub fn to_hsla(&self) -> (f32, f32, f32, f32) {
+        let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
+        (h, s, l, self.a)
+    }
+
+    #[cfg(feature = "okhsl")]
+    /// Returns: `(h, s, l)` in the [Okhsl](https://bottosson.github.io/posts/colorpicker/#hsl-2)
+    /// color space, alpha is ignored.
+    ///
+    /// * `h`: Hue angle [0..360], 0 for achromatic colors
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    pub fn to_okhsl(&self) -> (f32, f32, f32) {
+        let (r, g, b, _) = self.to_linear_rgba();
+        linear_srgb_to_okhsl(r, g, b)
+    }
+
+    #[cfg(feature = "okhsv")]
+    /// Returns: `(h, s, v)` in the [Okhsv](https://bottosson.github.io/posts/colorpicker/#hsv-2)
+    /// color space, alpha is ignored.
+    ///
+    /// * `h`: Hue angle [0..360], 0 for achromatic colors
+    /// * `s`: Saturation [0..1]
+    /// * `v`: Value [0..1]
+    pub fn to_okhsv(&self) -> (f32, f32, f32) {
+        let (r, g, b, _) = self.to_linear_rgba();
+        linear_srgb_to_okhsv(r, g, b)
+    }
+
+    /// Returns: `(h, w, b, a)`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `w`: Whiteness [0..1]
+    /// * `b`: Blackness [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn to_hwba(&self) -> (f32, f32, f32, f32) {
+        let (h, w, b) = rgb_to_hwb(self.r, self.g, self.b);
+        (h, w, b, self.a)
+    }
+
+    /// Returns: `(r, g, b, a)`
+    ///
+    /// * Red, green, blue and alpha in the range [0..1]
+    pub fn to_linear_rgba(&self) -> (f32, f32, f32, f32) {
+        (
+            srgb_expansion(self.r),
+            srgb_expansion(self.g),
+            srgb_expansion(self.b),
+            self.a,
+        )
+    }
+
+    /// Returns: `[r, g, b, a]` as IEEE 754 half-precision float bits, e.g. for writing to f16
+    /// GPU textures. The color components are linear sRGB.
+    ///
+    /// This is the inverse of [`Color::from_rgba_f16_array()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_rgba_f16_array(), [0x3c00, 0x0000, 0x0000, 0x3800]);
+    /// ```
+    pub fn to_rgba_f16_array(&self) -> [u16; 4] {
+        let (r, g, b, a) = self.to_linear_rgba();
+        [r, g, b, a].map(f32_to_f16)
+    }
+
+    /// Returns: `[r, g, b, a]` as IEEE 754 half-precision float bits, rounded to nearest even.
+    /// Unlike [`Color::to_rgba_f16_array()`] the components are used as is, without converting
+    /// to linear sRGB. Out of range values become infinity, NaN stays NaN.
+    ///
+    /// This is the inverse of [`Color::from_f16_array()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba(1.0, 0.0, 0.5, 1.0);
+    /// assert_eq!(c.to_f16_array(), [0x3c00, 0x0000, 0x3800, 0x3c00]);
+    /// ```
+    pub fn to_f16_array(&self) -> [u16; 4] {
+        self.to_array().map(f32_to_f16)
+    }
+
+    /// Get the color as a `0xRRGGBB` integer, alpha is ignored.
+    ///
+    /// This is the inverse of [`Color::from_24bit_integer()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb_u8(255, 102, 0);
+    /// assert_eq!(c.to_24bit_integer(), 0xff6600);
+    /// ```
+    pub fn to_24bit_integer(&self) -> u32 {
+        let (r, g, b, _) = self.rgba_u8();
+        u32::from_be_bytes([0, r, g, b])
+    }
+
+    /// Returns: `(r, g, b, a)`
+    ///
+    /// * Red, green, blue and alpha in the range [0..255]
+    pub fn to_linear_rgba_u8(&self) -> (u8, u8, u8, u8) {
+        let (r, g, b, a) = self.to_linear_rgba();
+        (
+            (r * 255.0).round() as u8,
+            (g * 255.0).round() as u8,
+            (b * 255.0).round() as u8,
+            (a * 255.0).round() as u8,
+        )
+    }
+
+    /// Returns: `(l, a, b, alpha)`
+    #[allow(clippy::excessive_precision)]
+    pub fn to_oklaba(&self) -> (f32, f32, f32, f32) {
+        let (r, g, b, _) = self.to_linear_rgba();
+        let l_ = (0.4121656120 * r + 0.5362752080 * g + 0.0514575653 * b).cbrt();
+        let m_ = (0.2118591070 * r + 0.6807189584 * g + 0.1074065790 * b).cbrt();
+        let s_ = (0.0883097947 * r + 0.2818474174 * g + 0.6302613616 * b).cbrt();
+        let l = 0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_;
+        let a = 1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_;
+        let b = 0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_;
+        (l, a, b, self.a)
+    }
+
+    /// Convert this color into the given color space.
+    ///
+    /// Returns the three components of `space` followed by alpha. See [`ColorSpace`] for the
+    /// meaning and range of each component.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ColorSpace};
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.convert_to(ColorSpace::Hsl), c.to_hsla());
+    /// assert_eq!(c.convert_to(ColorSpace::OkLab), c.to_oklaba());
+    /// ```
+    pub fn convert_to(&self, space: ColorSpace) -> (f32, f32, f32, f32) {
+        let (x, y, z) = match space {
+            ColorSpace::Srgb => return self.rgba(),
+            ColorSpace::SrgbLinear => return self.to_linear_rgba(),
+            ColorSpace::Hsl => return self.to_hsla(),
+            ColorSpace::Hsv => return self.to_hsva(),
+            ColorSpace::Hwb => return self.to_hwba(),
+            ColorSpace::OkLab => return self.to_oklaba(),
+            ColorSpace::OkLch => {
+                let (l, a, b, alpha) = self.to_oklaba();
+                let (l, c, h) = oklab_to_oklch(l, a, b);
+                return (l, c, h, alpha);
+            }
+            #[cfg(feature = "lab")]
+            ColorSpace::Lab => return self.to_lab(),
+            #[cfg(feature = "lab")]
+            ColorSpace::Lch => return self.to_lch(),
+            ColorSpace::XyzD65 => self.to_xyz_d65(),
+            ColorSpace::XyzD50 => {
+                let (x, y, z) = self.to_xyz_d65();
+                mul_matrix(&XYZ_D65_TO_XYZ_D50, x, y, z)
+            }
+            ColorSpace::DisplayP3 => {
+                let (x, y, z) = self.to_xyz_d65();
+                let (r, g, b) = mul_matrix(&XYZ_D65_TO_LINEAR_P3, x, y, z);
+                (
+                    srgb_from_linear(r),
+                    srgb_from_linear(g),
+                    srgb_from_linear(b),
+                )
+            }
+            ColorSpace::HslUv => {
+                let (x, y, z) = self.to_xyz_d65();
+                xyz_to_hsluv(x, y, z)
+            }
+            ColorSpace::Rec2020 => {
+                let (x, y, z) = self.to_xyz_d65();
+                let (r, g, b) = mul_matrix(&XYZ_D65_TO_LINEAR_REC2020, x, y, z);
+                (
+                    rec2020_from_linear(r),
+                    rec2020_from_linear(g),
+                    rec2020_from_linear(b),
+                )
+            }
+            #[cfg(feature = "wide-gamut")]
+            ColorSpace::A98Rgb => {
+                let (x, y, z) = self.to_xyz_d65();
+                let (r, g, b) = mul_matrix(&XYZ_D65_TO_LINEAR_A98, x, y, z);
+                (a98_from_linear(r), a98_from_linear(g), a98_from_linear(b))
+            }
+        };
+        (x, y, z, self.a)
+    }
+
+    fn to_xyz_d65(&self) -> (f32, f32, f32) {
+        let (r, g, b, _) = self.to_linear_rgba();
+        mul_matrix(&LINEAR_SRGB_TO_XYZ_D65, r, g, b)
+    }
+
+    fn from_xyz_d65(x: f32, y: f32, z: f32, alpha: f32) -> Color {
+        let (r, g, b) = mul_matrix(&XYZ_D65_TO_LINEAR_SRGB, x, y, z);
+        Color::from_linear_rgba(r, g, b, alpha)
+    }
+
+    // Clamp the components to [0..1]
+    fn clamped(&self) -> Color {
+        Color::from_rgba(
+            clamp0_1(self.r),
+            clamp0_1(self.g),
+            clamp0_1(self.b),
+            clamp0_1(self.a),
+        )
+    }
+
+    // Inverse of `convert_to()`
+    pub(crate) fn convert_from(space: ColorSpace, x: f32, y: f32, z: f32, alpha: f32) -> Color {
+        match space {
+            ColorSpace::Srgb => Color::from_rgba(x, y, z, alpha),
+            ColorSpace::SrgbLinear => Color::from_linear_rgba(x, y, z, alpha),
+            ColorSpace::Hsl => Color::from_hsla(x, y, z, alpha),
+            ColorSpace::Hsv => Color::from_hsva(x, y, z, alpha),
+            ColorSpace::Hwb => Color::from_hwba(x, y, z, alpha),
+            ColorSpace::OkLab => Color::from_oklaba(x, y, z, alpha),
+            ColorSpace::OkLch => {
+                let (sin_h, cos_h) = z.to_radians().sin_cos();
+                Color::from_oklaba(x, y * cos_h, y * sin_h, alpha)
+            }
+            #[cfg(feature = "lab")]
+            ColorSpace::Lab => Color::from_lab(x, y, z, alpha),
+            #[cfg(feature = "lab")]
+            ColorSpace::Lch => Color::from_lch(x, y, z, alpha),
+            ColorSpace::XyzD65 => Color::from_xyz_d65(x, y, z, alpha),
+            ColorSpace::XyzD50 => {
+                let (x, y, z) = mul_matrix(&XYZ_D50_TO_XYZ_D65, x, y, z);
+                Color::from_xyz_d65(x, y, z, alpha)
+            }
+            ColorSpace::DisplayP3 => {
+                let (r, g, b) = (srgb_to_linear(x), srgb_to_linear(y), srgb_to_linear(z));
+                let (x, y, z) = mul_matrix(&LINEAR_P3_TO_XYZ_D65, r, g, b);
+                Color::from_xyz_d65(x, y, z, alpha)
+            }
+            ColorSpace::HslUv => {
+                let (x, y, z) = hsluv_to_xyz(x, y, z);
+                Color::from_xyz_d65(x, y, z, alpha)
+            }
+            ColorSpace::Rec2020 => {
+                let (r, g, b) = (
+                    rec2020_to_linear(x),
+                    rec2020_to_linear(y),
+                    rec2020_to_linear(z),
+                );
+                let (x, y, z) = mul_matrix(&LINEAR_REC2020_TO_XYZ_D65, r, g, b);
+                Color::from_xyz_d65(x, y, z, alpha)
+            }
+            #[cfg(feature = "wide-gamut")]
+            ColorSpace::A98Rgb => {
+                let (r, g, b) = (a98_to_linear(x), a98_to_linear(y), a98_to_linear(z));
+                let (x, y, z) = mul_matrix(&LINEAR_A98_TO_XYZ_D65, r, g, b);
+                Color::from_xyz_d65(x, y, z, alpha)
+            }
+        }
+    }
+
+    /// Get the Oklch chroma, the distance from the neutral axis in the Oklab color-space.
+    pub fn chroma_oklab(&self) -> f32 {
+        let (_, a, b, _) = self.to_oklaba();
+        (a * a + b * b).sqrt()
+    }
+
+    /// Get the [relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance)
+    /// as defined by WCAG, in the range [0..1]. Alpha is ignored.
+    pub fn relative_luminance(&self) -> f32 {
+        let (r, g, b, _) = self.clamped().to_linear_rgba();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Get the WCAG [contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between
+    /// this color and `other`, in the range [1..21]. Alpha is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let ratio = Color::BLACK.contrast_ratio(&Color::WHITE);
+    /// assert!((ratio - 21.0).abs() < 1e-4);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let l1 = self.relative_luminance();
+        let l2 = other.relative_luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    /// Returns `true` if the [contrast ratio](#method.contrast_ratio) between this color and
+    /// `other` meets [WCAG level AA](https://www.w3.org/TR/WCAG21/#contrast-minimum): at least
+    /// 4.5:1, or 3:1 for large text (18pt, or 14pt bold).
+    ///
+    /// The ratio is not rounded, e.g. 4.49:1 fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let gray = Color::from_rgb_u8(118, 118, 118); // 4.54:1 on white
+    /// assert!(gray.wcag_aa_compliant(&Color::WHITE, false));
+    /// assert!(!gray.wcag_aaa_compliant(&Color::WHITE, false));
+    /// ```
+    pub fn wcag_aa_compliant(&self, other: &Color, large_text: bool) -> bool {
+        let min = if large_text { 3.0 } else { 4.5 };
+        self.contrast_ratio(other) >= min
+    }
+
+    /// Returns `true` if the [contrast ratio](#method.contrast_ratio) between this color and
+    /// `other` meets [WCAG level AAA](https://www.w3.org/TR/WCAG21/#contrast-enhanced): at
+    /// least 7:1, or 4.5:1 for large text (18pt, or 14pt bold).
+    ///
+    /// The ratio is not rounded, e.g. 6.99:1 fails.
+    pub fn wcag_aaa_compliant(&self, other: &Color, large_text: bool) -> bool {
+        let min = if large_text { 4.5 } else { 7.0 };
+        self.contrast_ratio(other) >= min
+    }
+
+    /// Adjust the Oklch lightness in the direction that increases the contrast against
+    /// `background`, until [`Color::contrast_ratio()`] reaches `target_ratio`. Chroma and hue
+    /// are kept, the result is clamped to the sRGB gamut.
+    ///
+    /// Returns this color unchanged if it already has enough contrast, or `None` if the target
+    /// contrast is unachievable.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let text = Color::from_rgb(0.6, 0.6, 1.0);
+    /// let c = text.oklch_lightness_adjust_to_contrast(&Color::WHITE, 4.5).unwrap();
+    /// assert!(c.contrast_ratio(&Color::WHITE) >= 4.5);
+    ///
+    /// assert_eq!(text.oklch_lightness_adjust_to_contrast(&Color::WHITE, 22.0), None);
+    /// ```
+    pub fn oklch_lightness_adjust_to_contrast(
+        &self,
+        background: &Color,
+        target_ratio: f32,
+    ) -> Option<Color> {
+        if self.contrast_ratio(background) >= target_ratio {
+            return Some(self.clone());
+        }
+
+        let (l, c, h, alpha) = self.convert_to(ColorSpace::OkLch);
+        let with_lightness =
+            |l: f32| Color::convert_from(ColorSpace::OkLch, l, c, h, alpha).clamped();
+
+        // Darken against lighter backgrounds, lighten against darker ones.
+        let limit = if self.relative_luminance() <= background.relative_luminance() {
+            0.0
+        } else {
+            1.0
+        };
+
+        if with_lightness(limit).contrast_ratio(background) < target_ratio {
+            return None;
+        }
+
+        // Binary search for the smallest change reaching the target
+        let (mut lo, mut hi) = (l, limit);
+        for _ in 0..24 {
+            let mid = (lo + hi) / 2.0;
+            if with_lightness(mid).contrast_ratio(background) >= target_ratio {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        Some(with_lightness(hi))
+    }
+
+    /// Add `delta` to the Oklab lightness, clamped to [0..1], keeping the `a` and `b`
+    /// components. Unlike adjusting HSL lightness, this preserves the hue and chroma.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).adjust_lightness_oklab(-0.2);
+    /// assert_eq!(c.rgba_u8(), (180, 0, 0, 255));
+    /// ```
+    pub fn adjust_lightness_oklab(&self, delta: f32) -> Color {
+        let (l, a, b, alpha) = self.to_oklaba();
+        Color::from_oklaba(clamp0_1(l + delta), a, b, alpha)
+    }
+
+    /// Multiply the Oklch chroma by `factor`, keeping lightness and hue. The chroma is
+    /// clamped to >= 0; a `factor` greater than 1 may give colors outside of the sRGB gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).scale_chroma_oklch(0.0);
+    /// assert!(c.chroma_oklab() < 1e-4);
+    /// ```
+    pub fn scale_chroma_oklch(&self, factor: f32) -> Color {
+        let (l, c, h, alpha) = self.convert_to(ColorSpace::OkLch);
+        Color::convert_from(ColorSpace::OkLch, l, (c * factor).max(0.0), h, alpha)
+    }
+
+    /// Returns a new color with the Oklch lightness set to `l`, clamped to [0..1]. Chroma, hue
+    /// and alpha are kept; the result may be outside of the sRGB gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ColorSpace};
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_oklch_lightness(0.5);
+    /// assert!((c.convert_to(ColorSpace::OkLch).0 - 0.5).abs() < 1e-4);
+    /// ```
+    pub fn set_oklch_lightness(&self, l: f32) -> Color {
+        let (_, c, h, alpha) = self.convert_to(ColorSpace::OkLch);
+        Color::convert_from(ColorSpace::OkLch, clamp0_1(l), c, h, alpha)
+    }
+
+    /// Returns a new color with the Oklch chroma set to `c`, clamped to >= 0. Lightness, hue
+    /// and alpha are kept; the result may be outside of the sRGB gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_oklch_chroma(0.0);
+    /// assert!(c.chroma_oklab() < 1e-4);
+    /// ```
+    pub fn set_oklch_chroma(&self, c: f32) -> Color {
+        let (l, _, h, alpha) = self.convert_to(ColorSpace::OkLch);
+        Color::convert_from(ColorSpace::OkLch, l, c.max(0.0), h, alpha)
+    }
+
+    /// Returns a new color with the Oklch hue set to `h` degrees, normalized to [0..360).
+    /// Lightness, chroma and alpha are kept; the result may be outside of the sRGB gamut.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ColorSpace};
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_oklch_hue(-30.0);
+    /// assert!((c.convert_to(ColorSpace::OkLch).2 - 330.0).abs() < 1e-2);
+    /// ```
+    pub fn set_oklch_hue(&self, h: f32) -> Color {
+        let (l, c, _, alpha) = self.convert_to(ColorSpace::OkLch);
+        Color::convert_from(ColorSpace::OkLch, l, c, normalize_angle(h), alpha)
+    }
+
+    /// Returns a new color with the HSL hue set to `h` degrees, normalized to [0..360).
+    /// Saturation, lightness and alpha are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_hsl_hue(240.0);
+    /// assert_eq!(c.to_hex_string(), "#0000ff");
+    /// ```
+    pub fn set_hsl_hue(&self, h: f32) -> Color {
+        let (_, s, l, a) = self.to_hsla();
+        Color::from_hsla(normalize_angle(h), s, l, a)
+    }
+
+    /// Returns a new color with the HSL saturation set to `s`, clamped to [0..1]. Hue,
+    /// lightness and alpha are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_hsl_saturation(0.0);
+    /// assert_eq!(c.to_hex_string(), "#808080");
+    /// ```
+    pub fn set_hsl_saturation(&self, s: f32) -> Color {
+        let (h, _, l, a) = self.to_hsla();
+        Color::from_hsla(h, clamp0_1(s), l, a)
+    }
+
+    /// Returns a new color with the HSL lightness set to `l`, clamped to [0..1]. Hue,
+    /// saturation and alpha are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).set_hsl_lightness(0.2);
+    /// assert_eq!(c.to_hex_string(), "#660000");
+    /// ```
+    pub fn set_hsl_lightness(&self, l: f32) -> Color {
+        let (h, s, _, a) = self.to_hsla();
+        Color::from_hsla(h, s, clamp0_1(l), a)
+    }
+
+    /// Get the complementary color, with the HSL hue rotated by 180°. Saturation, lightness and
+    /// alpha are preserved, so achromatic colors (white, black and grays) are unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0).complementary();
+    /// assert_eq!(c.to_hex_string(), "#00ffff");
+    /// ```
+    pub fn complementary(&self) -> Color {
+        self.rotate_hue(180.0)
+    }
+
+    /// Get the two triadic partners of this color, with the HSL hue rotated by 120° and 240°.
+    /// Saturation, lightness and alpha are preserved.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let [c1, c2] = Color::from_rgb(1.0, 0.0, 0.0).triadic();
+    /// assert_eq!(c1.to_hex_string(), "#00ff00");
+    /// assert_eq!(c2.to_hex_string(), "#0000ff");
+    /// ```
+    pub fn triadic(&self) -> [Color; 2] {
+        [self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    /// Get the three partners of this color in a square (tetradic) harmony, with the HSL hue
+    /// rotated by 90°, 180° and 270°. Saturation, lightness and alpha are preserved.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let [c1, c2, c3] = Color::from_rgb(1.0, 0.0, 0.0).tetradic();
+    /// assert_eq!(c1.to_hex_string(), "#80ff00");
+    /// assert_eq!(c2.to_hex_string(), "#00ffff");
+    /// assert_eq!(c3.to_hex_string(), "#8000ff");
+    /// ```
+    pub fn tetradic(&self) -> [Color; 3] {
+        [90.0, 180.0, 270.0].map(|d| self.rotate_hue(d))
+    }
+
+    /// Get the two analogous colors of this color, with the HSL hue rotated by `-spread` and
+    /// `+spread` degrees. Saturation, lightness and alpha are preserved.
+    ///
+    /// `spread` is clamped to [0..180], so `0` returns two copies of this color and `180`
+    /// two copies of the complementary color. A typical value is `30`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let [c1, c2] = Color::from_rgb(1.0, 0.0, 0.0).analogous(30.0);
+    /// assert_eq!(c1.to_hex_string(), "#ff0080");
+    /// assert_eq!(c2.to_hex_string(), "#ff8000");
+    /// ```
+    pub fn analogous(&self, spread: f32) -> [Color; 2] {
+        let spread = spread.clamp(0.0, 180.0);
+        [self.rotate_hue(-spread), self.rotate_hue(spread)]
+    }
+
+    /// Get the split-complementary colors of this color, the analogous colors of the
+    /// complementary color, with the HSL hue rotated by `180 - spread` and `180 + spread` degrees.
+    /// Saturation, lightness and alpha are preserved.
+    ///
+    /// `spread` is clamped to [0..180] like in [`Color::analogous()`]. A typical value is `30`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let [c1, c2] = Color::from_rgb(1.0, 0.0, 0.0).split_complementary(30.0);
+    /// assert_eq!(c1.to_hex_string(), "#00ff80");
+    /// assert_eq!(c2.to_hex_string(), "#0080ff");
+    /// ```
+    pub fn split_complementary(&self, spread: f32) -> [Color; 2] {
+        let spread = spread.clamp(0.0, 180.0);
+        [
+            self.rotate_hue(180.0 - spread),
+            self.rotate_hue(180.0 + spread),
+        ]
+    }
+
+    // Rotate the HSL hue by `degrees`, preserving saturation, lightness and alpha.
+    fn rotate_hue(&self, degrees: f32) -> Color {
+        let (h, s, l, a) = self.to_hsla();
+        Color::from_hsla(normalize_angle(h + degrees), s, l, a)
+    }
+
+    /// Get the hue category of this color, from its Oklch hue angle: `"red"`, `"orange"`,
+    /// `"yellow"`, `"chartreuse"`, `"green"`, `"teal"`, `"cyan"`, `"azure"`, `"blue"`,
+    /// `"violet"`, `"magenta"` or `"rose"`.
+    ///
+    /// The boundaries lie halfway between the Oklch hues of the twelve sRGB colors with hue
+    /// angles 0°, 30°, 60°, ..., 330°. Achromatic colors have no meaningful hue, check
+    /// [`Color::chroma_oklab()`] first.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_rgb(1.0, 0.0, 0.0).oklch_hue_category(), "red");
+    /// assert_eq!(Color::from_rgb(1.0, 0.5, 0.0).oklch_hue_category(), "orange");
+    /// ```
+    pub fn oklch_hue_category(&self) -> &'static str {
+        const CATEGORIES: [(f32, &str); 12] = [
+            (16.0, "rose"),
+            (41.0, "red"),
+            (81.0, "orange"),
+            (123.0, "yellow"),
+            (139.0, "chartreuse"),
+            (147.0, "green"),
+            (173.0, "teal"),
+            (225.0, "cyan"),
+            (260.0, "azure"),
+            (279.0, "blue"),
+            (311.0, "violet"),
+            (345.0, "magenta"),
+        ];
+
+        let (_, _, h, _) = self.convert_to(ColorSpace::OkLch);
+        let h = normalize_angle(h);
+
+        CATEGORIES
+            .iter()
+            .find(|(end, _)| h < *end)
+            .map_or("rose", |(_, name)| name)
+    }
+
+    /// Get a short English description of this color, e.g. `"vivid red"`, `"light gray"` or
+    /// `"dark muted blue"`. Alpha is ignored.
+    ///
+    /// Colors with an HSL saturation below 10% are described as `black`, `white` or `gray`.
+    /// Otherwise the description is a lightness qualifier (`very dark`, `dark`, `light`,
+    /// `very light`, none for medium lightness), a saturation qualifier (`muted` below 40%,
+    /// `vivid` from 80%) and the hue: `red`, `orange`, `yellow`, `green`, `cyan`, `blue`,
+    /// `violet` or `magenta`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::from_rgb_u8(255, 0, 0).describe(), "vivid red");
+    /// assert_eq!(Color::from_rgb_u8(204, 204, 204).describe(), "light gray");
+    /// assert_eq!(Color::from_rgb_u8(0, 0, 128).describe(), "dark vivid blue");
+    /// ```
+    pub fn describe(&self) -> String {
+        let (h, s, l, _) = self.clamped().to_hsla();
+
+        let lightness = match l {
+            l if l < 0.15 => "very dark",
+            l if l < 0.35 => "dark",
+            l if l < 0.65 => "",
+            l if l < 0.85 => "light",
+            _ => "very light",
+        };
+
+        if s < 0.1 {
+            return match l {
+                l if l < 0.05 => "black".to_string(),
+                l if l > 0.95 => "white".to_string(),
+                _ if lightness.is_empty() => "gray".to_string(),
+                _ => format!("{} gray", lightness),
+            };
+        }
+
+        let saturation = match s {
+            s if s < 0.4 => "muted",
+            s if s < 0.8 => "",
+            _ => "vivid",
+        };
+
+        let hue = match h {
+            h if h < 15.0 => "red",
+            h if h < 45.0 => "orange",
+            h if h < 70.0 => "yellow",
+            h if h < 165.0 => "green",
+            h if h < 190.0 => "cyan",
+            h if h < 255.0 => "blue",
+            h if h < 285.0 => "violet",
+            h if h < 345.0 => "magenta",
+            _ => "red",
+        };
+
+        [lightness, saturation, hue]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns `true` if the red, green and blue components differ by at most `epsilon`,
+    /// i.e. the color is white, black or a shade of gray.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert!(Color::from_rgb_u8(128, 128, 128).is_achromatic(1e-6));
+    /// assert!(!Color::from_rgb_u8(255, 128, 128).is_achromatic(1e-6));
+    /// ```
+    pub fn is_achromatic(&self, epsilon: f32) -> bool {
+        (self.r - self.g).abs() <= epsilon
+            && (self.g - self.b).abs() <= epsilon
+            && (self.r - self.b).abs() <= epsilon
+    }
+
+    /// Returns `true` if the color appears black, i.e. its Oklab lightness is below `threshold`
+    /// (a typical value is `0.05`).
+    ///
+    /// Dark saturated colors may look black while having a nonzero RGB component.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert!(Color::from_rgb_u8(0, 0, 0).is_perceptually_black(0.05));
+    /// assert!(Color::from_rgb_u8(0, 0, 3).is_perceptually_black(0.05));
+    /// assert!(!Color::from_rgb_u8(3, 3, 3).is_perceptually_black(0.05));
+    /// ```
+    pub fn is_perceptually_black(&self, threshold: f32) -> bool {
+        self.to_oklaba().0 < threshold
+    }
+
+    /// Returns `true` if the color appears white, i.e. its Oklab lightness is above
+    /// `1.0 - threshold` (a typical value is `0.05`).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert!(Color::from_rgb_u8(255, 255, 255).is_perceptually_white(0.05));
+    /// assert!(Color::from_rgb_u8(255, 250, 240).is_perceptually_white(0.05));
+    /// assert!(!Color::from_rgb_u8(255, 0, 0).is_perceptually_white(0.05));
+    /// ```
+    pub fn is_perceptually_white(&self, threshold: f32) -> bool {
+        self.to_oklaba().0 > 1.0 - threshold
+    }
+
+    /// Returns `true` if the color is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        self.a >= 1.0 - f32::EPSILON
+    }
+
+    /// Returns `true` if the color is fully transparent.
+    pub fn is_transparent(&self) -> bool {
+        self.a <= f32::EPSILON
+    }
+
+    /// Returns `true` if the color is neither fully opaque nor fully transparent.
+    pub fn is_translucent(&self) -> bool {
+        !self.is_opaque() && !self.is_transparent()
+    }
+
+    /// Find the named color closest to this color, if it is within `threshold` Euclidean
+    /// distance in the Oklab color-space. Alpha is ignored.
+    ///
+    /// Returns the name and the exact value of the named color. If several names share the
+    /// same value (e.g. `aqua` and `cyan`), the alphabetically first one is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb_u8(250, 5, 3);
+    /// let (name, named) = c.is_close_to_named(0.02).unwrap();
+    /// assert_eq!(name, "red");
+    /// assert_eq!(named.rgba_u8(), (255, 0, 0, 255));
+    ///
+    /// assert_eq!(c.is_close_to_named(0.001), None);
+    /// ```
+    #[cfg(feature = "named-colors")]
+    pub fn is_close_to_named(&self, threshold: f32) -> Option<(&'static str, Color)> {
+        // sorted by name, so the first of equally close colors is the alphabetically first
+        let mut named: Vec<_> = NAMED_COLORS.entries().collect();
+        named.sort_unstable_by_key(|&(name, _)| *name);
+        let palette: Vec<_> = named
+            .iter()
+            .map(|&(_, &[r, g, b])| Color::from_rgb_u8(r, g, b))
+            .collect();
+
+        let (i, c, d) = self.nearest_oklab(&palette)?;
+        if d.sqrt() > threshold {
+            return None;
+        }
+        Some((named[i].0, c.clone()))
+    }
+
+    /// Find the palette color closest to this color, using Euclidean distance in the Oklab
+    /// color-space. Alpha is ignored.
+    ///
+    /// Returns the index and a reference to the palette entry, or `None` if `palette` is empty.
+    /// On ties, the first entry is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let palette = [Color::BLACK, Color::WHITE, Color::from_rgb(1.0, 0.0, 0.0)];
+    ///
+    /// let c = Color::from_rgb(0.9, 0.1, 0.2);
+    /// assert_eq!(c.nearest_in_palette_oklab(&palette), Some((2, &palette[2])));
+    /// assert_eq!(c.nearest_in_palette_oklab(&[]), None);
+    /// ```
+    pub fn nearest_in_palette_oklab<'a>(&self, palette: &'a [Color]) -> Option<(usize, &'a Color)> {
+        self.nearest_oklab(palette).map(|(i, c, _)| (i, c))
+    }
+
+    // The index of the palette color closest to this color in the Oklab color-space, the color
+    // and the squared distance. The first of equally close colors is returned.
+    fn nearest_oklab<'a>(&self, palette: &'a [Color]) -> Option<(usize, &'a Color, f32)> {
+        let (l, a, b, _) = self.to_oklaba();
+        let mut nearest: Option<(usize, &'a Color, f32)> = None;
+
+        for (i, c) in palette.iter().enumerate() {
+            let (l2, a2, b2, _) = c.to_oklaba();
+            let d = (l - l2).powi(2) + (a - a2).powi(2) + (b - b2).powi(2);
+
+            match nearest {
+                Some((_, _, dist)) if dist <= d => {}
+                _ => nearest = Some((i, c, d)),
+            }
+        }
+
+        nearest
+    }
+
+    /// Euclidean distance to the other color in the RGB color-space, ignoring alpha.
+    ///
+    /// Fast but not perceptually uniform. The result is in the range [0..sqrt(3)] for colors
+    /// with components in [0..1].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::BLACK.distance_rgb(&Color::WHITE), 3f32.sqrt());
+    /// ```
+    pub fn distance_rgb(&self, other: &Color) -> f32 {
+        self.distance_squared_srgb(other).sqrt()
+    }
+
+    /// Squared Euclidean distance to the other color in sRGB space, ignoring alpha.
+    ///
+    /// This avoids the square root of [`distance_rgb()`](#method.distance_rgb), and is sufficient
+    /// when only comparing distances, e.g. for nearest-neighbour searches.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// assert_eq!(Color::BLACK.distance_squared_srgb(&Color::WHITE), 3.0);
+    /// ```
+    pub fn distance_squared_srgb(&self, other: &Color) -> f32 {
+        (self.r - other.r).powi(2) + (self.g - other.g).powi(2) + (self.b - other.b).powi(2)
+    }
+
+    /// Euclidean distance to the other color in the Oklab color-space, ignoring alpha.
+    ///
+    /// Oklab is approximately perceptually uniform, so equal distances correspond roughly to
+    /// equal perceived differences. The distance between black and white is 1.0.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let d = Color::BLACK.distance_oklab(&Color::WHITE);
+    /// assert!((d - 1.0).abs() < 1e-4);
+    /// ```
+    pub fn distance_oklab(&self, other: &Color) -> f32 {
+        let (l1, a1, b1, _) = self.to_oklaba();
+        let (l2, a2, b2, _) = other.to_oklaba();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// Find the color in `palette` closest to this color, by [`distance_oklab()`](#method.distance_oklab).
+    ///
+    /// Returns `None` if the palette is empty. The first of equally close colors is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let palette = [Color::BLACK, Color::WHITE, Color::from_rgb(1.0, 0.0, 0.0)];
+    ///
+    /// let c = Color::from_rgb_u8(200, 30, 40);
+    /// assert_eq!(c.closest_in(&palette), Some(&palette[2]));
+    /// assert_eq!(c.closest_in(&[]), None);
+    /// ```
+    pub fn closest_in<'a>(&self, palette: &'a [Color]) -> Option<&'a Color> {
+        self.nearest_oklab(palette).map(|(_, c, _)| c)
+    }
+
+    /// Like [`closest_in()`](#method.closest_in), but also returns the distance to the closest
+    /// color (`f32::INFINITY` if the palette is empty).
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let palette = [Color::BLACK, Color::WHITE];
+    ///
+    /// let (c, d) = Color::from_rgb(0.9, 0.9, 0.9).closest_in_with_distance(&palette);
+    /// assert_eq!(c, Some(&Color::WHITE));
+    /// assert!(d > 0.0 && d < 0.1);
+    /// ```
+    pub fn closest_in_with_distance<'a>(&self, palette: &'a [Color]) -> (Option<&'a Color>, f32) {
+        match self.nearest_oklab(palette) {
+            Some((_, c, d)) => (Some(c), d.sqrt()),
+            None => (None, f32::INFINITY),
+        }
+    }
+
+    /// Get the RGB hexadecimal color string.
+    pub fn to_hex_string(&self) -> String {
+        let (r, g, b, a) = self.rgba_u8();
+
+        if a < 255 {
+            return format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+        }
+
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    /// Encode the color as a compact URL-safe string: the 4 RGBA bytes as 6 characters of
+    /// unpadded [base64url](https://www.rfc-editor.org/rfc/rfc4648#section-5).
+    ///
+    /// Use [`Color::from_url_safe()`] to decode it.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgba_u8(255, 102, 0, 255);
+    /// assert_eq!(c.encode_url_safe(), "_2YA_w");
+    /// ```
+    pub fn encode_url_safe(&self) -> String {
+        let (r, g, b, a) = self.rgba_u8();
+        let n = u32::from_be_bytes([r, g, b, a]);
+        // 32 bits, padded with 4 zero bits to 6 characters of 6 bits
+        let n = (n as u64) << 4;
+        (0..6)
+            .rev()
+            .map(|i| BASE64URL[((n >> (i * 6)) & 0x3f) as usize] as char)
+            .collect()
+    }
+
+    /// Decode a color encoded with [`Color::encode_url_safe()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let c = Color::from_url_safe("_2YA_w")?;
+    /// assert_eq!(c.rgba_u8(), (255, 102, 0, 255));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_url_safe(s: &str) -> Result<Color, ParseColorError> {
+        if s.len() != 6 {
+            return Err(ParseColorError::InvalidUnknown);
+        }
+        let mut n = 0u64;
+        for c in s.bytes() {
+            let v = BASE64URL
+                .iter()
+                .position(|&x| x == c)
+                .ok_or(ParseColorError::InvalidUnknown)?;
+            n = (n << 6) | v as u64;
+        }
+        // the 4 padding bits must be zero
+        if n & 0xf != 0 {
+            return Err(ParseColorError::InvalidUnknown);
+        }
+        let [r, g, b, a] = ((n >> 4) as u32).to_be_bytes();
+        Ok(Color::from_rgba_u8(r, g, b, a))
+    }
+
+    /// Get a [GIMP palette](https://developer.gimp.org/core/standards/gpl/) (`.gpl`) color line,
+    /// the red, green and blue values followed by a tab and the color name.
+    ///
+    /// Alpha is not part of the format and is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb_u8(255, 128, 0);
+    /// assert_eq!(c.to_gpl_line("Orange"), "255 128   0\tOrange");
+    /// ```
+    pub fn to_gpl_line(&self, name: &str) -> String {
+        let (r, g, b, _) = self.rgba_u8();
+        format!("{:3} {:3} {:3}\t{}", r, g, b, name)
+    }
+
+    /// Parse a single [GIMP palette](https://developer.gimp.org/core/standards/gpl/) color line,
+    /// returning the color name (empty if there is none) and the color.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let (name, c) = Color::from_gpl_line("255 128   0\tOrange")?;
+    /// assert_eq!(name, "Orange");
+    /// assert_eq!(c.rgba_u8(), (255, 128, 0, 255));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_gpl_line(s: &str) -> Result<(String, Color), ParseColorError> {
+        let mut rest = s.trim();
+        let mut rgb = [0u8; 3];
+        for v in &mut rgb {
+            let (token, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if token.is_empty() {
+                return Err(ParseColorError::InvalidGpl);
+            }
+            *v = token.parse()?;
+            rest = tail.trim_start();
+        }
+        let [r, g, b] = rgb;
+        Ok((rest.to_string(), Color::from_rgb_u8(r, g, b)))
+    }
+
+    /// Parse a [GIMP palette](https://developer.gimp.org/core/standards/gpl/) file, returning the
+    /// name and color of every entry.
+    ///
+    /// The file must start with the `GIMP Palette` magic line. The `Name:` and `Columns:`
+    /// headers, comments and blank lines are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let gpl = "GIMP Palette\nName: Basic\n#\n255   0   0\tRed\n  0   0 255\tBlue\n";
+    /// let colors = Color::from_gpl_file(gpl)?;
+    ///
+    /// assert_eq!(colors.len(), 2);
+    /// assert_eq!(colors[0].0, "Red");
+    /// assert_eq!(colors[1].1.rgba_u8(), (0, 0, 255, 255));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_gpl_file(s: &str) -> Result<Vec<(String, Color)>, ParseColorError> {
+        let mut lines = s.lines();
+        if lines.next().map(str::trim) != Some("GIMP Palette") {
+            return Err(ParseColorError::InvalidGpl);
+        }
+
+        lines
+            .map(str::trim)
+            .filter(|line| {
+                !(line.is_empty()
+                    || line.starts_with('#')
+                    || line.starts_with("Name:")
+                    || line.starts_with("Columns:"))
+            })
+            .map(Color::from_gpl_line)
+            .collect()
+    }
+
+    #[cfg(feature = "ase")]
+    /// Encode the color as an [ASE (Adobe Swatch Exchange)](http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase)
+    /// color entry block with the given swatch name.
+    ///
+    /// The color is stored as a normal (non-global, non-spot) `RGB` color; alpha is not part of
+    /// the ASE format and is dropped. The bytes are a single block, without the file header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is longer than 65534 UTF-16 code units, the most the format can store.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let bytes = Color::from_rgb(1.0, 0.0, 0.0).to_ase_swatch_bytes("Red");
+    /// assert_eq!(&bytes[..2], &[0x00, 0x01]);
+    /// assert_eq!(bytes.len(), 34);
+    /// ```
+    pub fn to_ase_swatch_bytes(&self, name: &str) -> Vec<u8> {
+        // UTF-16 code units, including the terminator
+        let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+        let name_len = u16::try_from(name.len()).expect("ASE swatch name too long");
+
+        let mut body = Vec::with_capacity(2 + name.len() * 2 + 4 + 12 + 2);
+        body.extend_from_slice(&name_len.to_be_bytes());
+        for c in &name {
+            body.extend_from_slice(&c.to_be_bytes());
+        }
+        body.extend_from_slice(b"RGB ");
+        for v in [self.r, self.g, self.b] {
+            body.extend_from_slice(&v.clamp(0.0, 1.0).to_be_bytes());
+        }
+        // color type: normal
+        body.extend_from_slice(&2u16.to_be_bytes());
+
+        let mut bytes = Vec::with_capacity(6 + body.len());
+        bytes.extend_from_slice(&ASE_COLOR_ENTRY.to_be_bytes());
+        bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    #[cfg(feature = "ase")]
+    /// Decode an [ASE (Adobe Swatch Exchange)](http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase)
+    /// color entry block, returning the swatch name and the color.
+    ///
+    /// `RGB `, `Gray` and `CMYK` colors are supported, CMYK is converted naively without a color
+    /// profile. Bytes following the block are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let bytes = Color::from_rgb_u8(255, 128, 0).to_ase_swatch_bytes("Orange");
+    /// let (name, c) = Color::from_ase_swatch_bytes(&bytes)?;
+    ///
+    /// assert_eq!(name, "Orange");
+    /// assert_eq!(c.rgba_u8(), (255, 128, 0, 255));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_ase_swatch_bytes(data: &[u8]) -> Result<(String, Color), ParseColorError> {
+        const ERR: ParseColorError = ParseColorError::InvalidAse;
+
+        fn take<'a>(data: &mut &'a [u8], n: usize) -> Result<&'a [u8], ParseColorError> {
+            if data.len() < n {
+                return Err(ERR);
+            }
+            let (head, tail) = data.split_at(n);
+            *data = tail;
+            Ok(head)
+        }
+
+        fn read_f32(data: &mut &[u8]) -> Result<f32, ParseColorError> {
+            let b = take(data, 4)?;
+            Ok(f32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        }
+
+        let mut data = data;
+        let head = take(&mut data, 6)?;
+        if u16::from_be_bytes([head[0], head[1]]) != ASE_COLOR_ENTRY {
+            return Err(ERR);
+        }
+        let len = u32::from_be_bytes([head[2], head[3], head[4], head[5]]) as usize;
+        let mut data = take(&mut data, len)?;
+
+        let n = take(&mut data, 2)?;
+        let n = u16::from_be_bytes([n[0], n[1]]) as usize;
+        let name: Vec<u16> = take(&mut data, n * 2)?
+            .chunks(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .take_while(|&c| c != 0)
+            .collect();
+        let name = String::from_utf16(&name).map_err(|_| ERR)?;
+
+        let c = match take(&mut data, 4)? {
+            b"RGB " => {
+                let r = read_f32(&mut data)?;
+                let g = read_f32(&mut data)?;
+                let b = read_f32(&mut data)?;
+                Color::from_rgb(r, g, b)
+            }
+            b"Gray" => {
+                let v = read_f32(&mut data)?;
+                Color::from_rgb(v, v, v)
+            }
+            b"CMYK" => {
+                let c = read_f32(&mut data)?;
+                let m = read_f32(&mut data)?;
+                let y = read_f32(&mut data)?;
+                let k = read_f32(&mut data)?;
+                Color::from_rgb(
+                    (1.0 - c) * (1.0 - k),
+                    (1.0 - m) * (1.0 - k),
+                    (1.0 - y) * (1.0 - k),
+                )
+            }
+            _ => return Err(ERR),
+        };
+        // color type
+        take(&mut data, 2)?;
+
+        Ok((name, c.clamped()))
+    }
+
+    /// Get the `#rrggbbaa` hexadecimal color string, including the alpha even if the color is fully opaque.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_hex_string(), "#ff0000");
+    /// assert_eq!(c.to_css_alpha_hex(), "#ff0000ff");
+    /// ```
+    pub fn to_css_alpha_hex(&self) -> String {
+        let (r, g, b, a) = self.rgba_u8();
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+
+    /// Get the CSS `rgb()` format string.
+    pub fn to_rgb_string(&self) -> String {
+        let (r, g, b, _) = self.rgba_u8();
+
+        if self.a < 1.0 {
+            return format!("rgba({},{},{},{})", r, g, b, self.a);
+        }
+
+        format!("rgb({},{},{})", r, g, b)
+    }
+
+    /// Get the CSS `rgb()` format string with percentage components, rounded to two decimal
+    /// places.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.5);
+    /// assert_eq!(c.to_rgb_percent_string(), "rgb(100%,0%,50%)");
+    ///
+    /// let c = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_rgb_percent_string(), "rgba(100%,0%,0%,0.5)");
+    /// ```
+    pub fn to_rgb_percent_string(&self) -> String {
+        let r = fmt_float(self.r * 100.0, 2);
+        let g = fmt_float(self.g * 100.0, 2);
+        let b = fmt_float(self.b * 100.0, 2);
+
+        if self.a < 1.0 {
+            return format!("rgba({}%,{}%,{}%,{})", r, g, b, fmt_float(self.a, 2));
+        }
+
+        format!("rgb({}%,{}%,{}%)", r, g, b)
+    }
+
+    /// Get the CSS Level 4 `rgb()` format string, with space-separated components and
+    /// slash-separated alpha.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_modern_css_string(), "rgb(255 0 0)");
+    ///
+    /// let c = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_modern_css_string(), "rgb(255 0 0 / 0.5)");
+    /// ```
+    pub fn to_modern_css_string(&self) -> String {
+        let (r, g, b, _) = self.rgba_u8();
+
+        if self.a < 1.0 {
+            return format!("rgb({} {} {} / {})", r, g, b, fmt_float(self.a, 3));
+        }
+
+        format!("rgb({} {} {})", r, g, b)
+    }
+
+    /// Get the CSS Level 4 `hsl()` format string, with space-separated components, unitless hue
+    /// and slash-separated alpha. Hue, saturation and lightness are rounded to two decimal places.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_hsl_modern_string(), "hsl(0 100% 50%)");
+    ///
+    /// let c = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    /// assert_eq!(c.to_hsl_modern_string(), "hsl(0 100% 50% / 0.5)");
+    /// ```
+    pub fn to_hsl_modern_string(&self) -> String {
+        let (h, s, l, _) = self.to_hsla();
+        let (h, s, l) = (
+            fmt_float(h, 2),
+            fmt_float(s * 100.0, 2),
+            fmt_float(l * 100.0, 2),
+        );
+
+        if self.a < 1.0 {
+            return format!("hsl({} {}% {}% / {})", h, s, l, fmt_float(self.a, 3));
+        }
+
+        format!("hsl({} {}% {}%)", h, s, l)
+    }
+
+    /// Get the CSS `color(display-p3 r g b)` format string.
+    ///
+    /// Colors outside of the sRGB gamut are not clamped, so the components may exceed [0..1].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_display_p3_string(), "color(display-p3 0.9175 0.2003 0.1386)");
+    /// ```
+    pub fn to_display_p3_string(&self) -> String {
+        self.to_color_function_string(ColorSpace::DisplayP3, "display-p3")
+    }
+
+    /// Get the CSS `color(srgb-linear r g b)` format string.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_srgb_linear_string(), "color(srgb-linear 1 0 0)");
+    /// ```
+    pub fn to_srgb_linear_string(&self) -> String {
+        self.to_color_function_string(ColorSpace::SrgbLinear, "srgb-linear")
+    }
+
+    #[cfg(feature = "lab")]
+    /// Get the CSS `lab(L% a b)` format string.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_lab_string(), "lab(53.2382% 80.0923 67.2021)");
+    /// ```
+    pub fn to_lab_string(&self) -> String {
+        let (l, a, b, alpha) = self.to_lab();
+        fmt_lab_function("lab", l, a, b, alpha)
+    }
+
+    #[cfg(feature = "lab")]
+    /// Get the CSS `lch(L% C H)` format string, hue in degrees.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_lch_string(), "lch(53.2382% 104.5509 39.9986)");
+    /// ```
+    pub fn to_lch_string(&self) -> String {
+        let (l, c, h, alpha) = self.to_lch();
+        let h = normalize_angle(h.to_degrees());
+        fmt_lab_function("lch", l, c, h, alpha)
+    }
+
+    // CSS color() function string in the predefined color space `space` named `name`.
+    fn to_color_function_string(&self, space: ColorSpace, name: &str) -> String {
+        let (x, y, z, alpha) = self.convert_to(space);
+        let (x, y, z) = (fmt_float(x, 4), fmt_float(y, 4), fmt_float(z, 4));
+
+        if alpha < 1.0 {
+            return format!(
+                "color({} {} {} {} / {})",
+                name,
+                x,
+                y,
+                z,
+                fmt_float(alpha, 4)
+            );
+        }
+
+        format!("color({} {} {} {})", name, x, y, z)
+    }
+
+    /// Get the CSS color string in the format described by `opts`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, CssFormat, CssStringOptions};
+    ///
+    /// let c = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    ///
+    /// let opts = CssStringOptions {
+    ///     uppercase_hex: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(c.to_css_string_with_options(opts), "#FF000080");
+    ///
+    /// let opts = CssStringOptions {
+    ///     format: CssFormat::Hsl,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(c.to_css_string_with_options(opts), "hsla(0,100%,50%,0.5)");
+    /// ```
+    pub fn to_css_string_with_options(&self, opts: CssStringOptions) -> String {
+        let alpha = opts.always_include_alpha || self.a < 1.0;
+        let p = opts.precision as usize;
+
+        match opts.format {
+            CssFormat::Hex => {
+                let (r, g, b, a) = self.rgba_u8();
+                let s = if alpha {
+                    format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+                } else {
+                    format!("#{:02x}{:02x}{:02x}", r, g, b)
+                };
+                if opts.uppercase_hex {
+                    return s.to_uppercase();
+                }
+                s
+            }
+            CssFormat::Rgb => {
+                let (r, g, b, _) = self.rgba_u8();
+                if alpha {
+                    return format!("rgba({},{},{},{})", r, g, b, fmt_float(self.a, p));
+                }
+                format!("rgb({},{},{})", r, g, b)
+            }
+            CssFormat::Hsl => {
+                let (h, s, l, a) = self.to_hsla();
+                let (h, s, l) = (
+                    fmt_float(h, p),
+                    fmt_float(s * 100.0, p),
+                    fmt_float(l * 100.0, p),
+                );
+                if alpha {
+                    return format!("hsla({},{}%,{}%,{})", h, s, l, fmt_float(a, p));
+                }
+                format!("hsl({},{}%,{}%)", h, s, l)
+            }
+            CssFormat::Oklab => {
+                let (l, a, b, alpha_) = self.to_oklaba();
+                let (l, a, b) = (fmt_float(l, p), fmt_float(a, p), fmt_float(b, p));
+                if alpha {
+                    return format!("oklab({} {} {} / {})", l, a, b, fmt_float(alpha_, p));
+                }
+                format!("oklab({} {} {})", l, a, b)
+            }
+            CssFormat::Oklch => {
+                let (l, a, b, alpha_) = self.to_oklaba();
+                let (l, c, h) = oklab_to_oklch(l, a, b);
+                let (l, c, h) = (fmt_float(l, p), fmt_float(c, p), fmt_float(h, p));
+                if alpha {
+                    return format!("oklch({} {} {} / {})", l, c, h, fmt_float(alpha_, p));
+                }
+                format!("oklch({} {} {})", l, c, h)
+            }
+        }
+    }
+
+    /// Get a CSS `linear-gradient()` color stop, the color in the given `format` followed by
+    /// the position, e.g. `oklch(0.7 0.15 142) 50%`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, CssFormat};
+    ///
+    /// let c = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(c.to_css_linear_gradient_stop(0.0, CssFormat::Hex), "#ff0000 0%");
+    /// assert_eq!(
+    ///     c.to_css_linear_gradient_stop(50.0, CssFormat::Oklch),
+    ///     "oklch(0.628 0.258 29.221) 50%"
+    /// );
+    /// ```
+    pub fn to_css_linear_gradient_stop(&self, position_percent: f32, format: CssFormat) -> String {
+        let color = self.to_css_string_with_options(CssStringOptions {
+            format,
+            ..Default::default()
+        });
+        format!("{} {}%", color, fmt_float(position_percent, 3))
+    }
+
+    /// Get a CSS custom property definition with the hexadecimal color string as its value, e.g.
+    /// `--brand-primary: #1a73e8;`. The leading `--` of `name` is optional.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb_u8(26, 115, 232);
+    /// assert_eq!(c.to_css_var_definition("brand-primary"), "--brand-primary: #1a73e8;");
+    /// assert_eq!(c.to_css_var_definition("--brand-primary"), "--brand-primary: #1a73e8;");
+    /// ```
+    pub fn to_css_var_definition(&self, name: &str) -> String {
+        let name = name.strip_prefix("--").unwrap_or(name);
+        format!("--{}: {};", name, self.to_hex_string())
+    }
+
+    #[cfg(feature = "terminal")]
+    /// Get the 24-bit ("truecolor") ANSI escape sequence for setting the terminal foreground or background color.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let c = Color::from_rgb_u8(255, 128, 0);
+    /// assert_eq!(c.to_ansi_truecolor_string(true), "\x1b[38;2;255;128;0m");
+    /// assert_eq!(c.to_ansi_truecolor_string(false), "\x1b[48;2;255;128;0m");
+    /// ```
+    pub fn to_ansi_truecolor_string(&self, is_foreground: bool) -> String {
+        let (r, g, b, _) = self.rgba_u8();
+        let n = if is_foreground { 38 } else { 48 };
+        format!("\x1b[{};2;{};{};{}m", n, r, g, b)
+    }
+
+    #[cfg(feature = "terminal")]
+    /// Get the index of the nearest color in the xterm 256-color palette, using Euclidean RGB distance.
+    ///
+    /// All 256 colors are searched, with the basic colors 0..=15 taken as the xterm defaults
+    /// (see [`from_ansi_256()`](#method.from_ansi_256)). On ties the color cube and grayscale
+    /// ramp (16..=255) win, since the basic colors are usually redefined by the terminal theme.
+    pub fn to_ansi_256_index(&self) -> u8 {
+        let (r, g, b, _) = self.rgba_u8();
+        let mut index = 16;
+        let mut min_dist = u32::MAX;
+
+        for i in (16..=255).chain(0..16) {
+            let (r2, g2, b2) = ansi_256_to_rgb(i);
+            let dist = (r as i32 - r2 as i32).pow(2) as u32
+                + (g as i32 - g2 as i32).pow(2) as u32
+                + (b as i32 - b2 as i32).pow(2) as u32;
+            if dist < min_dist {
+                min_dist = dist;
+                index = i;
+            }
+        }
+
+        index
+    }
+
+    #[cfg(feature = "terminal")]
+    /// Get the color of the xterm 256-color palette `index`.
+    ///
+    /// * 0..=15: basic colors (xterm defaults)
+    /// * 16..=231: 6x6x6 color cube
+    /// * 232..=255: grayscale ramp
+    pub fn from_ansi_256(index: u8) -> Color {
+        let (r, g, b) = ansi_256_to_rgb(index);
+        Color::from_rgb_u8(r, g, b)
+    }
+
+    /// Get the GTK CSS color string.
+    ///
+    /// Translucent colors use the GTK `alpha()` function, e.g. `alpha(#ff0000, 0.5)`.
+    pub fn to_gtk_style_color(&self) -> String {
+        let (r, g, b, _) = self.rgba_u8();
+        let hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+
+        if self.a < 1.0 {
+            return format!("alpha({}, {})", hex, fmt_float(self.a, 3));
+        }
+
+        hex
+    }
+
+    #[cfg(debug_assertions)]
+    /// Print the color in several formats (hex, rgb, hsl, oklab and oklch) to stderr.
+    ///
+    /// Only available in debug builds, for inspecting colors during development.
+    pub fn print_debug(&self) {
+        eprintln!("{}", self.debug_string());
+    }
+
+    // The output of `print_debug()`.
+    #[cfg(debug_assertions)]
+    fn debug_string(&self) -> String {
+        let format = |format| {
+            self.to_css_string_with_options(CssStringOptions {
+                format,
+                ..Default::default()
+            })
+        };
+        format!(
+            "{:?}\n  hex:   {}\n  rgb:   {}\n  hsl:   {}\n  oklab: {}\n  oklch: {}",
+            self,
+            format(CssFormat::Hex),
+            format(CssFormat::Rgb),
+            format(CssFormat::Hsl),
+            format(CssFormat::Oklab),
+            format(CssFormat::Oklch),
+        )
+    }
+
+    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
+    pub fn interpolate_rgb(&self, other: &Color, t: f32) -> Color {
+        Color {
+            r: self.r + t * (other.r - self.r),
+            g: self.g + t * (other.g - self.g),
+            b: self.b + t * (other.b - self.b),
+            a: self.a + t * (other.a - self.a),
+        }
+    }
+
+    /// Blend this color with the other one, in the RGB color-space, with cubic Bézier easing.
+    /// `t` in the range [0..1].
+    ///
+    /// The easing curve goes from `(0, 0)` to `(1, 1)` with the control points `(1/3, p1)` and
+    /// `(2/3, p2)`. As the x-coordinates are evenly spaced, the curve's x equals its parameter
+    /// and `t` maps directly to the eased `t' = y(t)`, which is passed to
+    /// [`interpolate_rgb()`](#method.interpolate_rgb). `p1 = 1/3, p2 = 2/3` is linear; values
+    /// outside [0..1] overshoot.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let a = Color::from_rgb(0.0, 0.0, 0.0);
+    /// let b = Color::from_rgb(1.0, 1.0, 1.0);
+    ///
+    /// // ease-in: slow start
+    /// let c = a.interpolate_cubic(&b, 0.5, 0.0, 0.5);
+    /// assert_eq!(c.rgba_u8(), (80, 80, 80, 255));
+    /// ```
+    pub fn interpolate_cubic(&self, other: &Color, t: f32, p1: f32, p2: f32) -> Color {
+        let u = 1.0 - t;
+        let t = 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t;
+        self.interpolate_rgb(other, t)
+    }
+
+    /// Mix this color with the other one like paint, in the RYB (red-yellow-blue) color model.
+    /// `t` in the range [0..1].
+    ///
+    /// This is an approximation of subtractive color mixing, using the invertible RGB/RYB
+    /// conversion by Sugita & Takahashi. Mixing yellow and blue gives green, not gray.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let yellow = Color::from_rgb(1.0, 1.0, 0.0);
+    /// let blue = Color::from_rgb(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(yellow.blend_paint_mix(&blue, 0.5).rgba_u8(), (0, 128, 0, 255));
+    /// assert_eq!(yellow.interpolate_rgb(&blue, 0.5).rgba_u8(), (128, 128, 128, 255));
+    /// ```
+    pub fn blend_paint_mix(&self, other: &Color, t: f32) -> Color {
+        let [r1, y1, b1] = rgb_to_ryb([self.r, self.g, self.b].map(clamp0_1));
+        let [r2, y2, b2] = rgb_to_ryb([other.r, other.g, other.b].map(clamp0_1));
+        let [r, g, b] = ryb_to_rgb([r1 + t * (r2 - r1), y1 + t * (y2 - y1), b1 + t * (b2 - b1)]);
+        Color::from_rgba(r, g, b, self.a + t * (other.a - self.a))
+    }
+
+    /// Evaluate a multi-stop gradient at `t`, interpolating in the RGB color-space.
+    ///
+    /// `stops` are `(position, color)` pairs sorted by position. Outside of the range of
+    /// positions, the color of the first or last stop is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stops` is empty or not sorted by position.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let stops = [
+    ///     (0.0, Color::from_rgb(1.0, 0.0, 0.0)),
+    ///     (0.5, Color::from_rgb(0.0, 1.0, 0.0)),
+    ///     (1.0, Color::from_rgb(0.0, 0.0, 1.0)),
+    /// ];
+    ///
+    /// assert_eq!(Color::interpolate_many(&stops, 0.75).rgba(), (0.0, 0.5, 0.5, 1.0));
+    /// ```
+    pub fn interpolate_many(stops: &[(f32, Color)], t: f32) -> Color {
+        assert!(!stops.is_empty(), "interpolate_many: no color stops");
+        assert!(
+            stops.windows(2).all(|w| w[0].0 <= w[1].0),
+            "interpolate_many: color stops are not sorted by position"
+        );
+
+        let (first, last) = (&stops[0], &stops[stops.len() - 1]);
+        if t <= first.0 {
+            return first.1.clone();
+        }
+        if t >= last.0 {
+            return last.1.clone();
+        }
+
+        // First stop after t
+        let i = stops.partition_point(|(pos, _)| *pos <= t);
+        let (p1, c1) = &stops[i - 1];
+        let (p2, c2) = &stops[i];
+        c1.interpolate_rgb(c2, (t - p1) / (p2 - p1))
+    }
+
+    /// Blend this color with the other one, in the linear RGB color-space. `t` in the range [0..1].
+    pub fn interpolate_linear_rgb(&self, other: &Color, t: f32) -> Color {
+        let (r1, g1, b1, a1) = self.to_linear_rgba();
+        let (r2, g2, b2, a2) = other.to_linear_rgba();
+        Color::from_linear_rgba(
+            r1 + t * (r2 - r1),
+            g1 + t * (g2 - g1),
+            b1 + t * (b2 - b1),
+            a1 + t * (a2 - a1),
+        )
+    }
+
+    /// Blend this color with the other one, in the HSV color-space. `t` in the range [0..1].
+    pub fn interpolate_hsv(&self, other: &Color, t: f32) -> Color {
+        let (h1, s1, v1, a1) = self.to_hsva();
+        let (h2, s2, v2, a2) = other.to_hsva();
+        Color::from_hsva(
+            interp_angle(h1, h2, t),
+            s1 + t * (s2 - s1),
+            v1 + t * (v2 - v1),
+            a1 + t * (a2 - a1),
+        )
+    }
+
+    /// Blend this color with the other one, in the HSV color-space, interpolating hue as described by `mode`. `t` in the range [0..1].
+    pub fn interpolate_hsv_with_mode(
+        &self,
+        other: &Color,
+        t: f32,
+        mode: HueInterpolationMode,
+    ) -> Color {
+        self.interpolate_in_with_mode(ColorSpace::Hsv, other, t, mode)
+    }
+
+    /// Blend this color with the other one, in the HSL color-space. `t` in the range [0..1].
+    pub fn interpolate_hsl(&self, other: &Color, t: f32) -> Color {
+        self.interpolate_hsl_with_mode(other, t, HueInterpolationMode::Shorter)
+    }
+
+    /// Blend this color with the other one, in the HSL color-space, interpolating hue as described by `mode`. `t` in the range [0..1].
+    pub fn interpolate_hsl_with_mode(
+        &self,
+        other: &Color,
+        t: f32,
+        mode: HueInterpolationMode,
+    ) -> Color {
+        self.interpolate_in_with_mode(ColorSpace::Hsl, other, t, mode)
+    }
+
+    /// Blend this color with the other one, in the [Oklab](https://bottosson.github.io/posts/oklab/) color-space. `t` in the range [0..1].
+    pub fn interpolate_oklab(&self, other: &Color, t: f32) -> Color {
+        let (l1, a1, b1, alpha1) = self.to_oklaba();
+        let (l2, a2, b2, alpha2) = other.to_oklaba();
+        Color::from_oklaba(
+            l1 + t * (l2 - l1),
+            a1 + t * (a2 - a1),
+            b1 + t * (b2 - b1),
+            alpha1 + t * (alpha2 - alpha1),
+        )
+    }
+
+    /// Blend this color with the other one, in the given color-space. `t` in the range [0..1].
+    ///
+    /// Hue components (HSL, HSV, HWB, Oklch, LCH, HSLuv) are interpolated along the shorter arc,
+    /// other components are interpolated linearly.
+    ///
+    /// Missing components (`NaN`, or the hue of an achromatic color) take the value of the
+    /// corresponding component of the other color.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ColorSpace};
+    ///
+    /// let a = Color::from_rgb(0.0, 1.0, 0.0);
+    /// let b = Color::from_rgb(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(
+    ///     a.interpolate_in(ColorSpace::OkLab, &b, 0.5),
+    ///     a.interpolate_oklab(&b, 0.5)
+    /// );
+    /// ```
+    pub fn interpolate_in(&self, space: ColorSpace, other: &Color, t: f32) -> Color {
+        self.interpolate_in_with_mode(space, other, t, HueInterpolationMode::Shorter)
+    }
+
+    /// Mix this color with the other one, in the given color-space, like CSS `color-mix()`.
+    ///
+    /// `fraction` is the amount of `other` to use, clamped to [0..1]: `0.0` returns this color,
+    /// `1.0` returns `other`. Unlike [`interpolate_in()`](#method.interpolate_in), the
+    /// components are premultiplied by alpha, so a transparent color doesn't darken the mix.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, ColorSpace};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let red = Color::from_rgb(1.0, 0.0, 0.0);
+    /// let blue = Color::from_rgb(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(
+    ///     red.mix(&blue, 0.25, ColorSpace::OkLab),
+    ///     Color::from_html("color-mix(in oklab, #f00 75%, #00f)")?
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mix(&self, other: &Color, fraction: f32, space: ColorSpace) -> Color {
+        self.mix_in_with_mode(
+            space,
+            other,
+            clamp0_1(fraction),
+            HueInterpolationMode::Shorter,
+        )
+    }
+
+    /// Mix this color with the other one in the sRGB color-space, like
+    /// [`interpolate_rgb()`](#method.interpolate_rgb) with the CSS `color-mix()` convention.
+    ///
+    /// `weight` is the proportion of `other`, clamped to [0..1]: `0.0` returns this color,
+    /// `1.0` returns `other`. See [`mix()`](#method.mix) for other color-spaces.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let red = Color::from_rgb(1.0, 0.0, 0.0);
+    /// let blue = Color::from_rgb(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(red.mix_weighted(&blue, 0.25).rgba_u8(), (191, 0, 64, 255));
+    /// assert_eq!(red.mix_weighted(&blue, 1.5), blue);
+    /// ```
+    pub fn mix_weighted(&self, other: &Color, weight: f32) -> Color {
+        self.interpolate_rgb(other, clamp0_1(weight))
+    }
+
+    /// Mix this color with the other one in the sRGB color-space, using relative weights, like
+    /// CSS `color-mix(in srgb, color1 40%, color2 60%)`.
+    ///
+    /// The weights don't need to sum to 1.0, they are normalized: `3.0` and `1.0` means three
+    /// parts of this color and one part of `other`. Negative weights are treated as zero; if
+    /// both weights are zero this color is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    ///
+    /// let red = Color::from_rgb(1.0, 0.0, 0.0);
+    /// let blue = Color::from_rgb(0.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(
+    ///     red.mix_with_weight(&blue, 3.0, 1.0),
+    ///     Color::from_html("color-mix(in srgb, #f00 75%, #00f 25%)")?
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn mix_with_weight(&self, other: &Color, self_weight: f32, other_weight: f32) -> Color {
+        let (w1, w2) = (self_weight.max(0.0), other_weight.max(0.0));
+        if w1 + w2 <= 0.0 {
+            return self.clone();
+        }
+        self.interpolate_rgb(other, w2 / (w1 + w2))
+    }
+
+    /// Blend this color with the other one, in the given color-space, interpolating hue
+    /// components as described by `mode`. `t` in the range [0..1].
+    pub fn interpolate_in_with_mode(
+        &self,
+        space: ColorSpace,
+        other: &Color,
+        t: f32,
+        mode: HueInterpolationMode,
+    ) -> Color {
+        // Colors with missing (NaN) components take the general path below.
+        let missing = |c: &Color| c.r.is_nan() || c.g.is_nan() || c.b.is_nan() || c.a.is_nan();
+        if !missing(self) && !missing(other) {
+            match space {
+                ColorSpace::Srgb => return self.interpolate_rgb(other, t),
+                ColorSpace::SrgbLinear => return self.interpolate_linear_rgb(other, t),
+                ColorSpace::OkLab => return self.interpolate_oklab(other, t),
+                #[cfg(feature = "lab")]
+                ColorSpace::Lab => return self.interpolate_lab(other, t),
+                _ => {}
+            }
+        }
+
+        self.interpolate_components(space, other, t, mode, false)
+    }
+
+    // Mix like CSS `color-mix()`: the same as `interpolate_in_with_mode()`, but with the non-hue
+    // components premultiplied by alpha, as required by
+    // https://www.w3.org/TR/css-color-4/#interpolation-alpha
+    pub(crate) fn mix_in_with_mode(
+        &self,
+        space: ColorSpace,
+        other: &Color,
+        t: f32,
+        mode: HueInterpolationMode,
+    ) -> Color {
+        self.interpolate_components(space, other, t, mode, true)
+    }
+
+    fn interpolate_components(
+        &self,
+        space: ColorSpace,
+        other: &Color,
+        t: f32,
+        mode: HueInterpolationMode,
+        premultiply: bool,
+    ) -> Color {
+        let (x1, y1, z1, a1) = missing_hue(space, self.convert_to(space));
+        let (x2, y2, z2, a2) = missing_hue(space, other.convert_to(space));
+        let (x1, x2) = fill_missing(x1, x2);
+        let (y1, y2) = fill_missing(y1, y2);
+        let (z1, z2) = fill_missing(z1, z2);
+        let (a1, a2) = fill_missing(a1, a2);
+        let lerp = |a: f32, b: f32| a + t * (b - a);
+
+        // The index of the hue component, and whether it is in radians.
+        let hue = match space {
+            ColorSpace::Hsl | ColorSpace::Hsv | ColorSpace::Hwb | ColorSpace::HslUv => {
+                Some((0, false))
+            }
+            ColorSpace::OkLch => Some((2, false)),
+            #[cfg(feature = "lab")]
+            ColorSpace::Lch => Some((2, true)),
+            _ => None,
+        };
+        let (w1, w2) = if premultiply { (a1, a2) } else { (1.0, 1.0) };
+        let alpha = lerp(a1, a2);
+        let (c1, c2) = ([x1, y1, z1], [x2, y2, z2]);
+
+        let c = [0, 1, 2].map(|i| match hue {
+            Some((h, true)) if h == i => {
+                interp_hue(c1[i].to_degrees(), c2[i].to_degrees(), t, mode).to_radians()
+            }
+            Some((h, false)) if h == i => interp_hue(c1[i], c2[i], t, mode),
+            _ if premultiply && alpha > 0.0 => lerp(c1[i] * w1, c2[i] * w2) / alpha,
+            _ => lerp(c1[i] * w1, c2[i] * w2),
+        });
+
+        Color::convert_from(space, c[0], c[1], c[2], alpha)
+    }
+
+    /// Blend this color with the other one, in the Oklch color-space. `t` in the range [0..1].
+    pub fn interpolate_oklch(&self, other: &Color, t: f32) -> Color {
+        self.interpolate_oklch_with_mode(other, t, HueInterpolationMode::Shorter)
+    }
+
+    /// Blend this color with the other one, in the Oklch color-space, interpolating hue as described by `mode`. `t` in the range [0..1].
+    pub fn interpolate_oklch_with_mode(
+        &self,
+        other: &Color,
+        t: f32,
+        mode: HueInterpolationMode,
+    ) -> Color {
+        self.interpolate_in_with_mode(ColorSpace::OkLch, other, t, mode)
+    }
+
+    /// Blend this color (the source) over `backdrop` using the soft light blend mode.
+    ///
+    /// Uses the formula from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendingsoftlight)
+    /// specification. Alpha is taken from `backdrop`.
+    pub fn blend_soft_light(&self, backdrop: &Color) -> Color {
+        blend_separable(self, backdrop, soft_light)
+    }
+
+    #[doc(hidden)]
+    pub fn soft_light_blend(&self, backdrop: &Color) -> Color {
+        self.blend_soft_light(backdrop)
+    }
+
+    /// Blend this color (the source) over `backdrop` using the soft light formula used by Adobe Photoshop.
+    ///
+    /// Alpha is taken from `backdrop`.
+    pub fn blend_soft_light_photoshop(&self, backdrop: &Color) -> Color {
+        blend_separable(self, backdrop, |cs, cb| {
+            if cs < 0.5 {
+                return 2.0 * cb * cs + cb * cb * (1.0 - 2.0 * cs);
+            }
+            2.0 * cb * (1.0 - cs) + cb.sqrt() * (2.0 * cs - 1.0)
+        })
+    }
+
+    /// Blend this color (the source) over `backdrop` using the color dodge blend mode, which
+    /// brightens the backdrop: `min(1, backdrop / (1 - source))` per channel.
+    ///
+    /// Uses the formula from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendingcolordodge)
+    /// specification: a black backdrop stays black and a white source gives white. Alpha is
+    /// taken from `backdrop`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let src = Color::from_rgb(0.5, 1.0, 0.0);
+    /// let backdrop = Color::from_rgb(0.25, 0.5, 0.5);
+    /// assert_eq!(src.blend_color_dodge(&backdrop).rgba(), (0.5, 1.0, 0.5, 1.0));
+    /// ```
+    pub fn blend_color_dodge(&self, backdrop: &Color) -> Color {
+        blend_separable(self, backdrop, color_dodge)
+    }
+
+    /// Blend this color (the source) over `backdrop` using the color burn blend mode, which
+    /// darkens the backdrop: `1 - min(1, (1 - backdrop) / source)` per channel.
+    ///
+    /// Uses the formula from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendingcolorburn)
+    /// specification: a white backdrop stays white and a black source gives black. Alpha is
+    /// taken from `backdrop`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let src = Color::from_rgb(0.5, 0.0, 1.0);
+    /// let backdrop = Color::from_rgb(0.75, 0.5, 0.5);
+    /// assert_eq!(src.blend_color_burn(&backdrop).rgba(), (0.5, 0.0, 0.5, 1.0));
+    /// ```
+    pub fn blend_color_burn(&self, backdrop: &Color) -> Color {
+        blend_separable(self, backdrop, color_burn)
+    }
+
+    /// Blend this color (the source) over `backdrop` using the hue blend mode: the hue of the
+    /// source with the saturation and luminosity of the backdrop.
+    ///
+    /// Uses the `SetLum`/`SetSat` formulas from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendinghue)
+    /// specification. Alpha is taken from `backdrop`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let src = Color::from_rgb(0.0, 0.0, 1.0);
+    /// let backdrop = Color::from_rgb(0.5, 0.5, 0.5);
+    /// assert_eq!(src.blend_hue(&backdrop).rgba(), (0.5, 0.5, 0.5, 1.0));
+    /// ```
+    pub fn blend_hue(&self, backdrop: &Color) -> Color {
+        blend_non_separable(self, backdrop, |cs, cb| {
+            set_lum(set_sat(cs, sat(cb)), lum(cb))
+        })
     }
 
-    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
-    pub fn interpolate_rgb(&self, other: &Color, t: f32) -> Color {
-        Color {
-            r: self.r + t * (other.r - self.r),
-            g: self.g + t * (other.g - self.g),
-            b: self.b + t * (other.b - self.b),
-            a: self.a + t * (other.a - self.a),
-        }
+    /// Blend this color (the source) over `backdrop` using the saturation blend mode: the
+    /// saturation of the source with the hue and luminosity of the backdrop.
+    ///
+    /// Uses the `SetLum`/`SetSat` formulas from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendingsaturation)
+    /// specification. Alpha is taken from `backdrop`.
+    pub fn blend_saturation(&self, backdrop: &Color) -> Color {
+        blend_non_separable(self, backdrop, |cs, cb| {
+            set_lum(set_sat(cb, sat(cs)), lum(cb))
+        })
     }
 
-    /// Blend this color with the other one, in the linear RGB color-space. `t` in the range [0..1].
-    pub fn interpolate_linear_rgb(&self, other: &Color, t: f32) -> Color {
-        let (r1, g1, b1, a1) = self.to_linear_rgba();
-        let (r2, g2, b2, a2) = other.to_linear_rgba();
-        Color::from_linear_rgba(
-            r1 + t * (r2 - r1),
-            g1 + t * (g2 - g1),
-            b1 + t * (b2 - b1),
-            a1 + t * (a2 - a1),
-        )
+    /// Blend this color (the source) over `backdrop` using the color blend mode: the hue and
+    /// saturation of the source with the luminosity of the backdrop.
+    ///
+    /// Uses the `SetLum` formula from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendingcolor)
+    /// specification. Alpha is taken from `backdrop`.
+    pub fn blend_color(&self, backdrop: &Color) -> Color {
+        blend_non_separable(self, backdrop, |cs, cb| set_lum(cs, lum(cb)))
     }
 
-    /// Blend this color with the other one, in the HSV color-space. `t` in the range [0..1].
-    pub fn interpolate_hsv(&self, other: &Color, t: f32) -> Color {
-        let (h1, s1, v1, a1) = self.to_hsva();
-        let (h2, s2, v2, a2) = other.to_hsva();
-        Color::from_hsva(
-            interp_angle(h1, h2, t),
-            s1 + t * (s2 - s1),
-            v1 + t * (v2 - v1),
-            a1 + t * (a2 - a1),
-        )
+    /// Blend this color (the source) over `backdrop` using the luminosity blend mode: the
+    /// luminosity of the source with the hue and saturation of the backdrop.
+    ///
+    /// Uses the `SetLum` formula from the W3C [Compositing and Blending](https://www.w3.org/TR/compositing-1/#blendingluminosity)
+    /// specification. Alpha is taken from `backdrop`.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color;
+    ///
+    /// let src = Color::from_rgb(1.0, 1.0, 1.0);
+    /// let backdrop = Color::from_rgb(1.0, 0.0, 0.0);
+    /// assert_eq!(src.blend_luminosity(&backdrop).to_hex_string(), "#ffffff");
+    /// ```
+    pub fn blend_luminosity(&self, backdrop: &Color) -> Color {
+        blend_non_separable(self, backdrop, |cs, cb| set_lum(cb, lum(cs)))
     }
 
-    /// Blend this color with the other one, in the [Oklab](https://bottosson.github.io/posts/oklab/) color-space. `t` in the range [0..1].
-    pub fn interpolate_oklab(&self, other: &Color, t: f32) -> Color {
-        let (l1, a1, b1, alpha1) = self.to_oklaba();
-        let (l2, a2, b2, alpha2) = other.to_oklaba();
-        Color::from_oklaba(
-            l1 + t * (l2 - l1),
-            a1 + t * (a2 - a1),
-            b1 + t * (b2 - b1),
-            alpha1 + t * (alpha2 - alpha1),
-        )
+    /// Composite `src` over `dst` (the backdrop) using the blend `mode` and Porter-Duff
+    /// source-over alpha compositing.
+    ///
+    /// Implements the [Compositing and Blending](https://www.w3.org/TR/compositing-1/#generalformula)
+    /// general formula: the blended color is mixed into the source according to the backdrop
+    /// alpha, then the source is composited over the backdrop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csscolorparser::{BlendMode, Color};
+    ///
+    /// let src = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    /// let dst = Color::from_rgba(0.0, 0.0, 1.0, 1.0);
+    ///
+    /// let c = Color::blend_alpha_compositing(&src, &dst, BlendMode::Normal);
+    /// assert_eq!(c.rgba_u8(), (128, 0, 128, 255));
+    ///
+    /// let c = Color::blend_alpha_compositing(&src, &dst, BlendMode::Multiply);
+    /// assert_eq!(c.rgba_u8(), (0, 0, 128, 255));
+    /// ```
+    pub fn blend_alpha_compositing(src: &Color, dst: &Color, mode: BlendMode) -> Color {
+        let cs = [src.r, src.g, src.b].map(clamp0_1);
+        let cb = [dst.r, dst.g, dst.b].map(clamp0_1);
+        let alpha_s = clamp0_1(src.a);
+        let alpha_b = clamp0_1(dst.a);
+
+        let separable = |f: fn(f32, f32) -> f32| [0, 1, 2].map(|i| f(cs[i], cb[i]));
+
+        let blended = match mode {
+            BlendMode::Normal => cs,
+            BlendMode::Multiply => separable(|s, b| s * b),
+            BlendMode::Screen => separable(screen),
+            BlendMode::Overlay => separable(|s, b| hard_light(b, s)),
+            BlendMode::Darken => separable(f32::min),
+            BlendMode::Lighten => separable(f32::max),
+            BlendMode::ColorDodge => separable(color_dodge),
+            BlendMode::ColorBurn => separable(color_burn),
+            BlendMode::HardLight => separable(hard_light),
+            BlendMode::SoftLight => separable(soft_light),
+            BlendMode::Difference => separable(|s, b| (b - s).abs()),
+            BlendMode::Exclusion => separable(|s, b| b + s - 2.0 * b * s),
+            BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            BlendMode::Color => set_lum(cs, lum(cb)),
+            BlendMode::Luminosity => set_lum(cb, lum(cs)),
+        };
+
+        let alpha = alpha_s + alpha_b * (1.0 - alpha_s);
+        if alpha <= 0.0 {
+            return Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let [r, g, b] = [0, 1, 2].map(|i| {
+            let c = (1.0 - alpha_b) * cs[i] + alpha_b * clamp0_1(blended[i]);
+            (alpha_s * c + alpha_b * cb[i] * (1.0 - alpha_s)) / alpha
+        });
+        Color::from_rgba(r, g, b, alpha)
     }
 }
 
@@ -510,46 +3379,9 @@ mod impl_cint {
         }
     }
 
-    impl From<Color> for EncodedSrgb<f32> {
-        fn from(c: Color) -> Self {
-            let (r, g, b, _) = c.rgba();
-            let (r, g, b) = (r as f32, g as f32, b as f32);
-            EncodedSrgb { r, g, b }
-        }
-    }
-
-    impl From<EncodedSrgb<f32>> for Color {
-        fn from(c: EncodedSrgb<f32>) -> Self {
-            let EncodedSrgb { r, g, b } = c;
-            let (r, g, b) = (r as f32, g as f32, b as f32);
-            Color::from_rgb(r, g, b)
-        }
-    }
-
-    impl From<Color> for Alpha<EncodedSrgb<f32>> {
-        fn from(c: Color) -> Self {
-            let (r, g, b, alpha) = c.rgba();
-            Alpha {
-                color: EncodedSrgb { r, g, b },
-                alpha,
-            }
-        }
-    }
-
-    impl From<Alpha<EncodedSrgb<f32>>> for Color {
-        fn from(c: Alpha<EncodedSrgb<f32>>) -> Self {
-            let Alpha {
-                color: EncodedSrgb { r, g, b },
-                alpha,
-            } = c;
-            Color::from_rgba(r, g, b, alpha)
-        }
-    }
-
     impl From<Color> for Alpha<EncodedSrgb<f32>> {
         fn from(c: Color) -> Self {
             let (r, g, b, alpha) = c.rgba();
-            let (r, g, b, alpha) = (r as f32, g as f32, b as f32, alpha as f32);
             Alpha {
                 color: EncodedSrgb { r, g, b },
                 alpha,
@@ -563,7 +3395,6 @@ mod impl_cint {
                 color: EncodedSrgb { r, g, b },
                 alpha,
             } = c;
-            let (r, g, b, alpha) = (r as f32, g as f32, b as f32, alpha as f32);
             Color::from_rgba(r, g, b, alpha)
         }
     }
@@ -626,6 +3457,21 @@ impl TryFrom<&str> for Color {
     }
 }
 
+// SAFETY (for `AsRef` and `AsMut` below): `Color` is `#[repr(C)]` with exactly four `f32`
+// fields in `r, g, b, a` order, so it has the same size, alignment and layout as `[f32; 4]`.
+
+impl AsRef<[f32]> for Color {
+    fn as_ref(&self) -> &[f32] {
+        unsafe { &*(self as *const Color as *const [f32; 4]) }
+    }
+}
+
+impl AsMut<[f32]> for Color {
+    fn as_mut(&mut self) -> &mut [f32] {
+        unsafe { &mut *(self as *mut Color as *mut [f32; 4]) }
+    }
+}
+
 impl From<(f32, f32, f32, f32)> for Color {
     fn from((r, g, b, a): (f32, f32, f32, f32)) -> Self {
         Color { r, g, b, a }
@@ -639,8 +3485,8 @@ impl From<(f32, f32, f32)> for Color {
 }
 
 impl From<[f32; 4]> for Color {
-    fn from([r, g, b, a]: [f32; 4]) -> Self {
-        Color { r, g, b, a }
+    fn from(arr: [f32; 4]) -> Self {
+        Color::from_array(arr)
     }
 }
 
@@ -674,6 +3520,27 @@ impl From<[u8; 3]> for Color {
     }
 }
 
+impl From<Color> for [f32; 4] {
+    fn from(c: Color) -> Self {
+        c.to_array()
+    }
+}
+
+impl From<Color> for [u8; 4] {
+    fn from(c: Color) -> Self {
+        let (r, g, b, a) = c.rgba_u8();
+        [r, g, b, a]
+    }
+}
+
+/// Generate random opaque colors with `rng.gen::<Color>()`, see [`Color::random()`].
+#[cfg(feature = "rand")]
+impl Distribution<Color> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Color {
+        Color::from_rgb(rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
 /// Convert rust-rgb's `RGB<f32>` type into `Color`.
 #[cfg(feature = "rust-rgb")]
 impl From<RGB<f32>> for Color {
@@ -707,6 +3574,411 @@ impl<'de> Deserialize<'de> for Color {
     }
 }
 
+/// Convert a linear-light value to the sRGB transfer function (gamma compression).
+///
+/// # Examples
+/// ```
+/// use csscolorparser::srgb_compression;
+///
+/// assert_eq!(srgb_compression(0.0), 0.0);
+/// assert!((srgb_compression(0.2159) - 0.502).abs() < 0.001);
+/// ```
+pub fn srgb_compression(x: f32) -> f32 {
+    if x >= 0.0031308 {
+        return 1.055 * x.powf(1.0 / 2.4) - 0.055;
+    }
+    12.92 * x
+}
+
+/// Convert an sRGB encoded value to linear-light (gamma expansion).
+///
+/// # Examples
+/// ```
+/// use csscolorparser::srgb_expansion;
+///
+/// assert_eq!(srgb_expansion(0.0), 0.0);
+/// assert!((srgb_expansion(0.502) - 0.2159).abs() < 0.001);
+/// ```
+pub fn srgb_expansion(x: f32) -> f32 {
+    if x >= 0.04045 {
+        return ((x + 0.055) / 1.055).powf(2.4);
+    }
+    x / 12.92
+}
+
+/// Sort colors by HSL hue angle, in place.
+///
+/// Achromatic colors (black, white and grays), which have no hue, come first, sorted by
+/// lightness. The sort is stable.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::{sort_by_hue, Color};
+///
+/// let mut colors = [
+///     Color::from_rgb(0.0, 0.0, 1.0),
+///     Color::from_rgb(0.5, 0.5, 0.5),
+///     Color::from_rgb(1.0, 0.0, 0.0),
+///     Color::from_rgb(0.0, 1.0, 0.0),
+/// ];
+/// sort_by_hue(&mut colors);
+///
+/// let hex: Vec<_> = colors.iter().map(|c| c.to_hex_string()).collect();
+/// assert_eq!(hex, ["#808080", "#ff0000", "#00ff00", "#0000ff"]);
+/// ```
+pub fn sort_by_hue(colors: &mut [Color]) {
+    colors.sort_by_cached_key(|c| {
+        let (h, s, l, _) = c.to_hsla();
+        // achromatic colors first, by lightness
+        match s > 0.0 {
+            true => (true, TotalF32(h)),
+            false => (false, TotalF32(l)),
+        }
+    });
+}
+
+/// Sort colors by HSL lightness, in place, darkest first. The sort is stable.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::{sort_by_lightness, Color};
+///
+/// let mut colors = [Color::WHITE, Color::from_rgb(1.0, 0.0, 0.0), Color::BLACK];
+/// sort_by_lightness(&mut colors);
+///
+/// assert_eq!(colors[0], Color::BLACK);
+/// assert_eq!(colors[2], Color::WHITE);
+/// ```
+pub fn sort_by_lightness(colors: &mut [Color]) {
+    colors.sort_by_cached_key(|c| TotalF32(c.to_hsla().2));
+}
+
+/// Sort colors by Oklab lightness, in place, darkest first. The sort is stable.
+///
+/// Oklab lightness is perceptually more accurate than HSL lightness, e.g. yellow is lighter
+/// than blue although both have the same HSL lightness.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::{sort_by_oklab_lightness, Color};
+///
+/// let yellow = Color::from_rgb(1.0, 1.0, 0.0);
+/// let blue = Color::from_rgb(0.0, 0.0, 1.0);
+///
+/// let mut colors = [yellow.clone(), blue.clone()];
+/// sort_by_oklab_lightness(&mut colors);
+///
+/// assert_eq!(colors, [blue, yellow]);
+/// ```
+pub fn sort_by_oklab_lightness(colors: &mut [Color]) {
+    colors.sort_by_cached_key(|c| TotalF32(c.to_oklaba().0));
+}
+
+// Sort key ordering floats with `f32::total_cmp()`.
+struct TotalF32(f32);
+
+impl PartialEq for TotalF32 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for TotalF32 {}
+
+impl PartialOrd for TotalF32 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalF32 {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// f32 to IEEE 754 half-precision float bits, rounding to nearest even.
+fn f32_to_f16(x: f32) -> u16 {
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x7f_ffff;
+
+    // infinity and NaN
+    if exp == 0xff {
+        return sign | 0x7c00 | if mant != 0 { 0x200 } else { 0 };
+    }
+
+    let exp = exp - 127 + 15;
+    // overflow to infinity
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+
+    // shift the mantissa (with its implicit leading bit for subnormal results) down to 10 bits
+    let (mut h, shift, mant) = if exp <= 0 {
+        if exp < -10 {
+            return sign;
+        }
+        (0, (14 - exp) as u32, mant | 0x80_0000)
+    } else {
+        ((exp as u32) << 10, 13, mant)
+    };
+    h |= mant >> shift;
+
+    let rem = mant & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    // a carry into the exponent is correct, and may round up to infinity
+    if rem > half || (rem == half && h & 1 == 1) {
+        h += 1;
+    }
+    sign | h as u16
+}
+
+// IEEE 754 half-precision float bits to f32.
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = ((h >> 15) as u32) << 31;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let mant = (h & 0x3ff) as u32;
+
+    let bits = match exp {
+        // zero and subnormal numbers: mant * 2^-24
+        0 => {
+            let v = mant as f32 / 16_777_216.0;
+            return if sign != 0 { -v } else { v };
+        }
+        // infinity and NaN
+        0x1f => sign | 0x7f80_0000 | (mant << 13),
+        _ => sign | ((exp + 127 - 15) << 23) | (mant << 13),
+    };
+    f32::from_bits(bits)
+}
+
+/// ANSI escape sequence to reset the terminal colors.
+#[cfg(feature = "terminal")]
+pub const fn ansi_reset() -> &'static str {
+    "\x1b[0m"
+}
+
+#[cfg(feature = "terminal")]
+static ANSI_BASIC_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+#[cfg(feature = "terminal")]
+fn ansi_256_to_rgb(index: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=15 => ANSI_BASIC_COLORS[index as usize],
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (LEVELS[i / 36], LEVELS[(i / 6) % 6], LEVELS[i % 6])
+        }
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+#[cfg(feature = "ase")]
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Apply a separable blend function `f(source, backdrop)` to each RGB channel.
+fn blend_separable<F: Fn(f32, f32) -> f32>(src: &Color, backdrop: &Color, f: F) -> Color {
+    Color::from_rgba(
+        clamp0_1(f(src.r, backdrop.r)),
+        clamp0_1(f(src.g, backdrop.g)),
+        clamp0_1(f(src.b, backdrop.b)),
+        backdrop.a,
+    )
+}
+
+fn blend_non_separable<F: Fn([f32; 3], [f32; 3]) -> [f32; 3]>(
+    src: &Color,
+    backdrop: &Color,
+    f: F,
+) -> Color {
+    let cs = [src.r, src.g, src.b].map(clamp0_1);
+    let cb = [backdrop.r, backdrop.g, backdrop.b].map(clamp0_1);
+    let [r, g, b] = f(cs, cb).map(clamp0_1);
+    Color::from_rgba(r, g, b, backdrop.a)
+}
+
+fn screen(cs: f32, cb: f32) -> f32 {
+    cb + cs - cb * cs
+}
+
+fn hard_light(cs: f32, cb: f32) -> f32 {
+    if cs <= 0.5 {
+        cb * 2.0 * cs
+    } else {
+        screen(2.0 * cs - 1.0, cb)
+    }
+}
+
+fn color_dodge(cs: f32, cb: f32) -> f32 {
+    if cb <= 0.0 {
+        0.0
+    } else if cs >= 1.0 {
+        1.0
+    } else {
+        (cb / (1.0 - cs)).min(1.0)
+    }
+}
+
+fn color_burn(cs: f32, cb: f32) -> f32 {
+    if cb >= 1.0 {
+        1.0
+    } else if cs <= 0.0 {
+        0.0
+    } else {
+        1.0 - ((1.0 - cb) / cs).min(1.0)
+    }
+}
+
+fn soft_light(cs: f32, cb: f32) -> f32 {
+    if cs <= 0.5 {
+        return cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb);
+    }
+    let d = if cb <= 0.25 {
+        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+    } else {
+        cb.sqrt()
+    };
+    cb + (2.0 * cs - 1.0) * (d - cb)
+}
+
+// Helpers for the non-separable blend modes.
+// https://www.w3.org/TR/compositing-1/#blendingnonseparable
+
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut c = c;
+    if n < 0.0 {
+        c = c.map(|v| l + (v - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        c = c.map(|v| l + (v - l) * (1.0 - l) / (x - l));
+    }
+    c
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color(c.map(|v| v + d))
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+    if max > min {
+        c.map(|v| (v - min) * s / (max - min))
+    } else {
+        [0.0; 3]
+    }
+}
+
+// RGB <=> RYB, from "Computational RYB Color Model and its Applications" (Sugita & Takahashi, 2017)
+
+fn rgb_to_ryb([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let w = r.min(g).min(b);
+    let (r, g, b) = (r - w, g - w, b - w);
+    let max_g = r.max(g).max(b);
+
+    let y = r.min(g);
+    let (r, mut g, mut b) = (r - y, g - y, b);
+    if b > 0.0 && g > 0.0 {
+        b /= 2.0;
+        g /= 2.0;
+    }
+    let (y, b) = (y + g, b + g);
+
+    let max_y = r.max(y).max(b);
+    let n = if max_y > 0.0 { max_g / max_y } else { 0.0 };
+    [r * n + w, y * n + w, b * n + w]
+}
+
+fn ryb_to_rgb([r, y, b]: [f32; 3]) -> [f32; 3] {
+    let w = r.min(y).min(b);
+    let (r, y, b) = (r - w, y - w, b - w);
+    let max_y = r.max(y).max(b);
+
+    let g = y.min(b);
+    let (r, y, mut b, mut g) = (r, y - g, b - g, g);
+    if b > 0.0 && g > 0.0 {
+        b *= 2.0;
+        g *= 2.0;
+    }
+    let (r, g) = (r + y, g + y);
+
+    let max_g = r.max(g).max(b);
+    let n = if max_g > 0.0 { max_y / max_g } else { 0.0 };
+    [r * n + w, g * n + w, b * n + w]
+}
+
+// l, a, b => l, c, h (hue in degrees [0..360])
+fn oklab_to_oklch(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let c = (a * a + b * b).sqrt();
+    let h = normalize_angle(b.atan2(a).to_degrees());
+    (l, c, h)
+}
+
+// `name(l% y z)` or `name(l% y z / alpha)`, used for `lab()` and `lch()`.
+// Lightness is written as a percentage, which is unambiguous for the parser.
+#[cfg(feature = "lab")]
+fn fmt_lab_function(name: &str, l: f32, y: f32, z: f32, alpha: f32) -> String {
+    let (l, y, z) = (fmt_float(l, 4), fmt_float(y, 4), fmt_float(z, 4));
+
+    if alpha < 1.0 {
+        return format!("{}({}% {} {} / {})", name, l, y, z, fmt_float(alpha, 4));
+    }
+
+    format!("{}({}% {} {})", name, l, y, z)
+}
+
+// Format a float with at most `precision` decimal places, without trailing zeros.
+fn fmt_float(t: f32, precision: usize) -> String {
+    let s = format!("{:.*}", precision, t);
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &s
+    };
+    if s == "-0" {
+        return "0".into();
+    }
+    s.into()
+}
+
 fn hue_to_rgb(n1: f32, n2: f32, h: f32) -> f32 {
     let h = modulo(h, 6.0);
 
@@ -793,7 +4065,7 @@ fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
         return (0.0, 0.0, v);
     }
 
-    let s = d / v;
+    let s = if v != 0.0 { d / v } else { 0.0 };
     let dr = (v - r) / d;
     let dg = (v - g) / d;
     let db = (v - b) / d;
@@ -822,11 +4094,8 @@ fn rgb_to_hsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
 
     let d = max - min;
 
-    let s = if l < 0.5 {
-        d / (max + min)
-    } else {
-        d / (2.0 - max - min)
-    };
+    let n = if l < 0.5 { max + min } else { 2.0 - max - min };
+    let s = if n != 0.0 { d / n } else { 0.0 };
 
     let dr = (max - r) / d;
     let dg = (max - g) / d;
@@ -866,6 +4135,67 @@ fn interp_angle(a0: f32, a1: f32, t: f32) -> f32 {
     (a0 + t * delta + 360.0) % 360.0
 }
 
+// Mark the hue of an achromatic color as missing (NaN).
+fn missing_hue(space: ColorSpace, (x, y, z, a): (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+    const EPSILON: f32 = 1e-4;
+    match space {
+        ColorSpace::Hsl | ColorSpace::Hsv if y.abs() < EPSILON => (f32::NAN, y, z, a),
+        ColorSpace::HslUv if y.abs() < 0.01 => (f32::NAN, y, z, a),
+        ColorSpace::Hwb if y + z >= 1.0 - EPSILON => (f32::NAN, y, z, a),
+        ColorSpace::OkLch if y.abs() < 1e-3 => (x, y, f32::NAN, a),
+        #[cfg(feature = "lab")]
+        ColorSpace::Lch if y.abs() < 0.01 => (x, y, f32::NAN, a),
+        _ => (x, y, z, a),
+    }
+}
+
+// A missing component takes the value of the other one; if both are missing they are 0.
+#[inline]
+fn fill_missing(a: f32, b: f32) -> (f32, f32) {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => (0.0, 0.0),
+        (true, false) => (b, b),
+        (false, true) => (a, a),
+        (false, false) => (a, b),
+    }
+}
+
+// Hue interpolation as defined in https://www.w3.org/TR/css-color-4/#hue-interpolation
+fn interp_hue(a0: f32, a1: f32, t: f32, mode: HueInterpolationMode) -> f32 {
+    let a0 = normalize_angle(a0);
+    let a1 = normalize_angle(a1);
+    let d = a1 - a0;
+
+    let delta = match mode {
+        HueInterpolationMode::Shorter => return interp_angle(a0, a1, t),
+        HueInterpolationMode::Longer => {
+            if d > 0.0 && d < 180.0 {
+                d - 360.0
+            } else if d > -180.0 && d <= 0.0 {
+                d + 360.0
+            } else {
+                d
+            }
+        }
+        HueInterpolationMode::Increasing => {
+            if d < 0.0 {
+                d + 360.0
+            } else {
+                d
+            }
+        }
+        HueInterpolationMode::Decreasing => {
+            if d > 0.0 {
+                d - 360.0
+            } else {
+                d
+            }
+        }
+    };
+
+    normalize_angle(a0 + t * delta)
+}
+
 #[cfg(feature = "lab")]
 #[inline]
 fn interp_angle_rad(a0: f32, a1: f32, t: f32) -> f32 {
@@ -887,6 +4217,21 @@ fn modulo(x: f32, n: f32) -> f32 {
 mod tests {
     use super::*;
 
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_debug_string() {
+        let s = Color::from_rgba(1.0, 0.5, 0.0, 0.5).debug_string();
+        let expected = [
+            "Color { r: 1.0, g: 0.5, b: 0.0, a: 0.5 }",
+            "  hex:   #ff800080",
+            "  rgb:   rgba(255,128,0,0.5)",
+            "  hsl:   hsla(30,100%,50%,0.5)",
+            "  oklab: oklab(0.731 0.113 0.148 / 0.5)",
+            "  oklch: oklch(0.731 0.186 52.762 / 0.5)",
+        ];
+        assert_eq!(s, expected.join("\n"));
+    }
+
     #[test]
     fn test_normalize_angle() {
         let data = vec![
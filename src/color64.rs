@@ -0,0 +1,519 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{parse, Color, CssParseOptions, CssStringOptions, ParseColorError};
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[repr(C)]
+/// The color, with double precision components
+///
+/// Use [`Color`] for typical use, and `Color64` when the extra precision matters, e.g. for
+/// repeated conversions in scientific color computation.
+///
+/// `Color64` covers a subset of the `Color` methods: construction from and conversion to RGB,
+/// linear RGB, HSL, HSV, HWB and Oklab, interpolation, mixing, relative luminance and contrast
+/// ratio, all computed in double precision. CSS strings are parsed and formatted through
+/// [`Color`], so with single precision. For anything else (e.g. Lab, blend modes, color names)
+/// convert with [`to_color()`](#method.to_color).
+pub struct Color64 {
+    /// Red
+    pub r: f64,
+    /// Green
+    pub g: f64,
+    /// Blue
+    pub b: f64,
+    /// Alpha
+    pub a: f64,
+}
+
+impl Color64 {
+    /// Opaque black
+    pub const BLACK: Color64 = Color64 {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    };
+
+    /// Opaque white
+    pub const WHITE: Color64 = Color64 {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    };
+
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..1]
+    /// * `g`: Green value [0..1]
+    /// * `b`: Blue value [0..1]
+    pub fn from_rgb(r: f64, g: f64, b: f64) -> Color64 {
+        Color64 { r, g, b, a: 1.0 }
+    }
+
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..1]
+    /// * `g`: Green value [0..1]
+    /// * `b`: Blue value [0..1]
+    /// * `a`: Alpha value [0..1]
+    pub fn from_rgba(r: f64, g: f64, b: f64, a: f64) -> Color64 {
+        Color64 { r, g, b, a }
+    }
+
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..255]
+    /// * `g`: Green value [0..255]
+    /// * `b`: Blue value [0..255]
+    /// * `a`: Alpha value [0..255]
+    pub fn from_rgba_u8(r: u8, g: u8, b: u8, a: u8) -> Color64 {
+        Color64 {
+            r: r as f64 / 255.0,
+            g: g as f64 / 255.0,
+            b: b as f64 / 255.0,
+            a: a as f64 / 255.0,
+        }
+    }
+
+    /// Arguments:
+    ///
+    /// * `r`: Red value [0..1]
+    /// * `g`: Green value [0..1]
+    /// * `b`: Blue value [0..1]
+    /// * `a`: Alpha value [0..1]
+    pub fn from_linear_rgba(r: f64, g: f64, b: f64, a: f64) -> Color64 {
+        Color64::from_rgba(
+            srgb_compression(r),
+            srgb_compression(g),
+            srgb_compression(b),
+            a,
+        )
+    }
+
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn from_hsla(h: f64, s: f64, l: f64, a: f64) -> Color64 {
+        let (r, g, b) = hsl_to_rgb(normalize_angle(h), clamp0_1(s), clamp0_1(l));
+        Color64::from_rgba(clamp0_1(r), clamp0_1(g), clamp0_1(b), clamp0_1(a))
+    }
+
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `v`: Value [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn from_hsva(h: f64, s: f64, v: f64, a: f64) -> Color64 {
+        let (r, g, b) = hsv_to_rgb(normalize_angle(h), clamp0_1(s), clamp0_1(v));
+        Color64::from_rgba(clamp0_1(r), clamp0_1(g), clamp0_1(b), clamp0_1(a))
+    }
+
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `w`: Whiteness [0..1]
+    /// * `b`: Blackness [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn from_hwba(h: f64, w: f64, b: f64, a: f64) -> Color64 {
+        let (r, g, b) = hwb_to_rgb(normalize_angle(h), clamp0_1(w), clamp0_1(b));
+        Color64::from_rgba(clamp0_1(r), clamp0_1(g), clamp0_1(b), a)
+    }
+
+    /// Create color from CSS color string, accepting only the syntax forms enabled in `opts`.
+    /// The components are parsed with single precision.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color64, CssParseOptions};
+    ///
+    /// let opts = CssParseOptions {
+    ///     allow_css4: false,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Color64::from_css_string_with_options("rgb(255, 0, 0)", opts).is_ok());
+    /// assert!(Color64::from_css_string_with_options("rgb(255 0 0 / 50%)", opts).is_err());
+    /// ```
+    pub fn from_css_string_with_options(
+        s: &str,
+        opts: CssParseOptions,
+    ) -> Result<Color64, ParseColorError> {
+        Color::from_css_string_with_options(s, opts).map(Color64::from)
+    }
+
+    /// Arguments:
+    ///
+    /// * `l`: Perceived lightness
+    /// * `a`: How green/red the color is
+    /// * `b`: How blue/yellow the color is
+    /// * `alpha`: Alpha [0..1]
+    #[allow(clippy::excessive_precision)]
+    pub fn from_oklaba(l: f64, a: f64, b: f64, alpha: f64) -> Color64 {
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        let r = 4.0767245293 * l_ - 3.3072168827 * m_ + 0.2307590544 * s_;
+        let g = -1.2681437731 * l_ + 2.6093323231 * m_ - 0.3411344290 * s_;
+        let b = -0.0041119885 * l_ - 0.7034763098 * m_ + 1.7068625689 * s_;
+
+        Color64::from_linear_rgba(r, g, b, alpha)
+    }
+
+    /// Returns: `(r, g, b, a)`
+    ///
+    /// * Red, green, blue and alpha in the range [0..1]
+    pub fn rgba(&self) -> (f64, f64, f64, f64) {
+        (self.r, self.g, self.b, self.a)
+    }
+
+    /// Returns: `(r, g, b, a)`
+    ///
+    /// * Red, green, blue and alpha in the range [0..255]
+    pub fn rgba_u8(&self) -> (u8, u8, u8, u8) {
+        (
+            (self.r * 255.0).round() as u8,
+            (self.g * 255.0).round() as u8,
+            (self.b * 255.0).round() as u8,
+            (self.a * 255.0).round() as u8,
+        )
+    }
+
+    /// Returns: `(r, g, b, a)`
+    ///
+    /// * Red, green, blue and alpha in the range [0..1]
+    pub fn to_linear_rgba(&self) -> (f64, f64, f64, f64) {
+        (
+            srgb_expansion(self.r),
+            srgb_expansion(self.g),
+            srgb_expansion(self.b),
+            self.a,
+        )
+    }
+
+    /// Returns: `(h, s, l, a)`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `l`: Lightness [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn to_hsla(&self) -> (f64, f64, f64, f64) {
+        let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
+        (h, s, l, self.a)
+    }
+
+    /// Returns: `(h, s, v, a)`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `s`: Saturation [0..1]
+    /// * `v`: Value [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn to_hsva(&self) -> (f64, f64, f64, f64) {
+        let (h, s, v) = rgb_to_hsv(self.r, self.g, self.b);
+        (h, s, v, self.a)
+    }
+
+    /// Returns: `(h, w, b, a)`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `w`: Whiteness [0..1]
+    /// * `b`: Blackness [0..1]
+    /// * `a`: Alpha [0..1]
+    pub fn to_hwba(&self) -> (f64, f64, f64, f64) {
+        let (h, _, _) = rgb_to_hsl(self.r, self.g, self.b);
+        let white = self.r.min(self.g.min(self.b));
+        let black = 1.0 - self.r.max(self.g.max(self.b));
+        (h, white, black, self.a)
+    }
+
+    /// Returns: `(l, a, b, alpha)`
+    #[allow(clippy::excessive_precision)]
+    pub fn to_oklaba(&self) -> (f64, f64, f64, f64) {
+        let (r, g, b, _) = self.to_linear_rgba();
+        let l_ = (0.4121656120 * r + 0.5362752080 * g + 0.0514575653 * b).cbrt();
+        let m_ = (0.2118591070 * r + 0.6807189584 * g + 0.1074065790 * b).cbrt();
+        let s_ = (0.0883097947 * r + 0.2818474174 * g + 0.6302613616 * b).cbrt();
+        let l = 0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_;
+        let a = 1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_;
+        let b = 0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_;
+        (l, a, b, self.a)
+    }
+
+    /// Get the RGB hexadecimal color string.
+    pub fn to_hex_string(&self) -> String {
+        self.to_color().to_hex_string()
+    }
+
+    /// Get the CSS `rgb()` format string.
+    pub fn to_rgb_string(&self) -> String {
+        self.to_color().to_rgb_string()
+    }
+
+    /// Format as a CSS color string as described by `opts`, see
+    /// [`Color::to_css_string_with_options()`].
+    pub fn to_css_string_with_options(&self, opts: CssStringOptions) -> String {
+        self.to_color().to_css_string_with_options(opts)
+    }
+
+    /// Get the WCAG [relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance),
+    /// in the range [0..1]. Alpha is ignored.
+    pub fn relative_luminance(&self) -> f64 {
+        let (r, g, b, _) = Color64::from_rgb(clamp0_1(self.r), clamp0_1(self.g), clamp0_1(self.b))
+            .to_linear_rgba();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Get the WCAG [contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between
+    /// this color and `other`, in the range [1..21]. Alpha is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::Color64;
+    ///
+    /// assert!((Color64::BLACK.contrast_ratio(&Color64::WHITE) - 21.0).abs() < 1e-12);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Color64) -> f64 {
+        let l1 = self.relative_luminance();
+        let l2 = other.relative_luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    /// Mix this color with the other one in the RGB color-space, weighted by `self_weight` and
+    /// `other_weight`. Negative weights are treated as 0, if both are 0 this color is returned.
+    pub fn mix_with_weight(&self, other: &Color64, self_weight: f64, other_weight: f64) -> Color64 {
+        let (w1, w2) = (self_weight.max(0.0), other_weight.max(0.0));
+        if w1 + w2 <= 0.0 {
+            return self.clone();
+        }
+        self.interpolate_rgb(other, w2 / (w1 + w2))
+    }
+
+    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
+    pub fn interpolate_rgb(&self, other: &Color64, t: f64) -> Color64 {
+        Color64 {
+            r: self.r + t * (other.r - self.r),
+            g: self.g + t * (other.g - self.g),
+            b: self.b + t * (other.b - self.b),
+            a: self.a + t * (other.a - self.a),
+        }
+    }
+
+    /// Blend this color with the other one, in the linear RGB color-space. `t` in the range [0..1].
+    pub fn interpolate_linear_rgb(&self, other: &Color64, t: f64) -> Color64 {
+        let (r1, g1, b1, a1) = self.to_linear_rgba();
+        let (r2, g2, b2, a2) = other.to_linear_rgba();
+        Color64::from_linear_rgba(
+            r1 + t * (r2 - r1),
+            g1 + t * (g2 - g1),
+            b1 + t * (b2 - b1),
+            a1 + t * (a2 - a1),
+        )
+    }
+
+    /// Blend this color with the other one, in the Oklab color-space. `t` in the range [0..1].
+    pub fn interpolate_oklab(&self, other: &Color64, t: f64) -> Color64 {
+        let (l1, a1, b1, alpha1) = self.to_oklaba();
+        let (l2, a2, b2, alpha2) = other.to_oklaba();
+        Color64::from_oklaba(
+            l1 + t * (l2 - l1),
+            a1 + t * (a2 - a1),
+            b1 + t * (b2 - b1),
+            alpha1 + t * (alpha2 - alpha1),
+        )
+    }
+
+    /// Convert to the single precision [`Color`].
+    ///
+    /// # Examples
+    /// ```
+    /// use csscolorparser::{Color, Color64};
+    ///
+    /// let c = Color64::from_rgb(1.0, 0.5, 0.0);
+    /// assert_eq!(c.to_color(), Color::from_rgb(1.0, 0.5, 0.0));
+    /// ```
+    pub fn to_color(&self) -> Color {
+        Color::from_rgba(self.r as f32, self.g as f32, self.b as f32, self.a as f32)
+    }
+}
+
+impl Default for Color64 {
+    fn default() -> Self {
+        Color64::BLACK
+    }
+}
+
+impl fmt::Display for Color64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (r, g, b, a) = self.rgba();
+        write!(f, "RGBA({},{},{},{})", r, g, b, a)
+    }
+}
+
+impl FromStr for Color64 {
+    type Err = ParseColorError;
+
+    /// Parse a CSS color string. The components are parsed with single precision.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).map(Color64::from)
+    }
+}
+
+impl From<Color> for Color64 {
+    fn from(c: Color) -> Self {
+        c.to_color64()
+    }
+}
+
+impl From<Color64> for Color {
+    fn from(c: Color64) -> Self {
+        c.to_color()
+    }
+}
+
+fn srgb_compression(x: f64) -> f64 {
+    if x >= 0.0031308 {
+        return 1.055 * x.powf(1.0 / 2.4) - 0.055;
+    }
+    12.92 * x
+}
+
+fn srgb_expansion(x: f64) -> f64 {
+    if x >= 0.04045 {
+        return ((x + 0.055) / 1.055).powf(2.4);
+    }
+    x / 12.92
+}
+
+fn clamp0_1(t: f64) -> f64 {
+    t.clamp(0.0, 1.0)
+}
+
+fn normalize_angle(t: f64) -> f64 {
+    t.rem_euclid(360.0)
+}
+
+// The HSL, HSV and HWB conversions below are the double precision versions of the ones used
+// by `Color`.
+
+fn hue_to_rgb(n1: f64, n2: f64, h: f64) -> f64 {
+    let h = h.rem_euclid(6.0);
+
+    if h < 1.0 {
+        return n1 + ((n2 - n1) * h);
+    }
+
+    if h < 3.0 {
+        return n2;
+    }
+
+    if h < 4.0 {
+        return n1 + ((n2 - n1) * (4.0 - h));
+    }
+
+    n1
+}
+
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
+    if s == 0.0 {
+        return (l, l, l);
+    }
+
+    let n2 = if l < 0.5 {
+        l * (1.0 + s)
+    } else {
+        l + s - (l * s)
+    };
+
+    let n1 = 2.0 * l - n2;
+    let h = h / 60.0;
+    let r = hue_to_rgb(n1, n2, h + 2.0);
+    let g = hue_to_rgb(n1, n2, h);
+    let b = hue_to_rgb(n1, n2, h - 2.0);
+    (r, g, b)
+}
+
+fn hwb_to_rgb(hue: f64, white: f64, black: f64) -> (f64, f64, f64) {
+    if white + black >= 1.0 {
+        let l = white / (white + black);
+        return (l, l, l);
+    }
+
+    let (r, g, b) = hsl_to_rgb(hue, 1.0, 0.5);
+    let r = r * (1.0 - white - black) + white;
+    let g = g * (1.0 - white - black) + white;
+    let b = b * (1.0 - white - black) + white;
+    (r, g, b)
+}
+
+#[allow(clippy::float_cmp)]
+fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (f64, f64, f64) {
+    let l = (2.0 - s) * v / 2.0;
+
+    let s = if l != 0.0 {
+        if l == 1.0 {
+            0.0
+        } else if l < 0.5 {
+            s * v / (l * 2.0)
+        } else {
+            s * v / (2.0 - l * 2.0)
+        }
+    } else {
+        s
+    };
+
+    hsl_to_rgb(h, s, l)
+}
+
+// Hue of the RGB color in the range [0..360], `max` and `d` are the largest component and the
+// difference between the largest and the smallest.
+#[allow(clippy::float_cmp)]
+fn rgb_hue(r: f64, g: f64, b: f64, max: f64, d: f64) -> f64 {
+    let dr = (max - r) / d;
+    let dg = (max - g) / d;
+    let db = (max - b) / d;
+
+    let h = if r == max {
+        db - dg
+    } else if g == max {
+        2.0 + dr - db
+    } else {
+        4.0 + dg - dr
+    };
+
+    normalize_angle(h * 60.0)
+}
+
+fn rgb_to_hsv(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let v = r.max(g.max(b));
+    let d = v - r.min(g.min(b));
+
+    if d == 0.0 {
+        return (0.0, 0.0, v);
+    }
+
+    let s = if v != 0.0 { d / v } else { 0.0 };
+    (rgb_hue(r, g, b, v, d), s, v)
+}
+
+#[allow(clippy::float_cmp)]
+fn rgb_to_hsl(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let min = r.min(g.min(b));
+    let max = r.max(g.max(b));
+    let l = (max + min) / 2.0;
+
+    if min == max {
+        return (0.0, 0.0, l);
+    }
+
+    let d = max - min;
+
+    let n = if l < 0.5 { max + min } else { 2.0 - max - min };
+    let s = if n != 0.0 { d / n } else { 0.0 };
+    (rgb_hue(r, g, b, max, d), s, l)
+}
@@ -0,0 +1,403 @@
+#![allow(clippy::excessive_precision)]
+
+/// Color spaces used by the conversion and interpolation functions.
+///
+/// `Color` is always stored as sRGB; this only selects how its components are converted.
+///
+/// Some variants depend on crate features, so matches outside this crate need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ColorSpace {
+    /// sRGB: `(r, g, b)` in the range [0..1]
+    Srgb,
+    /// Linear sRGB: `(r, g, b)` in the range [0..1]
+    SrgbLinear,
+    /// HSL: `(h, s, l)`, hue angle [0..360], saturation and lightness [0..1]
+    Hsl,
+    /// HSV: `(h, s, v)`, hue angle [0..360], saturation and value [0..1]
+    Hsv,
+    /// HWB: `(h, w, b)`, hue angle [0..360], whiteness and blackness [0..1]
+    Hwb,
+    /// [Oklab](https://bottosson.github.io/posts/oklab/): `(l, a, b)`
+    OkLab,
+    /// Oklch: `(l, c, h)`, hue angle [0..360]
+    OkLch,
+    #[cfg(feature = "lab")]
+    /// CIE Lab: `(l, a, b)`, lightness [0..100]
+    Lab,
+    #[cfg(feature = "lab")]
+    /// CIE LCH: `(l, c, h)`, lightness [0..100], hue angle in radians
+    Lch,
+    /// CIE XYZ with D65 white point
+    XyzD65,
+    /// CIE XYZ with D50 white point
+    XyzD50,
+    /// Display P3: `(r, g, b)` in the range [0..1]
+    DisplayP3,
+    /// [HSLuv](https://www.hsluv.org/): `(h, s, l)`, hue angle [0..360], saturation and lightness [0..100]
+    HslUv,
+    /// ITU-R BT.2020: `(r, g, b)` in the range [0..1]
+    Rec2020,
+    #[cfg(feature = "wide-gamut")]
+    /// Adobe RGB (1998): `(r, g, b)` in the range [0..1]
+    A98Rgb,
+}
+
+/// Hue interpolation methods, as defined in [CSS Color Module Level 4](https://www.w3.org/TR/css-color-4/#hue-interpolation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HueInterpolationMode {
+    /// Take the shorter arc between the two hues.
+    #[default]
+    Shorter,
+    /// Take the longer arc between the two hues.
+    Longer,
+    /// Always increase the hue angle.
+    Increasing,
+    /// Always decrease the hue angle.
+    Decreasing,
+}
+
+// Matrices from https://www.w3.org/TR/css-color-4/#color-conversion-code
+
+type Matrix = [[f32; 3]; 3];
+
+pub(crate) const LINEAR_SRGB_TO_XYZ_D65: Matrix = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+pub(crate) const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+
+pub(crate) const LINEAR_P3_TO_XYZ_D65: Matrix = [
+    [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+    [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+    [0.0, 0.04511338185890264, 1.043944368900976],
+];
+
+pub(crate) const XYZ_D65_TO_LINEAR_P3: Matrix = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [
+        -0.8294889695615747,
+        1.7626640603183463,
+        0.023624685841943577,
+    ],
+    [
+        0.03584583024378447,
+        -0.07617238926804182,
+        0.9568845240076872,
+    ],
+];
+
+pub(crate) const XYZ_D65_TO_LINEAR_REC2020: Matrix = [
+    [
+        1.7166511879712674,
+        -0.35567078377639233,
+        -0.25336628137365974,
+    ],
+    [-0.6666843518324892, 1.6164812366349395, 0.01576854581391113],
+    [
+        0.017639857445310783,
+        -0.042770613257808524,
+        0.9421031212354738,
+    ],
+];
+
+pub(crate) const LINEAR_REC2020_TO_XYZ_D65: Matrix = [
+    [0.6369580483012914, 0.14461690358620832, 0.1688809751641721],
+    [0.2627002120112671, 0.6779980715188708, 0.05930171646986196],
+    [0.0, 0.028072693049087428, 1.060985057710791],
+];
+
+#[cfg(feature = "wide-gamut")]
+pub(crate) const LINEAR_A98_TO_XYZ_D65: Matrix = [
+    [0.5766690429101308, 0.18555823790654627, 0.18822864623499472],
+    [0.29734497525053616, 0.627363566255466, 0.07529145849399789],
+    [
+        0.027031361386412378,
+        0.07068885253582714,
+        0.9913375368376389,
+    ],
+];
+
+#[cfg(feature = "wide-gamut")]
+pub(crate) const XYZ_D65_TO_LINEAR_A98: Matrix = [
+    [2.041587903810746, -0.5650069742788596, -0.3447313507783295],
+    [-0.9692436362808798, 1.8759675015077206, 0.04155505740717561],
+    [
+        0.013444280632031024,
+        -0.11836239223101824,
+        1.0151749943912054,
+    ],
+];
+
+// Bradford chromatic adaptation
+pub(crate) const XYZ_D65_TO_XYZ_D50: Matrix = [
+    [
+        1.0479298208405488,
+        0.022946793341019088,
+        -0.05019222954313557,
+    ],
+    [
+        0.029627815688159344,
+        0.990434484573249,
+        -0.01707382502938514,
+    ],
+    [
+        -0.009243058152591178,
+        0.015055144896577895,
+        0.7518742899580008,
+    ],
+];
+
+pub(crate) const XYZ_D50_TO_XYZ_D65: Matrix = [
+    [
+        0.9554734527042182,
+        -0.023098536874261423,
+        0.0632593086610217,
+    ],
+    [
+        -0.028369706963208136,
+        1.0099954580058226,
+        0.021041398966943008,
+    ],
+    [
+        0.012314001688319899,
+        -0.020507696433477912,
+        1.3303659366080753,
+    ],
+];
+
+pub(crate) fn mul_matrix(m: &Matrix, x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    (
+        m[0][0] * x + m[0][1] * y + m[0][2] * z,
+        m[1][0] * x + m[1][1] * y + m[1][2] * z,
+        m[2][0] * x + m[2][1] * y + m[2][2] * z,
+    )
+}
+
+// linear => gamma encoded, using the sRGB transfer function (also used by Display P3)
+pub(crate) fn srgb_from_linear(x: f32) -> f32 {
+    let sign = x.signum();
+    let x = x.abs();
+    if x > 0.0031308 {
+        return sign * (1.055 * x.powf(1.0 / 2.4) - 0.055);
+    }
+    sign * 12.92 * x
+}
+
+// gamma encoded => linear, using the sRGB transfer function (also used by Display P3)
+pub(crate) fn srgb_to_linear(x: f32) -> f32 {
+    let sign = x.signum();
+    let x = x.abs();
+    if x > 0.04045 {
+        return sign * ((x + 0.055) / 1.055).powf(2.4);
+    }
+    sign * x / 12.92
+}
+
+pub(crate) fn rec2020_from_linear(x: f32) -> f32 {
+    const ALPHA: f32 = 1.09929682680944;
+    const BETA: f32 = 0.018053968510807;
+    let sign = x.signum();
+    let x = x.abs();
+    if x > BETA {
+        return sign * (ALPHA * x.powf(0.45) - (ALPHA - 1.0));
+    }
+    sign * 4.5 * x
+}
+
+// Adobe RGB (1998) uses a pure gamma of 563/256 (the nominal 2.2).
+#[cfg(feature = "wide-gamut")]
+const A98_GAMMA: f32 = 563.0 / 256.0;
+
+#[cfg(feature = "wide-gamut")]
+pub(crate) fn a98_from_linear(x: f32) -> f32 {
+    x.signum() * x.abs().powf(1.0 / A98_GAMMA)
+}
+
+#[cfg(feature = "wide-gamut")]
+pub(crate) fn a98_to_linear(x: f32) -> f32 {
+    x.signum() * x.abs().powf(A98_GAMMA)
+}
+
+pub(crate) fn rec2020_to_linear(x: f32) -> f32 {
+    const ALPHA: f32 = 1.09929682680944;
+    const BETA: f32 = 0.018053968510807;
+    let sign = x.signum();
+    let x = x.abs();
+    if x > BETA * 4.5 {
+        return sign * ((x + ALPHA - 1.0) / ALPHA).powf(1.0 / 0.45);
+    }
+    sign * x / 4.5
+}
+
+// D50 white point, as used by Photoshop's Lab mode
+#[cfg(feature = "lab")]
+const D50_WHITE: [f32; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+#[cfg(feature = "lab")]
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+#[cfg(feature = "lab")]
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+// XYZ (D50) => CIE Lab (D50)
+#[cfg(feature = "lab")]
+pub(crate) fn xyz_d50_to_lab(x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    let f = |t: f32| {
+        if t > LAB_EPSILON {
+            t.cbrt()
+        } else {
+            (LAB_KAPPA * t + 16.0) / 116.0
+        }
+    };
+    let fx = f(x / D50_WHITE[0]);
+    let fy = f(y / D50_WHITE[1]);
+    let fz = f(z / D50_WHITE[2]);
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+// CIE Lab (D50) => XYZ (D50)
+#[cfg(feature = "lab")]
+pub(crate) fn lab_to_xyz_d50(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+    let f_inv = |t: f32| {
+        if t.powi(3) > LAB_EPSILON {
+            t.powi(3)
+        } else {
+            (116.0 * t - 16.0) / LAB_KAPPA
+        }
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        fy.powi(3)
+    } else {
+        l / LAB_KAPPA
+    };
+    (
+        f_inv(fx) * D50_WHITE[0],
+        y * D50_WHITE[1],
+        f_inv(fz) * D50_WHITE[2],
+    )
+}
+
+const HSLUV_REF_U: f32 = 0.19783000664283;
+const HSLUV_REF_V: f32 = 0.46831999493879;
+const HSLUV_KAPPA: f32 = 903.2962962;
+const HSLUV_EPSILON: f32 = 0.0088564516;
+
+// XYZ (D65) => CIE Luv
+pub(crate) fn xyz_to_luv(x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    let l = if y <= HSLUV_EPSILON {
+        y * HSLUV_KAPPA
+    } else {
+        116.0 * y.cbrt() - 16.0
+    };
+    if l == 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+    let d = x + 15.0 * y + 3.0 * z;
+    (
+        l,
+        13.0 * l * (4.0 * x / d - HSLUV_REF_U),
+        13.0 * l * (9.0 * y / d - HSLUV_REF_V),
+    )
+}
+
+// CIE Luv => XYZ (D65)
+pub(crate) fn luv_to_xyz(l: f32, u: f32, v: f32) -> (f32, f32, f32) {
+    if l <= 1e-6 {
+        return (0.0, 0.0, 0.0);
+    }
+    let var_u = u / (13.0 * l) + HSLUV_REF_U;
+    let var_v = v / (13.0 * l) + HSLUV_REF_V;
+    let y = if l <= 8.0 {
+        l / HSLUV_KAPPA
+    } else {
+        ((l + 16.0) / 116.0).powi(3)
+    };
+    let x = 9.0 * y * var_u / (4.0 * var_v);
+    let z = (9.0 * y - 15.0 * var_v * y - var_v * x) / (3.0 * var_v);
+    (x, y, z)
+}
+
+// XYZ (D65) => h, s, l
+pub(crate) fn xyz_to_hsluv(x: f32, y: f32, z: f32) -> (f32, f32, f32) {
+    let (l, u, v) = xyz_to_luv(x, y, z);
+
+    // Luv => LCHuv
+    let c = (u * u + v * v).sqrt();
+    let h = if c < 1e-3 {
+        0.0
+    } else {
+        let h = v.atan2(u).to_degrees();
+        if h < 0.0 {
+            h + 360.0
+        } else {
+            h
+        }
+    };
+
+    // LCHuv => HSLuv
+    if l > 99.99999 {
+        return (h, 0.0, 100.0);
+    }
+    if l < 1e-6 {
+        return (h, 0.0, 0.0);
+    }
+    (h, c / hsluv_max_chroma(l, h) * 100.0, l)
+}
+
+// h, s, l => XYZ (D65)
+pub(crate) fn hsluv_to_xyz(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
+    if l <= 1e-6 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    // HSLuv => LCHuv
+    let c = if l > 99.99999 {
+        0.0
+    } else {
+        hsluv_max_chroma(l, h) / 100.0 * s
+    };
+
+    // LCHuv => Luv
+    let (sin_h, cos_h) = h.to_radians().sin_cos();
+    luv_to_xyz(l, c * cos_h, c * sin_h)
+}
+
+// Maximum chroma inside the sRGB gamut for the given lightness and hue.
+pub(crate) fn hsluv_max_chroma(l: f32, h: f32) -> f32 {
+    let sub1 = (l + 16.0).powi(3) / 1560896.0;
+    let sub2 = if sub1 > HSLUV_EPSILON {
+        sub1
+    } else {
+        l / HSLUV_KAPPA
+    };
+    let (sin_h, cos_h) = h.to_radians().sin_cos();
+    let mut min = f32::MAX;
+
+    for [m1, m2, m3] in XYZ_D65_TO_LINEAR_SRGB {
+        for t in 0..2 {
+            let t = t as f32;
+            let top1 = (284517.0 * m1 - 94839.0 * m3) * sub2;
+            let top2 =
+                (838422.0 * m3 + 769860.0 * m2 + 731718.0 * m1) * l * sub2 - 769860.0 * t * l;
+            let bottom = (632260.0 * m3 - 126452.0 * m2) * sub2 + 126452.0 * t;
+            let length = (top2 / bottom) / (sin_h - (top1 / bottom) * cos_h);
+            if length >= 0.0 && length < min {
+                min = length;
+            }
+        }
+    }
+
+    min
+}
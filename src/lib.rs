@@ -5,6 +5,8 @@
 //! ## Supported Color Format
 //!
 //! * [Named colors](https://www.w3.org/TR/css-color-4/#named-colors)
+//! * [X11 color names](https://en.wikipedia.org/wiki/X11_color_names) like `dodgerblue1` or `gray50` (`extended-named-colors` feature)
+//! * [System colors](https://www.w3.org/TR/css-color-4/#css-system-colors) (`system-colors` feature)
 //! * RGB hexadecimal (with and without `#` prefix)
 //!      + Short format `#rgb`
 //!      + Short format with alpha `#rgba`
@@ -15,6 +17,13 @@
 //! * `hwb()`
 //! * `lab()`
 //! * `lch()`
+//! * `oklab()`
+//! * `oklch()`
+//! * `color()` with `srgb`, `srgb-linear`, `display-p3`, `rec2020`, `xyz`, `xyz-d50` and `xyz-d65`
+//!   (and `a98-rgb` with the `wide-gamut` feature)
+//! * The `none` keyword for missing components
+//! * [Relative color syntax](https://www.w3.org/TR/css-color-5/#relative-colors) with `calc()`
+//! * [`color-mix()`](https://www.w3.org/TR/css-color-5/#color-mix)
 //! * `hwba()`, `hsv()`, `hsva()` - not in CSS standard.
 //!
 //! ### Example Color Format
@@ -47,6 +56,12 @@
 //! hwb(480deg 0% 0% / 100%)
 //! hsv(120,100%,100%)
 //! hsv(120deg 100% 100% / 100%)
+//! oklab(0.8664 -0.2339 0.1794)
+//! oklch(0.8664 0.2948 142.5)
+//! color(srgb 0 1 0)
+//! rgb(none 255 none)
+//! rgb(from #f00 r calc(g + 255) b)
+//! color-mix(in oklab, red 40%, blue)
 //! ```
 //! </details>
 //!
@@ -98,9 +113,38 @@
 //! * `rust-rgb`: Enables converting from [`rgb`](https://crates.io/crates/rgb) crate types into `Color`.
 //! * `cint`: Enables converting [`cint`](https://crates.io/crates/cint) crate types to and from `Color`.
 //! * `serde`: Enables serializing (into HEX string) and deserializing (from any supported string color format) using [`serde`](https://serde.rs/) framework.
+//! * `terminal`: Enables ANSI terminal color support (truecolor escape sequences and the xterm 256-color palette).
+//! * `extended-named-colors`: Enables parsing the extended [X11 color names](https://en.wikipedia.org/wiki/X11_color_names) (e.g. `aquamarine1`, `dodgerblue4`, `gray50`) that are not CSS named colors. Implies `named-colors`.
+//! * `system-colors`: Enables parsing [system colors](https://www.w3.org/TR/css-color-4/#css-system-colors) like `Canvas` and `LinkText`.
+//! * `css5`: Enables [`Color::from_css_color_level5_relative()`](struct.Color.html#method.from_css_color_level5_relative) for relative colors with a `Color` as the origin.
+//! * `wide-gamut`: Enables the [Adobe RGB (1998)](https://www.w3.org/TR/css-color-4/#predefined-a98-rgb) color space (`color(a98-rgb r g b)`).
+//! * `ase`: Enables encoding and decoding [ASE (Adobe Swatch Exchange)](http://www.selapa.net/swatches/colors/fileformats.php#adobe_ase) color entries.
+//! * `okhsl`: Enables converting to and from the [Okhsl](https://bottosson.github.io/posts/colorpicker/) color space.
+//! * `okhsv`: Enables converting to and from the [Okhsv](https://bottosson.github.io/posts/colorpicker/) color space.
+//! * `rand`: Enables generating random colors using the [`rand`](https://crates.io/crates/rand) crate.
 
 mod color;
+mod color64;
+mod color_space;
+#[cfg(any(feature = "okhsl", feature = "okhsv"))]
+mod ok_color;
 mod parser;
+#[cfg(feature = "system-colors")]
+mod system_colors;
 
-pub use color::Color;
-pub use parser::{parse, ParseColorError};
+#[cfg(feature = "terminal")]
+pub use color::ansi_reset;
+pub use color::{
+    sort_by_hue, sort_by_lightness, sort_by_oklab_lightness, srgb_compression, srgb_expansion,
+    BlendMode, Color, CssFormat, CssStringOptions,
+};
+pub use color64::Color64;
+pub use color_space::{ColorSpace, HueInterpolationMode};
+pub use parser::{
+    parse, parse_all, parse_css_function, parse_gradient_stops, parse_svg_color_attribute,
+    ColorParser, CssParseOptions, GradientStop, ParseColorContextError, ParseColorError, SvgColor,
+};
+#[cfg(feature = "named-colors")]
+pub use parser::{parse_named, parse_named_css1, parse_named_css3, parse_named_css4, CssVersion};
+#[cfg(feature = "system-colors")]
+pub use system_colors::{CssSystemColor, SystemColorResolver};
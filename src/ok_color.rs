@@ -0,0 +1,405 @@
+// Okhsl and Okhsv color spaces, ported from Björn Ottosson's reference implementation.
+// https://bottosson.github.io/posts/colorpicker/
+
+#![allow(clippy::excessive_precision)]
+
+use std::f32::consts::PI;
+
+// Lightness and chroma of a cusp.
+#[derive(Clone, Copy)]
+struct Lc {
+    l: f32,
+    c: f32,
+}
+
+// Oklab to linear sRGB, using the matrices of the reference implementation which map sRGB
+// white exactly to `a = b = 0`.
+fn oklab_to_linear_srgb(l: f32, a: f32, b: f32) -> [f32; 3] {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ]
+}
+
+// Linear sRGB to Oklab, the inverse of `oklab_to_linear_srgb()`.
+fn linear_srgb_to_oklab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let l_ = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m_ = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s_ = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_,
+        1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_,
+        0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_,
+    )
+}
+
+// Maximum saturation `S = C / L` possible for the hue `(a, b)` (normalized) that fits in sRGB.
+fn compute_max_saturation(a: f32, b: f32) -> f32 {
+    // Select the sRGB component that goes below zero first, and the polynomial fit for it.
+    let (k0, k1, k2, k3, k4, wl, wm, ws) = if -1.88170328 * a - 0.80936493 * b > 1.0 {
+        // red
+        (
+            1.19086277,
+            1.76576728,
+            0.59662641,
+            0.75515197,
+            0.56771245,
+            4.0767416621,
+            -3.3077115913,
+            0.2309699292,
+        )
+    } else if 1.81444104 * a - 1.19445276 * b > 1.0 {
+        // green
+        (
+            0.73956515,
+            -0.45954404,
+            0.08285427,
+            0.12541070,
+            0.14503204,
+            -1.2684380046,
+            2.6097574011,
+            -0.3413193965,
+        )
+    } else {
+        // blue
+        (
+            1.35733652,
+            -0.00915799,
+            -1.15130210,
+            -0.50559606,
+            0.00692167,
+            -0.0041960863,
+            -0.7034186147,
+            1.7076147010,
+        )
+    };
+
+    let s = k0 + k1 * a + k2 * b + k3 * a * a + k4 * a * b;
+
+    // One step of Halley's method to refine the approximation.
+    let k_l = 0.3963377774 * a + 0.2158037573 * b;
+    let k_m = -0.1055613458 * a - 0.0638541728 * b;
+    let k_s = -0.0894841775 * a - 1.2914855480 * b;
+
+    let l_ = 1.0 + s * k_l;
+    let m_ = 1.0 + s * k_m;
+    let s_ = 1.0 + s * k_s;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s3 = s_ * s_ * s_;
+
+    let l_ds = 3.0 * k_l * l_ * l_;
+    let m_ds = 3.0 * k_m * m_ * m_;
+    let s_ds = 3.0 * k_s * s_ * s_;
+
+    let l_ds2 = 6.0 * k_l * k_l * l_;
+    let m_ds2 = 6.0 * k_m * k_m * m_;
+    let s_ds2 = 6.0 * k_s * k_s * s_;
+
+    let f = wl * l + wm * m + ws * s3;
+    let f1 = wl * l_ds + wm * m_ds + ws * s_ds;
+    let f2 = wl * l_ds2 + wm * m_ds2 + ws * s_ds2;
+
+    s - f * f1 / (f1 * f1 - 0.5 * f * f2)
+}
+
+// The lightness and chroma of the sRGB gamut cusp for the hue `(a, b)` (normalized).
+fn find_cusp(a: f32, b: f32) -> Lc {
+    let s_cusp = compute_max_saturation(a, b);
+    let [r, g, b] = oklab_to_linear_srgb(1.0, s_cusp * a, s_cusp * b);
+    let l_cusp = (1.0 / r.max(g).max(b)).cbrt();
+    Lc {
+        l: l_cusp,
+        c: l_cusp * s_cusp,
+    }
+}
+
+#[cfg(feature = "okhsl")]
+// Find the intersection of the line from `(l0, 0)` to `(l1, c1)` with the sRGB gamut boundary,
+// for the hue `(a, b)` (normalized). Returns `t` along the line.
+fn find_gamut_intersection(a: f32, b: f32, l1: f32, c1: f32, l0: f32, cusp: Lc) -> f32 {
+    if ((l1 - l0) * cusp.c - (cusp.l - l0) * c1) <= 0.0 {
+        // lower half
+        return cusp.c * l0 / (c1 * cusp.l + cusp.c * (l0 - l1));
+    }
+
+    // upper half, first intersect with the triangle
+    let t = cusp.c * (l0 - 1.0) / (c1 * (cusp.l - 1.0) + cusp.c * (l0 - l1));
+
+    // then one step of Halley's method
+    let dl = l1 - l0;
+    let dc = c1;
+
+    let k_l = 0.3963377774 * a + 0.2158037573 * b;
+    let k_m = -0.1055613458 * a - 0.0638541728 * b;
+    let k_s = -0.0894841775 * a - 1.2914855480 * b;
+
+    let l_dt = dl + dc * k_l;
+    let m_dt = dl + dc * k_m;
+    let s_dt = dl + dc * k_s;
+
+    let l = l0 * (1.0 - t) + t * l1;
+    let c = t * c1;
+
+    let l_ = l + c * k_l;
+    let m_ = l + c * k_m;
+    let s_ = l + c * k_s;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    let ldt = 3.0 * l_dt * l_ * l_;
+    let mdt = 3.0 * m_dt * m_ * m_;
+    let sdt = 3.0 * s_dt * s_ * s_;
+
+    let ldt2 = 6.0 * l_dt * l_dt * l_;
+    let mdt2 = 6.0 * m_dt * m_dt * m_;
+    let sdt2 = 6.0 * s_dt * s_dt * s_;
+
+    let step = |w: [f32; 3]| {
+        let f = w[0] * l + w[1] * m + w[2] * s - 1.0;
+        let f1 = w[0] * ldt + w[1] * mdt + w[2] * sdt;
+        let f2 = w[0] * ldt2 + w[1] * mdt2 + w[2] * sdt2;
+        let u = f1 / (f1 * f1 - 0.5 * f * f2);
+        if u >= 0.0 {
+            -f * u
+        } else {
+            f32::MAX
+        }
+    };
+
+    let t_r = step([4.0767416621, -3.3077115913, 0.2309699292]);
+    let t_g = step([-1.2684380046, 2.6097574011, -0.3413193965]);
+    let t_b = step([-0.0041960863, -0.7034186147, 1.7076147010]);
+
+    t + t_r.min(t_g).min(t_b)
+}
+
+// Lightness estimate closer to the CIELab L*, mapping Oklab L to Okhsl/Okhsv L.
+fn toe(x: f32) -> f32 {
+    const K1: f32 = 0.206;
+    const K2: f32 = 0.03;
+    const K3: f32 = (1.0 + K1) / (1.0 + K2);
+    0.5 * (K3 * x - K1 + ((K3 * x - K1) * (K3 * x - K1) + 4.0 * K2 * K3 * x).sqrt())
+}
+
+// Inverse of `toe()`.
+fn toe_inv(x: f32) -> f32 {
+    const K1: f32 = 0.206;
+    const K2: f32 = 0.03;
+    const K3: f32 = (1.0 + K1) / (1.0 + K2);
+    (x * x + K1 * x) / (K3 * (x + K2))
+}
+
+// The cusp as saturation `S = C / L` and `T = C / (1 - L)`.
+fn to_st(cusp: Lc) -> (f32, f32) {
+    (cusp.c / cusp.l, cusp.c / (1.0 - cusp.l))
+}
+
+#[cfg(feature = "okhsl")]
+// Smooth approximation of the gamut boundary's S and T, without the sharp cusp.
+fn get_st_mid(a: f32, b: f32) -> (f32, f32) {
+    let s = 0.11516993
+        + 1.0
+            / (7.44778970
+                + 4.15901240 * b
+                + a * (-2.19557347
+                    + 1.75198401 * b
+                    + a * (-2.13704948 - 10.02301043 * b
+                        + a * (-4.24894561 + 5.38770819 * b + 4.69891013 * a))));
+    let t = 0.11239642
+        + 1.0
+            / (1.61320320 - 0.68124379 * b
+                + a * (0.40370612
+                    + 0.90148123 * b
+                    + a * (-0.27087943
+                        + 0.61223990 * b
+                        + a * (0.00299215 - 0.45399568 * b - 0.14661872 * a))));
+    (s, t)
+}
+
+#[cfg(feature = "okhsl")]
+// The chroma values `(c_0, c_mid, c_max)` Okhsl saturation is interpolated between.
+fn get_cs(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let cusp = find_cusp(a, b);
+
+    let c_max = find_gamut_intersection(a, b, l, 1.0, l, cusp);
+    let (s_max, t_max) = to_st(cusp);
+
+    // scale factor to compensate for the curved part of the gamut shape
+    let k = c_max / (l * s_max).min((1.0 - l) * t_max);
+
+    let c_mid = {
+        let (s_mid, t_mid) = get_st_mid(a, b);
+        // soft minimum instead of a sharp triangle shape, for a smooth chroma
+        let c_a = l * s_mid;
+        let c_b = (1.0 - l) * t_mid;
+        0.9 * k
+            * (1.0 / (1.0 / c_a.powi(4) + 1.0 / c_b.powi(4)))
+                .sqrt()
+                .sqrt()
+    };
+
+    let c_0 = {
+        // the shape is independent of hue here, values are roughly the average of S and T
+        let c_a = l * 0.4;
+        let c_b = (1.0 - l) * 0.8;
+        (1.0 / (1.0 / (c_a * c_a) + 1.0 / (c_b * c_b))).sqrt()
+    };
+
+    (c_0, c_mid, c_max)
+}
+
+#[cfg(feature = "okhsl")]
+// Okhsl (hue in degrees) to linear sRGB.
+pub(crate) fn okhsl_to_linear_srgb(h: f32, s: f32, l: f32) -> [f32; 3] {
+    if l >= 1.0 {
+        return [1.0; 3];
+    }
+    if l <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let h = h.to_radians();
+    let (a_, b_) = (h.cos(), h.sin());
+    let lightness = toe_inv(l);
+
+    let (c_0, c_mid, c_max) = get_cs(lightness, a_, b_);
+
+    const MID: f32 = 0.8;
+    const MID_INV: f32 = 1.25;
+
+    let c = if s < MID {
+        let t = MID_INV * s;
+        let k_1 = MID * c_0;
+        let k_2 = 1.0 - k_1 / c_mid;
+        t * k_1 / (1.0 - k_2 * t)
+    } else {
+        let t = (s - MID) / (1.0 - MID);
+        let k_0 = c_mid;
+        let k_1 = (1.0 - MID) * c_mid * c_mid * MID_INV * MID_INV / c_0;
+        let k_2 = 1.0 - k_1 / (c_max - c_mid);
+        k_0 + t * k_1 / (1.0 - k_2 * t)
+    };
+
+    oklab_to_linear_srgb(lightness, c * a_, c * b_)
+}
+
+#[cfg(feature = "okhsl")]
+// Linear sRGB to Okhsl (hue in degrees, 0 for achromatic colors).
+pub(crate) fn linear_srgb_to_okhsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (lightness, a, b) = linear_srgb_to_oklab(r, g, b);
+    let l = toe(lightness);
+    let c = (a * a + b * b).sqrt();
+
+    if c < 1e-5 || l <= 0.0 || l >= 1.0 {
+        return (0.0, 0.0, l.clamp(0.0, 1.0));
+    }
+
+    let (a_, b_) = (a / c, b / c);
+    let h = (0.5 + 0.5 * (-b).atan2(-a) / PI) * 360.0;
+
+    let (c_0, c_mid, c_max) = get_cs(lightness, a_, b_);
+
+    const MID: f32 = 0.8;
+    const MID_INV: f32 = 1.25;
+
+    let s = if c < c_mid {
+        let k_1 = MID * c_0;
+        let k_2 = 1.0 - k_1 / c_mid;
+        let t = c / (k_1 + k_2 * c);
+        t * MID
+    } else {
+        let k_0 = c_mid;
+        let k_1 = (1.0 - MID) * c_mid * c_mid * MID_INV * MID_INV / c_0;
+        let k_2 = 1.0 - k_1 / (c_max - c_mid);
+        let t = (c - k_0) / (k_1 + k_2 * (c - k_0));
+        MID + (1.0 - MID) * t
+    };
+
+    (h % 360.0, s.clamp(0.0, 1.0), l)
+}
+
+#[cfg(feature = "okhsv")]
+// Okhsv (hue in degrees) to linear sRGB.
+pub(crate) fn okhsv_to_linear_srgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    if v <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let h = h.to_radians();
+    let (a_, b_) = (h.cos(), h.sin());
+
+    let (s_max, t_max) = to_st(find_cusp(a_, b_));
+    const S_0: f32 = 0.5;
+    let k = 1.0 - S_0 / s_max;
+
+    // lightness and chroma along the line from white to the cusp, for v = 1
+    let l_v = 1.0 - s * S_0 / (S_0 + t_max - t_max * k * s);
+    let c_v = s * t_max * S_0 / (S_0 + t_max - t_max * k * s);
+
+    let mut l = v * l_v;
+    let mut c = v * c_v;
+
+    // compensate for the toe and the curved top of the gamut
+    let l_vt = toe_inv(l_v);
+    let c_vt = c_v * l_vt / l_v;
+
+    let l_new = toe_inv(l);
+    c = c * l_new / l;
+    l = l_new;
+
+    let [r, g, b] = oklab_to_linear_srgb(l_vt, a_ * c_vt, b_ * c_vt);
+    let scale_l = (1.0 / r.max(g).max(b).max(0.0)).cbrt();
+
+    l *= scale_l;
+    c *= scale_l;
+
+    oklab_to_linear_srgb(l, c * a_, c * b_)
+}
+
+#[cfg(feature = "okhsv")]
+// Linear sRGB to Okhsv (hue in degrees, 0 for achromatic colors).
+pub(crate) fn linear_srgb_to_okhsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (l, a, b) = linear_srgb_to_oklab(r, g, b);
+    let c = (a * a + b * b).sqrt();
+
+    if l <= 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+    if c < 1e-5 {
+        return (0.0, 0.0, toe(l).clamp(0.0, 1.0));
+    }
+
+    let (a_, b_) = (a / c, b / c);
+    let h = (0.5 + 0.5 * (-b).atan2(-a) / PI) * 360.0;
+
+    let (s_max, t_max) = to_st(find_cusp(a_, b_));
+    const S_0: f32 = 0.5;
+    let k = 1.0 - S_0 / s_max;
+
+    // find the triangle the color is in, and the color on its upper edge
+    let t = t_max / (c + l * t_max);
+    let l_v = t * l;
+    let c_v = t * c;
+
+    let l_vt = toe_inv(l_v);
+    let c_vt = c_v * l_vt / l_v;
+
+    // invert the scaling of the curved top of the gamut and the toe
+    let [r, g, b] = oklab_to_linear_srgb(l_vt, a_ * c_vt, b_ * c_vt);
+    let scale_l = (1.0 / r.max(g).max(b).max(0.0)).cbrt();
+
+    let l = toe(l / scale_l);
+
+    let v = l / l_v;
+    let s = (S_0 + t_max) * c_v / ((t_max * S_0) + t_max * k * c_v);
+
+    (h % 360.0, s.clamp(0.0, 1.0), v.clamp(0.0, 1.0))
+}
@@ -0,0 +1,151 @@
+// CSS `color-mix()` function, e.g. `color-mix(in oklch longer hue, red 40%, blue)`
+// https://www.w3.org/TR/css-color-5/#color-mix
+
+use super::relative::tokenize;
+use super::{parse_with_options, CssParseOptions, ParseColorError};
+use crate::{Color, ColorSpace, HueInterpolationMode};
+
+// Parse the arguments of `color-mix()`.
+pub(crate) fn parse_color_mix(
+    args: &str,
+    opts: &CssParseOptions,
+) -> Result<Color, ParseColorError> {
+    if !opts.allow_css4 {
+        return Err(ParseColorError::InvalidColorMix);
+    }
+
+    let args = split_commas(args);
+    let (method, c1, c2) = match args[..] {
+        [method, c1, c2] => (method, c1, c2),
+        _ => return Err(ParseColorError::InvalidColorMix),
+    };
+
+    let (space, mode) =
+        parse_interpolation_method(method).ok_or(ParseColorError::InvalidColorMix)?;
+    let (c1, p1) = parse_color_percentage(c1, opts).ok_or(ParseColorError::InvalidColorMix)?;
+    let (c2, p2) = parse_color_percentage(c2, opts).ok_or(ParseColorError::InvalidColorMix)?;
+
+    // Percentage normalization
+    let (p1, p2) = match (p1, p2) {
+        (None, None) => (0.5, 0.5),
+        (Some(p1), None) => (p1, 1.0 - p1),
+        (None, Some(p2)) => (1.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+    let sum = p1 + p2;
+
+    if sum <= 0.0 {
+        return Err(ParseColorError::InvalidColorMix);
+    }
+
+    let mut c = c1.mix_in_with_mode(space, &c2, p2 / sum, mode);
+
+    // If the percentages sum to less than 100%, the result is more transparent.
+    if sum < 1.0 {
+        c.a *= sum;
+    }
+
+    Ok(c)
+}
+
+// Split at commas outside of parentheses.
+pub(super) fn split_commas(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&s[start..]);
+    parts
+}
+
+// `in <color-space> [<hue-interpolation-method> hue]?`
+fn parse_interpolation_method(s: &str) -> Option<(ColorSpace, HueInterpolationMode)> {
+    let tokens = s.split_whitespace().collect::<Vec<_>>();
+
+    let (space, mode) = match tokens[..] {
+        ["in", space] => (space, None),
+        ["in", space, mode, "hue"] => (space, Some(mode)),
+        _ => return None,
+    };
+
+    let space = match space {
+        "srgb" => ColorSpace::Srgb,
+        "srgb-linear" => ColorSpace::SrgbLinear,
+        "display-p3" => ColorSpace::DisplayP3,
+        "rec2020" => ColorSpace::Rec2020,
+        #[cfg(feature = "wide-gamut")]
+        "a98-rgb" => ColorSpace::A98Rgb,
+        "xyz" | "xyz-d65" => ColorSpace::XyzD65,
+        "xyz-d50" => ColorSpace::XyzD50,
+        "hsl" => ColorSpace::Hsl,
+        "hsv" => ColorSpace::Hsv,
+        "hwb" => ColorSpace::Hwb,
+        #[cfg(feature = "lab")]
+        "lab" => ColorSpace::Lab,
+        #[cfg(feature = "lab")]
+        "lch" => ColorSpace::Lch,
+        "oklab" => ColorSpace::OkLab,
+        "oklch" => ColorSpace::OkLch,
+        _ => return None,
+    };
+
+    let mode = match mode {
+        None => HueInterpolationMode::Shorter,
+        Some(mode) => {
+            // Only polar color spaces have a hue component.
+            match space {
+                ColorSpace::Hsl | ColorSpace::Hsv | ColorSpace::Hwb | ColorSpace::OkLch => {}
+                #[cfg(feature = "lab")]
+                ColorSpace::Lch => {}
+                _ => return None,
+            }
+            match mode {
+                "shorter" => HueInterpolationMode::Shorter,
+                "longer" => HueInterpolationMode::Longer,
+                "increasing" => HueInterpolationMode::Increasing,
+                "decreasing" => HueInterpolationMode::Decreasing,
+                _ => return None,
+            }
+        }
+    };
+
+    Some((space, mode))
+}
+
+// `<color> <percentage>?` or `<percentage> <color>`. The percentage is returned in the range [0..1].
+fn parse_color_percentage(s: &str, opts: &CssParseOptions) -> Option<(Color, Option<f32>)> {
+    let tokens = tokenize(s);
+
+    let (color, percentage) = match tokens[..] {
+        [color] => (color, None),
+        [a, b] if a.ends_with('%') => (b, Some(a)),
+        [a, b] => (a, Some(b)),
+        _ => return None,
+    };
+
+    let percentage = match percentage {
+        Some(p) => {
+            let p = p.strip_suffix('%')?.parse::<f32>().ok()?;
+            if !(0.0..=100.0).contains(&p) {
+                return None;
+            }
+            Some(p / 100.0)
+        }
+        None => None,
+    };
+
+    let color = parse_with_options(color, opts).ok()?;
+    Some((color, percentage))
+}
@@ -0,0 +1,93 @@
+// CSS gradient color stop lists, e.g. `red, 30%, blue 50% 80%, green`
+// https://www.w3.org/TR/css-images-4/#color-stop-syntax
+
+use super::color_mix::split_commas;
+use super::relative::tokenize;
+use super::{parse, ParseColorError};
+use crate::Color;
+
+const ERR: ParseColorError = ParseColorError::InvalidGradient;
+
+/// An item of a CSS gradient color stop list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradientStop {
+    /// A color with an optional position in percent.
+    Color(Color, Option<f32>),
+    /// A color hint, the position in percent of the midpoint between the neighbouring colors.
+    Hint(f32),
+}
+
+/// Parse a CSS gradient color stop list, the comma separated stops of e.g. `linear-gradient()`
+/// without the gradient line.
+///
+/// Positions are percentages. A color with two positions, e.g. `red 20% 40%`, is expanded into
+/// two stops. A color hint must be between two color stops.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use csscolorparser::{parse_gradient_stops, Color, GradientStop};
+///
+/// let stops = parse_gradient_stops("#f00, 30%, #00f 50% 80%")?;
+/// let blue = Color::from_rgb(0.0, 0.0, 1.0);
+///
+/// assert_eq!(stops.len(), 4);
+/// assert_eq!(stops[0], GradientStop::Color(Color::from_rgb(1.0, 0.0, 0.0), None));
+/// assert_eq!(stops[1], GradientStop::Hint(30.0));
+/// assert_eq!(stops[2], GradientStop::Color(blue.clone(), Some(50.0)));
+/// assert_eq!(stops[3], GradientStop::Color(blue, Some(80.0)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_gradient_stops(s: &str) -> Result<Vec<GradientStop>, ParseColorError> {
+    let items = split_commas(s);
+    let mut stops = Vec::with_capacity(items.len());
+
+    for (i, item) in items.iter().enumerate() {
+        match parse_stop(item)? {
+            (stop @ GradientStop::Hint(_), _) => {
+                let last = i + 1 == items.len();
+                if !matches!(stops.last(), Some(GradientStop::Color(..))) || last {
+                    return Err(ERR);
+                }
+                stops.push(stop);
+            }
+            (stop, second) => {
+                stops.push(stop);
+                stops.extend(second);
+            }
+        }
+    }
+
+    Ok(stops)
+}
+
+// Parse a single item of a stop list, the second stop is set for a color with two positions.
+pub(crate) fn parse_stop(s: &str) -> Result<(GradientStop, Option<GradientStop>), ParseColorError> {
+    Ok(match tokenize(s.trim())[..] {
+        [hint] if hint.ends_with('%') => (GradientStop::Hint(parse_position(hint)?), None),
+        [color] => (GradientStop::Color(parse(color)?, None), None),
+        [color, pos] => {
+            let pos = parse_position(pos)?;
+            (GradientStop::Color(parse(color)?, Some(pos)), None)
+        }
+        [color, pos1, pos2] => {
+            let (pos1, pos2) = (parse_position(pos1)?, parse_position(pos2)?);
+            let c = parse(color)?;
+            (
+                GradientStop::Color(c.clone(), Some(pos1)),
+                Some(GradientStop::Color(c, Some(pos2))),
+            )
+        }
+        _ => return Err(ERR),
+    })
+}
+
+fn parse_position(s: &str) -> Result<f32, ParseColorError> {
+    s.strip_suffix('%')
+        .and_then(|s| s.parse().ok())
+        .filter(|v: &f32| v.is_finite())
+        .ok_or(ERR)
+}
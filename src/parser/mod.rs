@@ -1,47 +1,375 @@
+use std::num::{ParseFloatError, ParseIntError};
 use std::{error, fmt};
 
-use crate::Color;
+use crate::{Color, ColorSpace};
 
+mod color_mix;
+mod gradient;
 #[cfg(feature = "named-colors")]
 mod named_colors;
+mod relative;
+#[cfg(feature = "extended-named-colors")]
+mod x11_colors;
 
+pub(crate) use gradient::parse_stop;
+pub use gradient::{parse_gradient_stops, GradientStop};
 #[cfg(feature = "named-colors")]
-use named_colors::NAMED_COLORS;
+use named_colors::CSS1_COLORS;
+#[cfg(feature = "named-colors")]
+pub(crate) use named_colors::NAMED_COLORS;
+#[cfg(feature = "css5")]
+pub(crate) use relative::parse_relative_to;
+#[cfg(feature = "extended-named-colors")]
+use x11_colors::X11_COLORS;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Errors returned when parsing a color.
+///
+/// Number parse errors keep the std error as their [`source()`](std::error::Error::source),
+/// so this type is not `Copy`, `Ord` or `Hash`. The color function variants hold the parse error
+/// of an argument that is not a number, if any.
+///
+/// New variants may be added as the parser grows, so matches outside this crate need a
+/// wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ParseColorError {
     InvalidHex,
-    InvalidRgb,
-    InvalidHsl,
-    InvalidHwb,
-    InvalidHsv,
+    /// Hex color with the wrong number of digits (CSS allows 3, 4, 6 or 8)
+    InvalidHexLength(usize),
+    InvalidRgb(Option<ParseFloatError>),
+    InvalidHsl(Option<ParseFloatError>),
+    InvalidHwb(Option<ParseFloatError>),
+    InvalidHsv(Option<ParseFloatError>),
     #[cfg(feature = "lab")]
-    InvalidLab,
+    InvalidLab(Option<ParseFloatError>),
     #[cfg(feature = "lab")]
-    InvalidLch,
+    InvalidLch(Option<ParseFloatError>),
+    InvalidOklab(Option<ParseFloatError>),
+    InvalidOklch(Option<ParseFloatError>),
+    /// Invalid GIMP palette color line or file
+    InvalidGpl,
+    /// Invalid ASE (Adobe Swatch Exchange) color entry
+    InvalidAse,
+    InvalidColor(Option<ParseFloatError>),
+    InvalidColorMix,
+    /// Invalid gradient color stop list
+    InvalidGradient,
+    /// A gradient color hint (a bare position) where a color was expected
+    UnexpectedColorHint,
     InvalidFunction,
     InvalidUnknown,
+    /// Invalid floating point number
+    InvalidFloat(ParseFloatError),
+    /// Invalid integer
+    InvalidInt(ParseIntError),
 }
 
 impl fmt::Display for ParseColorError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
+        match self {
             ParseColorError::InvalidHex => f.write_str("Invalid hex format."),
-            ParseColorError::InvalidRgb => f.write_str("Invalid rgb format."),
-            ParseColorError::InvalidHsl => f.write_str("Invalid hsl format."),
-            ParseColorError::InvalidHwb => f.write_str("Invalid hwb format."),
-            ParseColorError::InvalidHsv => f.write_str("Invalid hsv format."),
+            ParseColorError::InvalidHexLength(n) => write!(
+                f,
+                "Invalid hex length: {} digits, expected 3, 4, 6 or 8.",
+                n
+            ),
+            ParseColorError::InvalidRgb(_) => f.write_str("Invalid rgb format."),
+            ParseColorError::InvalidHsl(_) => f.write_str("Invalid hsl format."),
+            ParseColorError::InvalidHwb(_) => f.write_str("Invalid hwb format."),
+            ParseColorError::InvalidHsv(_) => f.write_str("Invalid hsv format."),
             #[cfg(feature = "lab")]
-            ParseColorError::InvalidLab => f.write_str("Invalid lab format."),
+            ParseColorError::InvalidLab(_) => f.write_str("Invalid lab format."),
             #[cfg(feature = "lab")]
-            ParseColorError::InvalidLch => f.write_str("Invalid lch format."),
+            ParseColorError::InvalidLch(_) => f.write_str("Invalid lch format."),
+            ParseColorError::InvalidOklab(_) => f.write_str("Invalid oklab format."),
+            ParseColorError::InvalidOklch(_) => f.write_str("Invalid oklch format."),
+            ParseColorError::InvalidGpl => f.write_str("Invalid GIMP palette format."),
+            ParseColorError::InvalidAse => f.write_str("Invalid ASE color entry."),
+            ParseColorError::InvalidColor(_) => f.write_str("Invalid color() format."),
+            ParseColorError::InvalidColorMix => f.write_str("Invalid color-mix() format."),
+            ParseColorError::InvalidGradient => f.write_str("Invalid gradient color stop list."),
+            ParseColorError::UnexpectedColorHint => {
+                f.write_str("Unexpected gradient color hint, expected a color.")
+            }
             ParseColorError::InvalidFunction => f.write_str("Invalid color function."),
             ParseColorError::InvalidUnknown => f.write_str("Invalid unknown format."),
+            ParseColorError::InvalidFloat(e) => write!(f, "Invalid number: {}.", e),
+            ParseColorError::InvalidInt(e) => write!(f, "Invalid integer: {}.", e),
+        }
+    }
+}
+
+impl error::Error for ParseColorError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseColorError::InvalidRgb(Some(e))
+            | ParseColorError::InvalidHsl(Some(e))
+            | ParseColorError::InvalidHwb(Some(e))
+            | ParseColorError::InvalidHsv(Some(e))
+            | ParseColorError::InvalidOklab(Some(e))
+            | ParseColorError::InvalidOklch(Some(e))
+            | ParseColorError::InvalidColor(Some(e))
+            | ParseColorError::InvalidFloat(e) => Some(e),
+            #[cfg(feature = "lab")]
+            ParseColorError::InvalidLab(Some(e)) | ParseColorError::InvalidLch(Some(e)) => Some(e),
+            ParseColorError::InvalidInt(e) => Some(e),
+            _ => None,
         }
     }
 }
 
-impl error::Error for ParseColorError {}
+impl From<ParseFloatError> for ParseColorError {
+    fn from(e: ParseFloatError) -> Self {
+        ParseColorError::InvalidFloat(e)
+    }
+}
+
+impl From<ParseIntError> for ParseColorError {
+    fn from(e: ParseIntError) -> Self {
+        ParseColorError::InvalidInt(e)
+    }
+}
+
+/// Parse error with the failed string and a user-provided context label, returned by
+/// [`Color::from_html_with_error_context()`](struct.Color.html#method.from_html_with_error_context).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorContextError {
+    /// The string that failed to parse.
+    pub value: String,
+    /// Where the string came from, e.g. `background-color property`.
+    pub context: String,
+    /// The underlying error.
+    pub kind: ParseColorError,
+}
+
+impl fmt::Display for ParseColorContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Failed to parse color '{}' in {}",
+            self.value, self.context
+        )
+    }
+}
+
+impl error::Error for ParseColorContextError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+/// Options for [`Color::from_css_string_with_options()`](struct.Color.html#method.from_css_string_with_options).
+///
+/// The default value accepts everything accepted by [`parse()`](fn.parse.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CssParseOptions {
+    /// Accept CSS Level 4 syntax: space separated arguments, `/` before alpha,
+    /// `#rgba` and `#rrggbbaa` hex, `hwb()`, `lab()` and `lch()`.
+    pub allow_css4: bool,
+    /// Accept named colors (including `transparent`).
+    pub allow_named: bool,
+    /// Accept hexadecimal colors.
+    pub allow_hex: bool,
+    /// Require the red, green and blue arguments to be either all numbers or all percentages,
+    /// and saturation, lightness, whiteness, blackness and value to be percentages.
+    pub strict_percentages: bool,
+    /// Accept hexadecimal colors without the `#` prefix.
+    pub allow_bare_hex: bool,
+    /// Accept a percentage alpha in the comma separated `rgb()`, `rgba()`, `hsl()` and `hsla()`
+    /// syntax.
+    pub allow_legacy_alpha_percentages: bool,
+    /// Accept the non-standard `hwba()`, `hsv()` and `hsva()` functions.
+    pub allow_nonstandard: bool,
+    /// Accept bare `rgb()` arguments without the function name, e.g. `255 0 0` or `255,0,0`.
+    pub allow_bare_numbers: bool,
+}
+
+impl Default for CssParseOptions {
+    fn default() -> Self {
+        CssParseOptions {
+            allow_css4: true,
+            allow_named: true,
+            allow_hex: true,
+            strict_percentages: false,
+            allow_bare_hex: true,
+            allow_legacy_alpha_percentages: true,
+            allow_nonstandard: true,
+            allow_bare_numbers: false,
+        }
+    }
+}
+
+/// Configurable CSS color parser.
+///
+/// A builder over [`CssParseOptions`]: [`ColorParser::default()`] accepts everything accepted
+/// by [`parse()`](fn.parse.html), [`ColorParser::strict()`] only accepts CSS syntax, and
+/// [`ColorParser::lenient()`] also accepts common deviations.
+///
+/// # Examples
+///
+/// ```
+/// use csscolorparser::ColorParser;
+///
+/// let parser = ColorParser::strict();
+/// assert!(parser.parse("#ff0000").is_ok());
+/// assert!(parser.parse("ff0000").is_err());
+///
+/// let parser = ColorParser::lenient();
+/// assert_eq!(parser.parse("255 0 0").unwrap().rgba_u8(), (255, 0, 0, 255));
+///
+/// let parser = ColorParser::new().allow_bare_hex_without_hash(false);
+/// assert!(parser.parse("ff0000").is_err());
+/// assert!(!parser.options().allow_bare_hex);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorParser {
+    opts: CssParseOptions,
+}
+
+impl From<CssParseOptions> for ColorParser {
+    fn from(opts: CssParseOptions) -> Self {
+        ColorParser { opts }
+    }
+}
+
+impl ColorParser {
+    /// Create parser with the same behavior as [`parse()`](fn.parse.html).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create parser that rejects anything outside of the CSS syntax.
+    pub fn strict() -> Self {
+        Self::new()
+            .allow_bare_hex_without_hash(false)
+            .allow_mixed_percentages(false)
+            .allow_legacy_rgba_percentages(false)
+            .strict_level_4_mode(true)
+    }
+
+    /// Create parser that accepts common deviations from the CSS syntax.
+    pub fn lenient() -> Self {
+        Self::new().allow_bare_numbers(true)
+    }
+
+    /// Accept hexadecimal colors without the `#` prefix, e.g. `ff0000`.
+    ///
+    /// Sets [`CssParseOptions::allow_bare_hex`].
+    pub fn allow_bare_hex_without_hash(mut self, allow: bool) -> Self {
+        self.opts.allow_bare_hex = allow;
+        self
+    }
+
+    /// Accept red, green and blue arguments mixing numbers and percentages, and numbers for
+    /// saturation, lightness, whiteness, blackness and value.
+    ///
+    /// Sets [`CssParseOptions::strict_percentages`] to the opposite of `allow`.
+    pub fn allow_mixed_percentages(mut self, allow: bool) -> Self {
+        self.opts.strict_percentages = !allow;
+        self
+    }
+
+    /// Accept a percentage alpha in the comma separated syntax, e.g. `rgba(255,0,0,50%)`.
+    ///
+    /// Sets [`CssParseOptions::allow_legacy_alpha_percentages`].
+    pub fn allow_legacy_rgba_percentages(mut self, allow: bool) -> Self {
+        self.opts.allow_legacy_alpha_percentages = allow;
+        self
+    }
+
+    /// Only accept the color functions defined by CSS, rejecting the non-standard `hwba()`,
+    /// `hsv()` and `hsva()`. It does not change which CSS Level 4 syntax is accepted, see
+    /// [`CssParseOptions::allow_css4`] for that.
+    ///
+    /// Sets [`CssParseOptions::allow_nonstandard`] to the opposite of `strict`.
+    pub fn strict_level_4_mode(mut self, strict: bool) -> Self {
+        self.opts.allow_nonstandard = !strict;
+        self
+    }
+
+    /// Accept bare `rgb()` arguments without the function name, e.g. `255 0 0`.
+    ///
+    /// Sets [`CssParseOptions::allow_bare_numbers`].
+    pub fn allow_bare_numbers(mut self, allow: bool) -> Self {
+        self.opts.allow_bare_numbers = allow;
+        self
+    }
+
+    /// The [`CssParseOptions`] used by this parser.
+    pub fn options(&self) -> CssParseOptions {
+        self.opts
+    }
+
+    /// Parse CSS color string
+    pub fn parse(&self, s: &str) -> Result<Color, ParseColorError> {
+        parse_with_options(s, &self.opts)
+    }
+}
+
+/// CSS specification level, for [`parse_named()`](fn.parse_named.html).
+#[cfg(feature = "named-colors")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CssVersion {
+    /// CSS Level 1 (and HTML 4.01): the 16 basic colors
+    Css1,
+    /// CSS Level 2.1: adds `orange`
+    Css2,
+    /// CSS Color Module Level 3: the extended color keywords
+    Css3,
+    /// CSS Color Module Level 4: adds `rebeccapurple`
+    Css4,
+}
+
+/// Parse named color valid in the given CSS specification level (case-insensitive).
+///
+/// # Examples
+///
+/// ```
+/// use csscolorparser::{parse_named, CssVersion};
+///
+/// assert!(parse_named("navy", CssVersion::Css1).is_some());
+/// assert!(parse_named("orange", CssVersion::Css1).is_none());
+/// assert!(parse_named("orange", CssVersion::Css2).is_some());
+/// assert!(parse_named("rebeccapurple", CssVersion::Css3).is_none());
+/// assert!(parse_named("rebeccapurple", CssVersion::Css4).is_some());
+/// ```
+#[cfg(feature = "named-colors")]
+pub fn parse_named(name: &str, version: CssVersion) -> Option<Color> {
+    let name = name.trim().to_lowercase();
+
+    let valid = match version {
+        CssVersion::Css1 => CSS1_COLORS.contains(&&*name),
+        CssVersion::Css2 => CSS1_COLORS.contains(&&*name) || name == "orange",
+        CssVersion::Css3 => name != "rebeccapurple",
+        CssVersion::Css4 => true,
+    };
+
+    if !valid {
+        return None;
+    }
+
+    NAMED_COLORS
+        .get(&*name)
+        .map(|&[r, g, b]| Color::from_rgb_u8(r, g, b))
+}
+
+/// Parse named color from CSS Level 1 (the 16 basic colors).
+#[cfg(feature = "named-colors")]
+pub fn parse_named_css1(name: &str) -> Option<Color> {
+    parse_named(name, CssVersion::Css1)
+}
+
+/// Parse named color from CSS Color Module Level 3.
+#[cfg(feature = "named-colors")]
+pub fn parse_named_css3(name: &str) -> Option<Color> {
+    parse_named(name, CssVersion::Css3)
+}
+
+/// Parse named color from CSS Color Module Level 4.
+#[cfg(feature = "named-colors")]
+pub fn parse_named_css4(name: &str) -> Option<Color> {
+    parse_named(name, CssVersion::Css4)
+}
 
 /// Parse CSS color string
 ///
@@ -73,20 +401,138 @@ impl error::Error for ParseColorError {}
 /// # }
 /// ```
 pub fn parse(s: &str) -> Result<Color, ParseColorError> {
+    parse_with_options(s, &CssParseOptions::default())
+}
+
+/// Parse a comma-separated list of CSS color strings.
+///
+/// Commas inside color functions do not split the list. Each item is parsed separately, so
+/// the result has one entry per item; an empty string returns an empty list.
+///
+/// # Examples
+///
+/// ```
+/// let colors = csscolorparser::parse_all("#f00, rgb(0,255,0), foo");
+///
+/// assert_eq!(colors.len(), 3);
+/// assert_eq!(colors[0].as_ref().unwrap().rgba_u8(), (255, 0, 0, 255));
+/// assert_eq!(colors[1].as_ref().unwrap().rgba_u8(), (0, 255, 0, 255));
+/// assert!(colors[2].is_err());
+/// ```
+pub fn parse_all(s: &str) -> Vec<Result<Color, ParseColorError>> {
+    if s.trim().is_empty() {
+        return Vec::new();
+    }
+    color_mix::split_commas(s)
+        .into_iter()
+        .map(|s| parse(s.trim()))
+        .collect()
+}
+
+/// Parse a CSS color function from its already tokenized name and arguments.
+///
+/// `name` is the function name without the parentheses, `args` is everything between them.
+/// Both are case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let c = csscolorparser::parse_css_function("oklch", "0.7 0.15 142")?;
+/// assert_eq!(c, csscolorparser::parse("oklch(0.7 0.15 142)")?);
+///
+/// let c = csscolorparser::parse_css_function("rgb", "255, 0, 0")?;
+/// assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_css_function(name: &str, args: &str) -> Result<Color, ParseColorError> {
+    let name = name.trim().to_lowercase();
+    let args = args.to_lowercase();
+    parse_function(&name, &args, &CssParseOptions::default())
+}
+
+/// The value of an SVG color attribute such as `fill` or `stroke`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgColor {
+    /// A CSS color value.
+    Color(Color),
+    /// The `none` keyword, nothing is painted.
+    None,
+    /// The `inherit` keyword, the value is taken from the parent element.
+    Inherit,
+}
+
+/// Parse an SVG color attribute value: a CSS color or the `none` or `inherit` keyword
+/// (case-insensitive).
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use csscolorparser::{parse_svg_color_attribute, Color, SvgColor};
+///
+/// assert_eq!(
+///     parse_svg_color_attribute("#f00")?,
+///     SvgColor::Color(Color::from_rgb(1.0, 0.0, 0.0))
+/// );
+/// assert_eq!(parse_svg_color_attribute("none")?, SvgColor::None);
+/// assert_eq!(parse_svg_color_attribute("inherit")?, SvgColor::Inherit);
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_svg_color_attribute(s: &str) -> Result<SvgColor, ParseColorError> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("none") {
+        return Ok(SvgColor::None);
+    }
+    if s.eq_ignore_ascii_case("inherit") {
+        return Ok(SvgColor::Inherit);
+    }
+    parse(s).map(SvgColor::Color)
+}
+
+pub(crate) fn parse_with_options(
+    s: &str,
+    opts: &CssParseOptions,
+) -> Result<Color, ParseColorError> {
     let s = s.trim().to_lowercase();
 
-    if s == "transparent" {
+    if opts.allow_named && s == "transparent" {
         return Ok(Color::from_rgba(0.0, 0.0, 0.0, 0.0));
     }
 
     // Named colors
     #[cfg(feature = "named-colors")]
-    if let Some([r, g, b]) = NAMED_COLORS.get(&*s) {
-        return Ok(Color::from_rgb_u8(*r, *g, *b));
+    if opts.allow_named {
+        if let Some([r, g, b]) = NAMED_COLORS.get(&*s) {
+            return Ok(Color::from_rgb_u8(*r, *g, *b));
+        }
+    }
+
+    // X11 color names
+    #[cfg(feature = "extended-named-colors")]
+    if opts.allow_named {
+        if let Some([r, g, b]) = X11_COLORS.get(&*s) {
+            return Ok(Color::from_rgb_u8(*r, *g, *b));
+        }
+    }
+
+    // System colors
+    #[cfg(feature = "system-colors")]
+    if opts.allow_named {
+        if let Some(c) = crate::system_colors::default_system_color(&s) {
+            return Ok(c);
+        }
     }
 
     // Hex format
     if let Some(s) = s.strip_prefix('#') {
+        if !opts.allow_hex || (!opts.allow_css4 && s.len() != 3 && s.len() != 6) {
+            return Err(ParseColorError::InvalidHex);
+        }
         if let Ok(c) = parse_hex(s) {
             return Ok(c);
         }
@@ -94,167 +540,256 @@ pub fn parse(s: &str) -> Result<Color, ParseColorError> {
     }
 
     if let (Some(i), Some(s)) = (s.find('('), s.strip_suffix(')')) {
-        let fname = &s[..i].trim_end();
-        let s = &s[i + 1..].replace(',', " ").replace('/', " ");
-        let params = s.split_whitespace().collect::<Vec<&str>>();
-        let p_len = params.len();
-
-        match *fname {
-            "rgb" | "rgba" => {
-                if p_len != 3 && p_len != 4 {
-                    return Err(ParseColorError::InvalidRgb);
-                }
-
-                let r = parse_percent_or_255(params[0]);
-                let g = parse_percent_or_255(params[1]);
-                let b = parse_percent_or_255(params[2]);
-
-                let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
-                } else {
-                    Some(1.0)
-                };
-
-                if let (Some(r), Some(g), Some(b), Some(a)) = (r, g, b, a) {
-                    return Ok(Color {
-                        r: r.clamp(0.0, 1.0),
-                        g: g.clamp(0.0, 1.0),
-                        b: b.clamp(0.0, 1.0),
-                        a: a.clamp(0.0, 1.0),
-                    });
-                }
-
-                return Err(ParseColorError::InvalidRgb);
-            }
-            "hsl" | "hsla" => {
-                if p_len != 3 && p_len != 4 {
-                    return Err(ParseColorError::InvalidHsl);
-                }
-
-                let h = parse_angle(params[0]);
-                let s = parse_percent_or_float(params[1]);
-                let l = parse_percent_or_float(params[2]);
-
-                let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
-                } else {
-                    Some(1.0)
-                };
-
-                if let (Some(h), Some(s), Some(l), Some(a)) = (h, s, l, a) {
-                    return Ok(Color::from_hsla(h, s, l, a));
-                }
-
-                return Err(ParseColorError::InvalidHsl);
-            }
-            "hwb" | "hwba" => {
-                if p_len != 3 && p_len != 4 {
-                    return Err(ParseColorError::InvalidHwb);
-                }
-
-                let h = parse_angle(params[0]);
-                let w = parse_percent_or_float(params[1]);
-                let b = parse_percent_or_float(params[2]);
-
-                let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
-                } else {
-                    Some(1.0)
-                };
-
-                if let (Some(h), Some(w), Some(b), Some(a)) = (h, w, b, a) {
-                    return Ok(Color::from_hwba(h, w, b, a));
-                }
-
-                return Err(ParseColorError::InvalidHwb);
+        return parse_function(s[..i].trim_end(), &s[i + 1..], opts);
+    }
+
+    // Hex format without prefix '#'
+    if opts.allow_hex && opts.allow_bare_hex && (opts.allow_css4 || s.len() == 3 || s.len() == 6) {
+        if let Ok(c) = parse_hex(&s) {
+            return Ok(c);
+        }
+    }
+
+    // rgb() arguments without the function name
+    if opts.allow_bare_numbers && s.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        if let Ok(c) = parse_function("rgb", &s, opts) {
+            return Ok(c);
+        }
+    }
+
+    Err(ParseColorError::InvalidUnknown)
+}
+
+// Parse the color function `fname` with the (lowercase) arguments string `args`.
+fn parse_function(
+    fname: &str,
+    args: &str,
+    opts: &CssParseOptions,
+) -> Result<Color, ParseColorError> {
+    if fname == "color-mix" {
+        return color_mix::parse_color_mix(args, opts);
+    }
+
+    // Relative color syntax
+    if let Some(args) = args.trim_start().strip_prefix("from") {
+        if args.starts_with(char::is_whitespace) {
+            return relative::parse_relative(fname, args, opts);
+        }
+    }
+
+    // CSS Level 3 only allows comma separated arguments, and no `none` keyword.
+    let valid_syntax = opts.allow_css4
+        || (!args.contains('/')
+            && args
+                .split(',')
+                .all(|p| p.split_whitespace().count() == 1 && p.trim() != "none"));
+    let s = &args.replace([',', '/'], " ");
+    let params = s.split_whitespace().collect::<Vec<&str>>();
+    let p_len = params.len();
+    let invalid_alpha = !opts.allow_legacy_alpha_percentages
+        && p_len == 4
+        && args.contains(',')
+        && is_percent(params[3]);
+
+    if !opts.allow_nonstandard && matches!(fname, "hwba" | "hsv" | "hsva") {
+        return Err(ParseColorError::InvalidFunction);
+    }
+
+    if fname == "color" {
+        return parse_color_function(&params, opts);
+    }
+
+    let f = ColorFunction::from_name(fname).ok_or(ParseColorError::InvalidFunction)?;
+
+    // hwb(), lab(), lch(), oklab() and oklch() are CSS Level 4, and only rgb() and hsl() have a
+    // legacy syntax with comma separated alpha.
+    let invalid = match f {
+        ColorFunction::Rgb | ColorFunction::Hsl => invalid_alpha,
+        ColorFunction::Hsv => false,
+        _ => !opts.allow_css4,
+    };
+    if (p_len != 3 && p_len != 4) || !valid_syntax || invalid {
+        return Err(f.error());
+    }
+
+    if opts.strict_percentages {
+        let valid = match f {
+            ColorFunction::Rgb => {
+                let n = params[..3].iter().filter(|p| is_percent(p)).count();
+                n == 0 || n == 3
             }
-            "hsv" | "hsva" => {
-                if p_len != 3 && p_len != 4 {
-                    return Err(ParseColorError::InvalidHsv);
-                }
-
-                let h = parse_angle(params[0]);
-                let s = parse_percent_or_float(params[1]);
-                let v = parse_percent_or_float(params[2]);
-
-                let a = if p_len == 4 {
-                    parse_percent_or_float(params[3])
-                } else {
-                    Some(1.0)
-                };
-
-                if let (Some(h), Some(s), Some(v), Some(a)) = (h, s, v, a) {
-                    return Ok(Color::from_hsva(h, s, v, a));
-                }
-
-                return Err(ParseColorError::InvalidHsv);
+            ColorFunction::Hsl | ColorFunction::Hwb | ColorFunction::Hsv => {
+                is_percent(params[1]) && is_percent(params[2])
             }
+            _ => true,
+        };
+        if !valid {
+            return Err(f.error());
+        }
+    }
+
+    let values = parse_channels(f, &params).map_err(|e| f.error_from(e))?;
+    let [x, y, z, alpha] = resolve_missing(values);
+
+    Ok(f.to_color(x, y, z, alpha))
+}
+
+// Parse the three channels and the optional alpha of the color function `f`.
+fn parse_channels(f: ColorFunction, params: &[&str]) -> Result<[f32; 4], Option<ParseFloatError>> {
+    let [x, y, z] = match f {
+        ColorFunction::Rgb => [
+            parse_percent_or_255(params[0])?,
+            parse_percent_or_255(params[1])?,
+            parse_percent_or_255(params[2])?,
+        ],
+        ColorFunction::Hsl | ColorFunction::Hwb | ColorFunction::Hsv => [
+            parse_angle(params[0])?,
+            parse_percent_or_float(params[1])?,
+            parse_percent_or_float(params[2])?,
+        ],
+        #[cfg(feature = "lab")]
+        ColorFunction::Lab => [
+            parse_percent_or_float(params[0])? * 100.0,
+            parse_percent_or_float(params[1])?,
+            parse_percent_or_float(params[2])?,
+        ],
+        #[cfg(feature = "lab")]
+        ColorFunction::Lch => [
+            parse_percent_or_float(params[0])? * 100.0,
+            parse_percent_or_float(params[1])?,
+            parse_angle(params[2])?,
+        ],
+        ColorFunction::OkLab => [
+            parse_percent_or_float(params[0])?,
+            scale_percent(params[1], parse_percent_or_float(params[1])?, 0.4),
+            scale_percent(params[2], parse_percent_or_float(params[2])?, 0.4),
+        ],
+        ColorFunction::OkLch => [
+            parse_percent_or_float(params[0])?,
+            scale_percent(params[1], parse_percent_or_float(params[1])?, 0.4),
+            parse_angle(params[2])?,
+        ],
+    };
+
+    let alpha = match params.get(3) {
+        Some(s) => parse_percent_or_float(s)?,
+        None => 1.0,
+    };
+
+    Ok([x, y, z, alpha])
+}
+
+// Parse the parameters of `color(<colorspace> x y z / alpha)`.
+fn parse_color_function(params: &[&str], opts: &CssParseOptions) -> Result<Color, ParseColorError> {
+    let p_len = params.len();
+    if (p_len != 4 && p_len != 5) || !opts.allow_css4 {
+        return Err(ParseColorError::InvalidColor(None));
+    }
+
+    let space = match params[0] {
+        "srgb" => ColorSpace::Srgb,
+        "srgb-linear" => ColorSpace::SrgbLinear,
+        "display-p3" => ColorSpace::DisplayP3,
+        "rec2020" => ColorSpace::Rec2020,
+        #[cfg(feature = "wide-gamut")]
+        "a98-rgb" => ColorSpace::A98Rgb,
+        "xyz" | "xyz-d65" => ColorSpace::XyzD65,
+        "xyz-d50" => ColorSpace::XyzD50,
+        _ => return Err(ParseColorError::InvalidColor(None)),
+    };
+
+    let mut values = [0.0, 0.0, 0.0, 1.0];
+    for (v, s) in values.iter_mut().zip(&params[1..]) {
+        *v = parse_percent_or_float(s).map_err(ParseColorError::InvalidColor)?;
+    }
+    let [x, y, z, alpha] = resolve_missing(values);
+
+    Ok(Color::convert_from(space, x, y, z, alpha.clamp(0.0, 1.0)))
+}
+
+// The color functions taking three channels and an optional alpha, shared by the parser, the
+// relative color syntax and `Color::from_css_function_name()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorFunction {
+    Rgb,
+    Hsl,
+    Hwb,
+    Hsv,
+    #[cfg(feature = "lab")]
+    Lab,
+    #[cfg(feature = "lab")]
+    Lch,
+    OkLab,
+    OkLch,
+}
+
+impl ColorFunction {
+    // `name` must be lowercase.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "rgb" | "rgba" => ColorFunction::Rgb,
+            "hsl" | "hsla" => ColorFunction::Hsl,
+            "hwb" | "hwba" => ColorFunction::Hwb,
+            "hsv" | "hsva" => ColorFunction::Hsv,
             #[cfg(feature = "lab")]
-            "lab" => {
-                if p_len != 3 && p_len != 4 {
-                    return Err(ParseColorError::InvalidLab);
-                }
-
-                let l = parse_percent_or_float(params[0]);
-                let a = parse_percent_or_float(params[1]);
-                let b = parse_percent_or_float(params[2]);
-
-                let alpha = if p_len == 4 {
-                    parse_percent_or_float(params[3])
-                } else {
-                    Some(1.0)
-                };
-
-                if let (Some(l), Some(a), Some(b), Some(alpha)) = (l, a, b, alpha) {
-                    return Ok(Color::from_lab(l.max(0.0) * 100.0, a, b, alpha));
-                }
-
-                return Err(ParseColorError::InvalidLab);
-            }
+            "lab" => ColorFunction::Lab,
             #[cfg(feature = "lab")]
-            "lch" => {
-                if p_len != 3 && p_len != 4 {
-                    return Err(ParseColorError::InvalidLch);
-                }
-
-                let l = parse_percent_or_float(params[0]);
-                let c = parse_percent_or_float(params[1]);
-                let h = parse_angle(params[2]);
-
-                let alpha = if p_len == 4 {
-                    parse_percent_or_float(params[3])
-                } else {
-                    Some(1.0)
-                };
-
-                if let (Some(l), Some(c), Some(h), Some(alpha)) = (l, c, h, alpha) {
-                    return Ok(Color::from_lch(
-                        l.max(0.0) * 100.0,
-                        c.max(0.0),
-                        h.to_radians(),
-                        alpha,
-                    ));
-                }
-
-                return Err(ParseColorError::InvalidLch);
-            }
-            _ => {
-                return Err(ParseColorError::InvalidFunction);
-            }
-        }
+            "lch" => ColorFunction::Lch,
+            "oklab" => ColorFunction::OkLab,
+            "oklch" => ColorFunction::OkLch,
+            _ => return None,
+        })
     }
 
-    // Hex format without prefix '#'
-    if let Ok(c) = parse_hex(&s) {
-        return Ok(c);
+    pub(crate) fn error(self) -> ParseColorError {
+        self.error_from(None)
     }
 
-    Err(ParseColorError::InvalidUnknown)
+    // The format error of this function, with the parse error of an invalid argument.
+    fn error_from(self, source: Option<ParseFloatError>) -> ParseColorError {
+        match self {
+            ColorFunction::Rgb => ParseColorError::InvalidRgb(source),
+            ColorFunction::Hsl => ParseColorError::InvalidHsl(source),
+            ColorFunction::Hwb => ParseColorError::InvalidHwb(source),
+            ColorFunction::Hsv => ParseColorError::InvalidHsv(source),
+            #[cfg(feature = "lab")]
+            ColorFunction::Lab => ParseColorError::InvalidLab(source),
+            #[cfg(feature = "lab")]
+            ColorFunction::Lch => ParseColorError::InvalidLch(source),
+            ColorFunction::OkLab => ParseColorError::InvalidOklab(source),
+            ColorFunction::OkLch => ParseColorError::InvalidOklch(source),
+        }
+    }
+
+    // Create the color from its channels: rgb, saturation, lightness, whiteness, blackness and
+    // value in the range [0..1], lab and lch lightness in the range [0..100], hue in degrees.
+    pub(crate) fn to_color(self, x: f32, y: f32, z: f32, alpha: f32) -> Color {
+        let alpha = alpha.clamp(0.0, 1.0);
+        match self {
+            ColorFunction::Rgb => Color::from_rgba(
+                x.clamp(0.0, 1.0),
+                y.clamp(0.0, 1.0),
+                z.clamp(0.0, 1.0),
+                alpha,
+            ),
+            ColorFunction::Hsl => Color::from_hsla(x, y, z, alpha),
+            ColorFunction::Hwb => Color::from_hwba(x, y, z, alpha),
+            ColorFunction::Hsv => Color::from_hsva(x, y, z, alpha),
+            #[cfg(feature = "lab")]
+            ColorFunction::Lab => Color::from_lab(x.max(0.0), y, z, alpha),
+            #[cfg(feature = "lab")]
+            ColorFunction::Lch => Color::from_lch(x.max(0.0), y.max(0.0), z.to_radians(), alpha),
+            ColorFunction::OkLab => Color::from_oklaba(x.max(0.0), y, z, alpha),
+            ColorFunction::OkLch => {
+                Color::convert_from(ColorSpace::OkLch, x.max(0.0), y.max(0.0), z, alpha)
+            }
+        }
+    }
 }
 
-fn parse_hex(s: &str) -> Result<Color, Box<dyn error::Error>> {
+pub(crate) fn parse_hex(s: &str) -> Result<Color, ParseColorError> {
     if !s.is_ascii() {
-        return Err(Box::new(ParseColorError::InvalidHex));
+        return Err(ParseColorError::InvalidHex);
     }
 
     let n = s.len();
@@ -284,76 +819,86 @@ fn parse_hex(s: &str) -> Result<Color, Box<dyn error::Error>> {
 
         (r, g, b, a)
     } else {
-        return Err(Box::new(ParseColorError::InvalidHex));
+        return Err(ParseColorError::InvalidHex);
     };
 
     Ok(Color::from_rgba_u8(r, g, b, a))
 }
 
-fn parse_percent_or_float(s: &str) -> Option<f32> {
-    if let Some(s) = s.strip_suffix('%') {
-        if let Ok(t) = s.parse::<f32>() {
-            return Some(t / 100.0);
-        }
-        return None;
+fn is_percent(s: &str) -> bool {
+    s.ends_with('%')
+}
+
+// Percentages are relative to `reference`, e.g. 100% of oklab() `a` is 0.4
+fn scale_percent(s: &str, t: f32, reference: f32) -> f32 {
+    if is_percent(s) {
+        return t * reference;
     }
+    t
+}
 
-    if let Ok(t) = s.parse::<f32>() {
-        return Some(t);
+// Parse a finite number. `f32::from_str` also accepts `nan`, `inf` and `infinity`, which are not
+// CSS numbers (and NaN marks missing components), they are rejected without a parse error.
+fn parse_number(s: &str) -> Result<f32, Option<ParseFloatError>> {
+    match s.parse::<f32>() {
+        Ok(t) if t.is_finite() => Ok(t),
+        Ok(_) => Err(None),
+        Err(e) => Err(Some(e)),
     }
+}
 
-    None
+// Missing components (the `none` keyword) are parsed as NaN and resolved to 0 once all the
+// components of a color function are known.
+fn resolve_missing(values: [f32; 4]) -> [f32; 4] {
+    values.map(|t| if t.is_nan() { 0.0 } else { t })
 }
 
-fn parse_percent_or_255(s: &str) -> Option<f32> {
+fn parse_percent_or_float(s: &str) -> Result<f32, Option<ParseFloatError>> {
+    if s == "none" {
+        return Ok(f32::NAN);
+    }
+
     if let Some(s) = s.strip_suffix('%') {
-        if let Ok(t) = s.parse::<f32>() {
-            return Some(t / 100.0);
-        }
-        return None;
+        return Ok(parse_number(s)? / 100.0);
+    }
+
+    parse_number(s)
+}
+
+fn parse_percent_or_255(s: &str) -> Result<f32, Option<ParseFloatError>> {
+    if s == "none" {
+        return Ok(f32::NAN);
     }
 
-    if let Ok(t) = s.parse::<f32>() {
-        return Some(t / 255.0);
+    if let Some(s) = s.strip_suffix('%') {
+        return Ok(parse_number(s)? / 100.0);
     }
 
-    None
+    Ok(parse_number(s)? / 255.0)
 }
 
-fn parse_angle(s: &str) -> Option<f32> {
+fn parse_angle(s: &str) -> Result<f32, Option<ParseFloatError>> {
+    if s == "none" {
+        return Ok(f32::NAN);
+    }
+
     if let Some(s) = s.strip_suffix("deg") {
-        if let Ok(t) = s.parse::<f32>() {
-            return Some(t);
-        }
-        return None;
+        return parse_number(s);
     }
 
     if let Some(s) = s.strip_suffix("grad") {
-        if let Ok(t) = s.parse::<f32>() {
-            return Some(t * 360.0 / 400.0);
-        }
-        return None;
+        return Ok(parse_number(s)? * 360.0 / 400.0);
     }
 
     if let Some(s) = s.strip_suffix("rad") {
-        if let Ok(t) = s.parse::<f32>() {
-            return Some(t.to_degrees());
-        }
-        return None;
+        return Ok(parse_number(s)?.to_degrees());
     }
 
     if let Some(s) = s.strip_suffix("turn") {
-        if let Ok(t) = s.parse::<f32>() {
-            return Some(t * 360.0);
-        }
-        return None;
-    }
-
-    if let Ok(t) = s.parse::<f32>() {
-        return Some(t);
+        return Ok(parse_number(s)? * 360.0);
     }
 
-    None
+    parse_number(s)
 }
 
 #[cfg(test)]
@@ -375,7 +920,7 @@ mod tests {
         ];
         for (s, expected) in data {
             let c = parse_angle(s);
-            assert_eq!(Some(expected), c);
+            assert_eq!(Ok(expected), c);
         }
     }
 }
@@ -150,3 +150,9 @@ pub(crate) static NAMED_COLORS: phf::Map<&'static str, [u8; 3]> = phf::phf_map!
     "yellow" => [255, 255, 0],
     "yellowgreen" => [154, 205, 50],
 };
+
+// https://www.w3.org/TR/CSS1/#color-units
+pub(crate) static CSS1_COLORS: [&str; 16] = [
+    "aqua", "black", "blue", "fuchsia", "gray", "green", "lime", "maroon", "navy", "olive",
+    "purple", "red", "silver", "teal", "white", "yellow",
+];
@@ -0,0 +1,309 @@
+// CSS relative color syntax, e.g. `rgb(from red r calc(g + 20) b / 50%)`
+// https://www.w3.org/TR/css-color-5/#relative-colors
+
+use super::{
+    parse_angle, parse_number, parse_with_options, resolve_missing, ColorFunction, CssParseOptions,
+    ParseColorError,
+};
+use crate::{Color, ColorSpace};
+
+// Parse the arguments following `from` of the color function `fname`.
+pub(crate) fn parse_relative(
+    fname: &str,
+    args: &str,
+    opts: &CssParseOptions,
+) -> Result<Color, ParseColorError> {
+    if !opts.allow_nonstandard && matches!(fname, "hwba" | "hsv" | "hsva") {
+        return Err(ParseColorError::InvalidFunction);
+    }
+
+    let f = ColorFunction::from_name(fname).ok_or(ParseColorError::InvalidFunction)?;
+    let err = f.error();
+
+    if !opts.allow_css4 {
+        return Err(err);
+    }
+
+    let tokens = tokenize(args);
+    let (origin, channels) = match tokens.split_first() {
+        Some(v) => v,
+        None => return Err(err),
+    };
+
+    let origin = parse_with_options(origin, opts).map_err(|_| err)?;
+    relative_color(f, &origin, channels)
+}
+
+// Parse `fname(channels)` relative to `origin`, i.e. relative color syntax without `from <color>`.
+#[cfg(feature = "css5")]
+pub(crate) fn parse_relative_to(origin: &Color, s: &str) -> Result<Color, ParseColorError> {
+    let s = s.trim().to_lowercase();
+    let (fname, args) = match (s.find('('), s.strip_suffix(')')) {
+        (Some(i), Some(s)) => (s[..i].trim_end(), &s[i + 1..]),
+        _ => return Err(ParseColorError::InvalidUnknown),
+    };
+    let f = ColorFunction::from_name(fname).ok_or(ParseColorError::InvalidFunction)?;
+    relative_color(f, origin, &tokenize(args))
+}
+
+// Evaluate the `channels` of a relative color of the color function `f`.
+fn relative_color(
+    f: ColorFunction,
+    origin: &Color,
+    channels: &[&str],
+) -> Result<Color, ParseColorError> {
+    let err = f.error();
+    let channels = match channels {
+        [c1, c2, c3] => [*c1, *c2, *c3, "alpha"],
+        [c1, c2, c3, "/", alpha] => [*c1, *c2, *c3, *alpha],
+        _ => return Err(err),
+    };
+
+    // Channel keywords, their values taken from the origin color, and the value of 100% for
+    // each channel (`None` for hue channels).
+    let (names, (x, y, z, alpha), refs): ([&str; 3], _, [Option<f32>; 3]) = match f {
+        ColorFunction::Rgb => {
+            let (r, g, b, a) = origin.rgba();
+            let refs = [Some(255.0); 3];
+            (["r", "g", "b"], (r * 255.0, g * 255.0, b * 255.0, a), refs)
+        }
+        ColorFunction::Hsl => {
+            let (h, s, l, a) = origin.to_hsla();
+            let refs = [None, Some(100.0), Some(100.0)];
+            (["h", "s", "l"], (h, s * 100.0, l * 100.0, a), refs)
+        }
+        ColorFunction::Hwb => {
+            let (h, w, b, a) = origin.to_hwba();
+            let refs = [None, Some(100.0), Some(100.0)];
+            (["h", "w", "b"], (h, w * 100.0, b * 100.0, a), refs)
+        }
+        ColorFunction::Hsv => {
+            let (h, s, v, a) = origin.to_hsva();
+            let refs = [None, Some(100.0), Some(100.0)];
+            (["h", "s", "v"], (h, s * 100.0, v * 100.0, a), refs)
+        }
+        #[cfg(feature = "lab")]
+        ColorFunction::Lab => {
+            let refs = [Some(100.0), Some(125.0), Some(125.0)];
+            (["l", "a", "b"], origin.to_lab(), refs)
+        }
+        #[cfg(feature = "lab")]
+        ColorFunction::Lch => {
+            let (l, c, h, a) = origin.to_lch();
+            let h = h.to_degrees().rem_euclid(360.0);
+            let refs = [Some(100.0), Some(150.0), None];
+            (["l", "c", "h"], (l, c, h, a), refs)
+        }
+        ColorFunction::OkLab => {
+            let refs = [Some(1.0), Some(0.4), Some(0.4)];
+            (["l", "a", "b"], origin.to_oklaba(), refs)
+        }
+        ColorFunction::OkLch => {
+            let refs = [Some(1.0), Some(0.4), None];
+            (["l", "c", "h"], origin.convert_to(ColorSpace::OkLch), refs)
+        }
+    };
+
+    let vars = [
+        (names[0], x),
+        (names[1], y),
+        (names[2], z),
+        ("alpha", alpha),
+    ];
+
+    let mut values = [0.0; 4];
+    for (i, token) in channels.iter().enumerate() {
+        let reference = if i < 3 { refs[i] } else { Some(1.0) };
+        values[i] = eval_channel(token, reference, &vars).ok_or_else(|| f.error())?;
+    }
+    let [x, y, z, alpha] = resolve_missing(values);
+
+    // Percentage channels are relative to 100, rgb channels to 255.
+    Ok(match f {
+        ColorFunction::Rgb => f.to_color(x / 255.0, y / 255.0, z / 255.0, alpha),
+        ColorFunction::Hsl | ColorFunction::Hwb | ColorFunction::Hsv => {
+            f.to_color(x, y / 100.0, z / 100.0, alpha)
+        }
+        _ => f.to_color(x, y, z, alpha),
+    })
+}
+
+// Split at whitespace and '/' outside of parentheses.
+pub(super) fn tokenize(s: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 && (c.is_whitespace() || c == '/') {
+            if let Some(st) = start.take() {
+                tokens.push(&s[st..i]);
+            }
+            if c == '/' {
+                tokens.push("/");
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+
+    if let Some(st) = start {
+        tokens.push(&s[st..]);
+    }
+
+    tokens
+}
+
+// Evaluate a channel value: a channel keyword, `calc()` expression, or literal.
+// `reference` is the value of 100%, `None` for hue angles.
+fn eval_channel(s: &str, reference: Option<f32>, vars: &[(&str, f32)]) -> Option<f32> {
+    if s == "none" {
+        return Some(f32::NAN);
+    }
+
+    if let Some((_, v)) = vars.iter().find(|(name, _)| *name == s) {
+        return Some(*v);
+    }
+
+    if let Some(expr) = s.strip_prefix("calc(").and_then(|s| s.strip_suffix(')')) {
+        let mut calc = Calc {
+            s: expr.as_bytes(),
+            pos: 0,
+            depth: 0,
+            vars,
+        };
+        let v = calc.expr()?;
+        calc.skip_whitespace();
+        if calc.pos != calc.s.len() {
+            return None;
+        }
+        return Some(v);
+    }
+
+    match reference {
+        Some(reference) => {
+            if let Some(s) = s.strip_suffix('%') {
+                return parse_number(s).ok().map(|t| t / 100.0 * reference);
+            }
+            parse_number(s).ok()
+        }
+        None => parse_angle(s).ok(),
+    }
+}
+
+// Maximum nesting of parentheses and unary signs in `calc()`, deeper expressions are rejected
+// instead of overflowing the stack.
+const MAX_CALC_DEPTH: usize = 64;
+
+// Minimal `calc()` evaluator supporting numbers (with optional angle units), channel keywords,
+// `+ - * /` and parentheses.
+struct Calc<'a> {
+    s: &'a [u8],
+    pos: usize,
+    depth: usize,
+    vars: &'a [(&'a str, f32)],
+}
+
+impl Calc<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.s.len() && self.s[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.s.get(self.pos).copied()
+    }
+
+    fn expr(&mut self) -> Option<f32> {
+        let mut v = self.term()?;
+        while let Some(op @ (b'+' | b'-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            v = if op == b'+' { v + rhs } else { v - rhs };
+        }
+        Some(v)
+    }
+
+    fn term(&mut self) -> Option<f32> {
+        let mut v = self.factor()?;
+        while let Some(op @ (b'*' | b'/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            v = if op == b'*' { v * rhs } else { v / rhs };
+        }
+        Some(v)
+    }
+
+    fn factor(&mut self) -> Option<f32> {
+        if self.depth == MAX_CALC_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let v = self.primary();
+        self.depth -= 1;
+        v
+    }
+
+    fn primary(&mut self) -> Option<f32> {
+        match self.peek()? {
+            b'(' => {
+                self.pos += 1;
+                let v = self.expr()?;
+                if self.peek()? != b')' {
+                    return None;
+                }
+                self.pos += 1;
+                Some(v)
+            }
+            b'-' => {
+                self.pos += 1;
+                Some(-self.factor()?)
+            }
+            b'+' => {
+                self.pos += 1;
+                self.factor()
+            }
+            c if c.is_ascii_digit() || c == b'.' => {
+                let start = self.pos;
+                while self.pos < self.s.len()
+                    && (self.s[self.pos].is_ascii_digit() || self.s[self.pos] == b'.')
+                {
+                    self.pos += 1;
+                }
+                let v = parse_number(std::str::from_utf8(&self.s[start..self.pos]).ok()?).ok()?;
+
+                // angle units, converted to degrees
+                let start = self.pos;
+                while self.pos < self.s.len() && self.s[self.pos].is_ascii_alphabetic() {
+                    self.pos += 1;
+                }
+                match &self.s[start..self.pos] {
+                    b"" | b"deg" => Some(v),
+                    b"grad" => Some(v * 360.0 / 400.0),
+                    b"rad" => Some(v.to_degrees()),
+                    b"turn" => Some(v * 360.0),
+                    _ => None,
+                }
+            }
+            c if c.is_ascii_alphabetic() => {
+                let start = self.pos;
+                while self.pos < self.s.len() && self.s[self.pos].is_ascii_alphabetic() {
+                    self.pos += 1;
+                }
+                let name = std::str::from_utf8(&self.s[start..self.pos]).ok()?;
+                if name == "calc" && self.peek() == Some(b'(') {
+                    return self.factor();
+                }
+                self.vars.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+            }
+            _ => None,
+        }
+    }
+}
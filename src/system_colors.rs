@@ -0,0 +1,82 @@
+// https://www.w3.org/TR/css-color-4/#css-system-colors
+
+use crate::Color;
+
+/// Resolve CSS [system colors](https://www.w3.org/TR/css-color-4/#css-system-colors) to
+/// platform colors.
+///
+/// # Examples
+/// ```
+/// use csscolorparser::{Color, SystemColorResolver};
+///
+/// struct DarkTheme;
+///
+/// impl SystemColorResolver for DarkTheme {
+///     fn resolve(&self, name: &str) -> Option<Color> {
+///         match name {
+///             "canvas" => Some(Color::BLACK),
+///             "canvastext" => Some(Color::WHITE),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Color::from_css_system_color_with("Canvas", &DarkTheme), Some(Color::BLACK));
+/// assert_eq!(
+///     Color::from_css_system_color_with("LinkText", &DarkTheme),
+///     Color::from_css_system_color("LinkText")
+/// );
+/// ```
+pub trait SystemColorResolver {
+    /// Resolve the system color `name` (in lowercase). Returning `None` falls back to the
+    /// default value.
+    fn resolve(&self, name: &str) -> Option<Color>;
+}
+
+// Chrome's values on a standard light theme
+static SYSTEM_COLORS: [(&str, [u8; 3]); 19] = [
+    ("AccentColor", [0, 117, 255]),
+    ("AccentColorText", [255, 255, 255]),
+    ("ActiveText", [255, 0, 0]),
+    ("ButtonBorder", [118, 118, 118]),
+    ("ButtonFace", [239, 239, 239]),
+    ("ButtonText", [0, 0, 0]),
+    ("Canvas", [255, 255, 255]),
+    ("CanvasText", [0, 0, 0]),
+    ("Field", [255, 255, 255]),
+    ("FieldText", [0, 0, 0]),
+    ("GrayText", [128, 128, 128]),
+    ("Highlight", [181, 213, 255]),
+    ("HighlightText", [0, 0, 0]),
+    ("LinkText", [0, 0, 238]),
+    ("Mark", [255, 255, 0]),
+    ("MarkText", [0, 0, 0]),
+    ("SelectedItem", [0, 117, 255]),
+    ("SelectedItemText", [255, 255, 255]),
+    ("VisitedText", [85, 26, 139]),
+];
+
+/// CSS color which may be a [system color](https://www.w3.org/TR/css-color-4/#css-system-colors)
+/// left unresolved, returned by [`Color::from_css_system_keyword()`](struct.Color.html#method.from_css_system_keyword).
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssSystemColor {
+    /// A regular CSS color.
+    Resolved(Color),
+    /// A system color keyword, in its canonical case (e.g. `ButtonFace`).
+    System(String),
+}
+
+pub(crate) fn default_system_color(name: &str) -> Option<Color> {
+    SYSTEM_COLORS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, [r, g, b])| Color::from_rgb_u8(*r, *g, *b))
+}
+
+// Canonical name of the system color `name` (case-insensitive).
+pub(crate) fn system_color_name(name: &str) -> Option<&'static str> {
+    SYSTEM_COLORS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(n, _)| *n)
+}
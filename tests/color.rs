@@ -1,5 +1,9 @@
-use csscolorparser::Color;
+use csscolorparser::{
+    sort_by_hue, sort_by_lightness, sort_by_oklab_lightness, BlendMode, Color, Color64, ColorSpace,
+    CssFormat, CssStringOptions, HueInterpolationMode, ParseColorError,
+};
 use std::convert::TryFrom;
+use std::error::Error;
 
 #[test]
 fn basic() {
@@ -35,7 +39,7 @@ fn basic() {
     let c = Color::from_rgb(0., 0., 0.6);
     assert_eq!(c.to_hsva(), (240., 1., 0.6, 1.));
     assert_eq!(c.to_hsla(), (240., 1., 0.3, 1.));
-    assert_eq!(c.to_hwba(), (240., 0., 0.4, 1.));
+    assert_eq!(c.to_hwba(), (240., 0., 1. - 0.6, 1.));
 
     let c = Color::from_rgb(0.5, 0.5, 0.5);
     assert_eq!(c.to_hsva(), (0., 0., 0.5, 1.));
@@ -75,6 +79,19 @@ fn basic() {
 }
 
 #[test]
+fn gtk_style_color() {
+    let c = Color::from_rgb(1., 0., 0.);
+    assert_eq!(c.to_gtk_style_color(), "#ff0000");
+
+    let c = Color::from_rgba(1., 0., 0., 0.5);
+    assert_eq!(c.to_gtk_style_color(), "alpha(#ff0000, 0.5)");
+
+    let c = Color::from_rgba_u8(0, 128, 255, 64);
+    assert_eq!(c.to_gtk_style_color(), "alpha(#0080ff, 0.251)");
+}
+
+#[test]
+#[allow(clippy::excessive_precision)]
 fn red() {
     let data = &[
         Color::from_rgb(1., 0., 0.),
@@ -96,6 +113,33 @@ fn red() {
     }
 }
 
+#[test]
+fn achromatic() {
+    let data = &[
+        Color::from_rgb(0., 0., 0.),
+        Color::from_rgb(0.5, 0.5, 0.5),
+        Color::from_rgb(1., 1., 1.),
+        Color::from_rgb(1.5, 1.5, 1.5),
+        Color::from_rgb(-0.5, -0.5, -0.5),
+    ];
+    for c in data {
+        let (h, s, _, _) = c.to_hsla();
+        assert_eq!((h, s), (0., 0.));
+        let (h, s, _, _) = c.to_hsva();
+        assert_eq!((h, s), (0., 0.));
+    }
+
+    let c = Color::from_rgb(0.5, 0.5, 0.5);
+    assert_eq!(c.to_hsla(), (0., 0., 0.5, 1.));
+    assert_eq!(c.to_hsva(), (0., 0., 0.5, 1.));
+
+    // out of gamut values must not divide by zero
+    let c = Color::from_rgb(1.5, 0.5, 0.5);
+    assert!(c.to_hsla().1.is_finite());
+    let c = Color::from_rgb(0., -0.5, -0.5);
+    assert!(c.to_hsva().1.is_finite());
+}
+
 #[test]
 fn interpolate() {
     let a = Color::from_rgb(0., 1., 0.);
@@ -139,3 +183,2796 @@ fn interpolate() {
         assert_eq!(a.interpolate_lch(&b, 1.0).rgba_u8(), (0, 0, 255, 255));
     }
 }
+
+#[test]
+fn soft_light() {
+    let backdrop = Color::from_rgb(0.25, 0.5, 0.75);
+
+    // a 50% gray source leaves the backdrop unchanged in both formulas
+    let gray = Color::from_rgb(0.5, 0.5, 0.5);
+    assert_eq!(gray.blend_soft_light(&backdrop), backdrop);
+    assert_eq!(gray.blend_soft_light_photoshop(&backdrop), backdrop);
+
+    let black = Color::from_rgb(0., 0., 0.);
+    assert_eq!(
+        black.blend_soft_light(&backdrop).rgba_u8(),
+        (16, 64, 143, 255)
+    );
+    assert_eq!(
+        black.blend_soft_light_photoshop(&backdrop).rgba_u8(),
+        (16, 64, 143, 255)
+    );
+
+    let white = Color::from_rgb(1., 1., 1.);
+    assert_eq!(
+        white.blend_soft_light(&backdrop).rgba_u8(),
+        (128, 180, 221, 255)
+    );
+    assert_eq!(
+        white.blend_soft_light_photoshop(&backdrop).rgba_u8(),
+        (128, 180, 221, 255)
+    );
+
+    // the formulas differ for light sources over dark backdrops
+    let src = Color::from_rgb(0.75, 0.75, 0.75);
+    let dark = Color::from_rgb(0.1, 0.1, 0.1);
+    assert_ne!(
+        src.blend_soft_light(&dark).rgba_u8(),
+        src.blend_soft_light_photoshop(&dark).rgba_u8()
+    );
+
+    let backdrop = Color::from_rgba(0.25, 0.5, 0.75, 0.5);
+    assert_eq!(white.blend_soft_light(&backdrop).a, 0.5);
+    assert_eq!(
+        src.soft_light_blend(&backdrop),
+        src.blend_soft_light(&backdrop)
+    );
+}
+
+#[test]
+fn blend_color_dodge_burn() {
+    let src = Color::from_rgba(0.5, 0.25, 0.8, 0.3);
+    let backdrop = Color::from_rgba(0.25, 0.5, 0.1, 0.6);
+
+    let c = src.blend_color_dodge(&backdrop);
+    assert!((c.r - 0.5).abs() < 1e-6);
+    assert!((c.g - 0.5 / 0.75).abs() < 1e-6);
+    assert!((c.b - 0.5).abs() < 1e-6);
+    assert_eq!(c.a, 0.6);
+
+    let c = src.blend_color_burn(&backdrop);
+    assert!(c.r.abs() < 1e-6);
+    assert!(c.g.abs() < 1e-6);
+    assert!(c.b.abs() < 1e-6);
+    assert_eq!(c.a, 0.6);
+
+    let c = Color::from_rgb(0.8, 0.8, 0.8).blend_color_burn(&Color::from_rgb(0.6, 0.9, 0.2));
+    assert!((c.r - 0.5).abs() < 1e-6);
+    assert!((c.g - 0.875).abs() < 1e-6);
+    assert!(c.b.abs() < 1e-6);
+
+    // singularities: source 1 for dodge, source 0 for burn
+    let values = [0.0, 0.25, 0.5, 1.0];
+    for &s in &values {
+        for &b in &values {
+            let src = Color::from_rgb(s, s, s);
+            let backdrop = Color::from_rgb(b, b, b);
+            for c in [
+                src.blend_color_dodge(&backdrop),
+                src.blend_color_burn(&backdrop),
+            ] {
+                assert!(c.to_array().iter().all(|v| v.is_finite()));
+                assert!(c.r >= 0.0 && c.r <= 1.0);
+            }
+        }
+    }
+
+    let white = Color::from_rgb(1.0, 1.0, 1.0);
+    let black = Color::from_rgb(0.0, 0.0, 0.0);
+    let gray = Color::from_rgb(0.5, 0.5, 0.5);
+    assert_eq!(white.blend_color_dodge(&gray).rgba(), white.rgba());
+    assert_eq!(white.blend_color_dodge(&black).rgba(), black.rgba());
+    assert_eq!(black.blend_color_dodge(&gray).rgba(), gray.rgba());
+    assert_eq!(black.blend_color_burn(&gray).rgba(), black.rgba());
+    assert_eq!(black.blend_color_burn(&white).rgba(), white.rgba());
+    assert_eq!(white.blend_color_burn(&gray).rgba(), gray.rgba());
+
+    for mode in [BlendMode::ColorDodge, BlendMode::ColorBurn] {
+        let src = Color::from_rgb(0.3, 0.6, 0.9);
+        let backdrop = Color::from_rgb(0.4, 0.2, 0.7);
+        let expected = match mode {
+            BlendMode::ColorDodge => src.blend_color_dodge(&backdrop),
+            _ => src.blend_color_burn(&backdrop),
+        };
+        let c = Color::blend_alpha_compositing(&src, &backdrop, mode);
+        assert_eq!(c.rgba_u8(), expected.rgba_u8());
+    }
+}
+
+#[test]
+fn blend_non_separable() {
+    let red = Color::from_rgb(1.0, 0.0, 0.0);
+    let gray = Color::from_rgba(0.5, 0.5, 0.5, 0.4);
+
+    // a gray backdrop has no saturation to give, a gray source none to take
+    assert_eq!(red.blend_hue(&gray).rgba(), (0.5, 0.5, 0.5, 0.4));
+    assert_eq!(red.blend_saturation(&gray).rgba(), (0.5, 0.5, 0.5, 0.4));
+    assert_eq!(gray.blend_saturation(&red).to_hex_string(), "#4d4d4d");
+
+    // SetLum(red, 0.5) is clipped back into gamut
+    let c = red.blend_color(&gray);
+    assert!((c.r - 1.0).abs() < 1e-6);
+    assert!((c.g - 0.2 / 0.7).abs() < 1e-6);
+    assert!((c.b - 0.2 / 0.7).abs() < 1e-6);
+    assert_eq!(c.a, 0.4);
+
+    let c = red.blend_luminosity(&gray);
+    assert!((c.r - 0.3).abs() < 1e-6);
+    assert!((c.g - 0.3).abs() < 1e-6);
+    assert!((c.b - 0.3).abs() < 1e-6);
+
+    // the result of SetLum has the requested luminosity
+    let src = Color::from_rgb(0.2, 0.7, 0.4);
+    let backdrop = Color::from_rgb(0.9, 0.3, 0.6);
+    let lum = |c: &Color| 0.3 * c.r + 0.59 * c.g + 0.11 * c.b;
+    assert!((lum(&src.blend_color(&backdrop)) - lum(&backdrop)).abs() < 1e-5);
+    assert!((lum(&src.blend_luminosity(&backdrop)) - lum(&src)).abs() < 1e-5);
+    assert!((lum(&src.blend_hue(&backdrop)) - lum(&backdrop)).abs() < 1e-5);
+
+    let modes = [
+        BlendMode::Hue,
+        BlendMode::Saturation,
+        BlendMode::Color,
+        BlendMode::Luminosity,
+    ];
+    for s in [
+        "#ff0000", "#3366cc", "#c0ffee", "#804000", "#808080", "#000000",
+    ] {
+        for b in ["#00ff00", "#ffcc00", "#123456", "#ffffff", "#7f7f7f"] {
+            let (src, backdrop): (Color, Color) = (s.parse().unwrap(), b.parse().unwrap());
+            for mode in modes {
+                let c = match mode {
+                    BlendMode::Hue => src.blend_hue(&backdrop),
+                    BlendMode::Saturation => src.blend_saturation(&backdrop),
+                    BlendMode::Color => src.blend_color(&backdrop),
+                    _ => src.blend_luminosity(&backdrop),
+                };
+                let expected = Color::blend_alpha_compositing(&src, &backdrop, mode);
+                assert_eq!(c.rgba_u8(), expected.rgba_u8(), "{} {}", s, b);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "lab")]
+#[test]
+fn lab_lch_string() {
+    let data = [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgb(0.2, 0.6, 0.4),
+        Color::from_rgba(0.0, 0.0, 1.0, 0.5),
+        Color::from_rgb(1.0, 1.0, 1.0),
+        Color::from_rgba_u8(12, 34, 56, 78),
+    ];
+    for c in data {
+        let s = c.to_lab_string();
+        assert!(s.starts_with("lab("));
+        assert_eq!(
+            csscolorparser::parse(&s).unwrap().rgba_u8(),
+            c.rgba_u8(),
+            "{}",
+            s
+        );
+
+        let s = c.to_lch_string();
+        assert!(s.starts_with("lch("));
+        assert_eq!(
+            csscolorparser::parse(&s).unwrap().rgba_u8(),
+            c.rgba_u8(),
+            "{}",
+            s
+        );
+    }
+
+    let c = Color::from_rgba(0.0, 0.0, 0.0, 0.5);
+    assert_eq!(c.to_lab_string(), "lab(0% 0 0 / 0.5)");
+    assert_eq!(c.to_lch_string(), "lch(0% 0 0 / 0.5)");
+}
+
+#[test]
+fn linear_gradient_stop() {
+    let c = Color::from_rgba(1.0, 0.5, 0.0, 0.5);
+    let data = [
+        (CssFormat::Hex, 0.0, "#ff800080 0%"),
+        (CssFormat::Rgb, 33.3333, "rgba(255,128,0,0.5) 33.333%"),
+        (CssFormat::Hsl, 100.0, "hsla(30,100%,50%,0.5) 100%"),
+        (
+            CssFormat::Oklab,
+            12.5,
+            "oklab(0.731 0.113 0.148 / 0.5) 12.5%",
+        ),
+        (
+            CssFormat::Oklch,
+            -10.0,
+            "oklch(0.731 0.186 52.762 / 0.5) -10%",
+        ),
+    ];
+    for (format, pos, s) in data {
+        assert_eq!(c.to_css_linear_gradient_stop(pos, format), s);
+    }
+
+    let stops = [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgb(0.0, 0.0, 1.0),
+    ]
+    .iter()
+    .zip([0.0, 100.0])
+    .map(|(c, pos)| c.to_css_linear_gradient_stop(pos, CssFormat::Rgb))
+    .collect::<Vec<_>>();
+    assert_eq!(
+        format!("linear-gradient({})", stops.join(", ")),
+        "linear-gradient(rgb(255,0,0) 0%, rgb(0,0,255) 100%)"
+    );
+}
+
+#[test]
+fn integer_24bit() {
+    let data = [
+        (0x000000, (0, 0, 0)),
+        (0xffffff, (255, 255, 255)),
+        (0xff6600, (255, 102, 0)),
+        (0x123456, (18, 52, 86)),
+    ];
+    for (n, (r, g, b)) in data {
+        let c = Color::from_24bit_integer(n);
+        assert_eq!(c.rgba_u8(), (r, g, b, 255));
+        assert_eq!(c.to_24bit_integer(), n);
+    }
+
+    // higher bits are ignored
+    assert_eq!(
+        Color::from_24bit_integer(0xabff6600).rgba_u8(),
+        (255, 102, 0, 255)
+    );
+    // alpha is ignored
+    assert_eq!(
+        Color::from_rgba_u8(255, 102, 0, 0).to_24bit_integer(),
+        0xff6600
+    );
+}
+
+#[test]
+fn to_css_var_definition() {
+    let data = [
+        ("#1a73e8", "brand-primary", "--brand-primary: #1a73e8;"),
+        ("#1a73e8", "--brand-primary", "--brand-primary: #1a73e8;"),
+        ("#ff000080", "overlay", "--overlay: #ff000080;"),
+        ("#000", "x", "--x: #000000;"),
+    ];
+    for (s, name, expected) in data {
+        let c: Color = s.parse().unwrap();
+        assert_eq!(c.to_css_var_definition(name), expected);
+    }
+}
+
+#[test]
+fn from_oklch_polar() {
+    let data = [
+        "#ff0000", "#00ff00", "#0000ff", "#ff8000", "#336699", "#000000", "#ffffff", "#808080",
+    ];
+    for s in data {
+        let c: Color = s.parse().unwrap();
+        let (l, ch, h, _) = c.convert_to(ColorSpace::OkLch);
+        let h = if h.is_nan() { 0.0 } else { h };
+        let rad = h.to_radians();
+
+        assert_eq!(Color::from_oklch_polar(l, ch, rad).to_hex_string(), s);
+        assert_eq!(
+            Color::from_oklch_polar(l, ch, rad).rgba_u8(),
+            csscolorparser::parse(&format!("oklch({} {} {})", l, ch, h))
+                .unwrap()
+                .rgba_u8()
+        );
+        // a full turn gives the same color
+        assert_eq!(
+            Color::from_oklch_polar(l, ch, rad + std::f32::consts::TAU).to_hex_string(),
+            s
+        );
+    }
+
+    let (l, a, b, _) = Color::from_rgb(0.2, 0.5, 0.8).to_oklaba();
+    let c = Color::from_oklcha_polar(l, a.hypot(b), b.atan2(a), 0.5);
+    assert_eq!(c.rgba_u8(), (51, 128, 204, 128));
+}
+
+#[test]
+fn distance_rgb() {
+    assert_eq!(Color::BLACK.distance_rgb(&Color::WHITE), 3f32.sqrt());
+    assert_eq!(Color::WHITE.distance_rgb(&Color::BLACK), 3f32.sqrt());
+
+    let data = [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgba(0.2, 0.6, 0.4, 0.5),
+        Color::from_rgb(0.9, 0.9, 0.1),
+    ];
+    for a in &data {
+        assert_eq!(a.distance_rgb(a), 0.0);
+        for b in &data {
+            assert_eq!(a.distance_rgb(b), b.distance_rgb(a));
+        }
+    }
+
+    let a = Color::from_rgb(1.0, 0.0, 0.0);
+    let b = Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+    assert_eq!(a.distance_rgb(&b), 1.0);
+}
+
+#[test]
+fn distance_squared_srgb() {
+    assert_eq!(Color::BLACK.distance_squared_srgb(&Color::WHITE), 3.0);
+
+    let data = [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgba(0.2, 0.6, 0.4, 0.5),
+        Color::from_rgb(0.9, 0.9, 0.1),
+        Color::BLACK,
+    ];
+    for a in &data {
+        assert_eq!(a.distance_squared_srgb(a), 0.0);
+        for b in &data {
+            let d = a.distance_squared_srgb(b);
+            assert_eq!(d, b.distance_squared_srgb(a));
+            assert!((d.sqrt() - a.distance_rgb(b)).abs() < 1e-6);
+        }
+    }
+
+    let a = Color::from_rgb(0.5, 0.0, 0.0);
+    let b = Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+    assert_eq!(a.distance_squared_srgb(&b), 0.25);
+}
+
+#[test]
+fn distance_oklab() {
+    assert!((Color::BLACK.distance_oklab(&Color::WHITE) - 1.0).abs() < 1e-4);
+
+    let data = [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgba(0.2, 0.6, 0.4, 0.5),
+        Color::from_rgb(0.9, 0.9, 0.1),
+        Color::BLACK,
+    ];
+    for a in &data {
+        assert_eq!(a.distance_oklab(a), 0.0);
+        for b in &data {
+            assert_eq!(a.distance_oklab(b), b.distance_oklab(a));
+        }
+    }
+
+    let pastel1 = Color::from_rgb_u8(255, 209, 220);
+    let pastel2 = Color::from_rgb_u8(255, 211, 221);
+    let d = pastel1.distance_oklab(&pastel2);
+    assert!(d > 0.0 && d < 0.01);
+    assert!(Color::BLACK.distance_oklab(&Color::WHITE) > d);
+
+    // alpha is ignored
+    let c = Color::from_rgba(1.0, 0.0, 0.0, 0.0);
+    assert_eq!(c.distance_oklab(&data[0]), 0.0);
+}
+
+#[test]
+fn closest_in() {
+    let palette: Vec<Color> = [
+        "#000", "#fff", "#f00", "#0f0", "#00f", "#ff0", "#0ff", "#f0f",
+    ]
+    .iter()
+    .map(|s| s.parse().unwrap())
+    .collect();
+
+    let data = [
+        ("#000", "#000"),
+        ("#222", "#000"),
+        ("#ddd", "#fff"),
+        ("#c03", "#f00"),
+        ("#3c3", "#0f0"),
+        ("#11a", "#00f"),
+        ("#ee5", "#ff0"),
+        ("#5ee", "#0ff"),
+        ("#d3d", "#f0f"),
+    ];
+    for (s, expected) in data {
+        let c: Color = s.parse().unwrap();
+        let expected: Color = expected.parse().unwrap();
+        assert_eq!(c.closest_in(&palette), Some(&expected), "{}", s);
+
+        let (closest, d) = c.closest_in_with_distance(&palette);
+        assert_eq!(closest, Some(&expected));
+        assert_eq!(d, c.distance_oklab(&expected));
+    }
+
+    let c = Color::from_rgb(0.5, 0.5, 0.5);
+    assert_eq!(c.closest_in(&[]), None);
+    assert_eq!(c.closest_in_with_distance(&[]), (None, f32::INFINITY));
+}
+
+#[test]
+fn url_safe() {
+    let data = [
+        ((0, 0, 0, 0), "AAAAAA"),
+        ((255, 255, 255, 255), "_____w"),
+        ((255, 0, 0, 255), "_wAA_w"),
+        ((255, 102, 0, 128), "_2YAgA"),
+    ];
+    for ((r, g, b, a), s) in data {
+        let c = Color::from_rgba_u8(r, g, b, a);
+        assert_eq!(c.encode_url_safe(), s);
+        assert_eq!(Color::from_url_safe(s).unwrap(), c);
+    }
+
+    for i in 0..=255u8 {
+        let c = Color::from_rgba_u8(i, 255 - i, i / 2, i ^ 0x5a);
+        let s = c.encode_url_safe();
+        assert_eq!(s.len(), 6);
+        assert!(s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(Color::from_url_safe(&s).unwrap(), c);
+    }
+
+    for s in [
+        "",
+        "AAAAA",
+        "AAAAAAA",
+        "AAAA+A",
+        "AAAA/A",
+        "AAAAA=",
+        "AAAAAB",
+        "_____\u{a3}",
+    ] {
+        assert!(Color::from_url_safe(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn channel() {
+    let c = Color::from_rgba(0.1, 0.2, 0.3, 0.4);
+    assert_eq!(c.channel(0), 0.1);
+    assert_eq!(c.channel(1), 0.2);
+    assert_eq!(c.channel(2), 0.3);
+    assert_eq!(c.channel(3), 0.4);
+
+    assert_eq!(c.set_channel(0, 1.0), Color::from_rgba(1.0, 0.2, 0.3, 0.4));
+    assert_eq!(c.set_channel(1, 0.5), Color::from_rgba(0.1, 0.5, 0.3, 0.4));
+    assert_eq!(c.set_channel(2, -1.0), Color::from_rgba(0.1, 0.2, 0.0, 0.4));
+    assert_eq!(c.set_channel(3, 2.0), Color::from_rgba(0.1, 0.2, 0.3, 1.0));
+
+    for i in 0..4 {
+        assert_eq!(c.set_channel(i, 0.75).channel(i), 0.75);
+    }
+}
+
+#[test]
+#[should_panic]
+fn channel_out_of_bounds() {
+    Color::BLACK.channel(4);
+}
+
+#[test]
+#[should_panic]
+fn set_channel_out_of_bounds() {
+    Color::BLACK.set_channel(usize::MAX, 0.0);
+}
+
+#[test]
+fn perceptually_black_white() {
+    let black = [
+        Color::from_rgb_u8(0, 0, 0),
+        Color::from_rgb_u8(0, 0, 3),
+        Color::from_rgba_u8(0, 0, 0, 0),
+    ];
+    for c in black {
+        assert!(c.is_perceptually_black(0.05), "{:?}", c);
+        assert!(!c.is_perceptually_white(0.05), "{:?}", c);
+    }
+
+    let white = [
+        Color::from_rgb_u8(255, 255, 255),
+        Color::from_rgb_u8(250, 250, 250),
+        Color::from_rgb_u8(255, 250, 240),
+        Color::from_rgb_u8(240, 240, 240),
+    ];
+    for c in white {
+        assert!(c.is_perceptually_white(0.05), "{:?}", c);
+        assert!(!c.is_perceptually_black(0.05), "{:?}", c);
+    }
+
+    let neither = [
+        Color::from_rgb_u8(128, 128, 128),
+        Color::from_rgb_u8(255, 0, 0),
+        Color::from_rgb_u8(0, 0, 255),
+        Color::from_rgb_u8(20, 0, 0),
+        Color::from_rgb_u8(200, 200, 200),
+    ];
+    for c in neither {
+        assert!(!c.is_perceptually_black(0.05), "{:?}", c);
+        assert!(!c.is_perceptually_white(0.05), "{:?}", c);
+    }
+
+    // larger thresholds include near-black and light colors
+    for c in [Color::from_rgb_u8(1, 1, 1), Color::from_rgb_u8(0, 0, 40)] {
+        assert!(!c.is_perceptually_black(0.05));
+        assert!(c.is_perceptually_black(0.2));
+    }
+    let c = Color::from_rgb_u8(255, 255, 0);
+    assert!(!c.is_perceptually_white(0.02));
+    assert!(c.is_perceptually_white(0.05));
+}
+
+#[test]
+fn blend_paint_mix() {
+    let red = Color::from_rgb(1.0, 0.0, 0.0);
+    let yellow = Color::from_rgb(1.0, 1.0, 0.0);
+    let blue = Color::from_rgb(0.0, 0.0, 1.0);
+
+    // red + blue = purple
+    let c = red.blend_paint_mix(&blue, 0.5);
+    assert!(c.r > c.g && c.b > c.g);
+    assert_eq!(c.rgba_u8(), (128, 0, 128, 255));
+
+    // yellow + blue = green, RGB interpolation gives gray
+    let c = yellow.blend_paint_mix(&blue, 0.5);
+    assert!(c.g > c.r && c.g > c.b);
+    assert_eq!(c.rgba_u8(), (0, 128, 0, 255));
+    assert!(yellow.interpolate_rgb(&blue, 0.5).is_achromatic(1e-6));
+
+    // red + yellow = orange
+    let c = red.blend_paint_mix(&yellow, 0.5);
+    assert!(c.r > c.g && c.g > c.b);
+    assert_eq!(c.rgba_u8(), (128, 64, 0, 255));
+
+    // RYB and RGB mixing only agree for some pairs, e.g. red + blue
+    assert_eq!(
+        red.blend_paint_mix(&blue, 0.5).rgba_u8(),
+        red.interpolate_rgb(&blue, 0.5).rgba_u8()
+    );
+    for (a, b) in [(&yellow, &blue), (&red, &yellow), (&blue, &yellow)] {
+        for t in [0.25, 0.5, 0.75] {
+            assert_ne!(
+                a.blend_paint_mix(b, t).rgba_u8(),
+                a.interpolate_rgb(b, t).rgba_u8()
+            );
+        }
+    }
+
+    // end points are unchanged
+    let data = [
+        Color::from_rgb(0.2, 0.6, 0.4),
+        Color::from_rgba(0.9, 0.3, 0.7, 0.5),
+        Color::from_rgb(1.0, 1.0, 1.0),
+        Color::from_rgb(0.0, 0.0, 0.0),
+    ];
+    for c in data {
+        assert_eq!(c.blend_paint_mix(&blue, 0.0).rgba_u8(), c.rgba_u8());
+        assert_eq!(blue.blend_paint_mix(&c, 1.0).rgba_u8(), c.rgba_u8());
+    }
+
+    let c = red.blend_paint_mix(&Color::from_rgba(0.0, 0.0, 1.0, 0.0), 0.5);
+    assert_eq!(c.a, 0.5);
+}
+
+#[test]
+fn mix_weighted() {
+    let a = Color::from_rgba(1.0, 0.0, 0.0, 1.0);
+    let b = Color::from_rgba(0.0, 0.4, 1.0, 0.5);
+
+    for t in [0.0, 0.1, 0.25, 0.5, 0.8, 1.0] {
+        assert_eq!(a.mix_weighted(&b, t), a.interpolate_rgb(&b, t));
+    }
+    assert_eq!(a.mix_weighted(&b, 0.0), a);
+    assert_eq!(a.mix_weighted(&b, 1.0), b);
+
+    // weight is clamped
+    assert_eq!(a.mix_weighted(&b, -0.5), a);
+    assert_eq!(a.mix_weighted(&b, 2.0), b);
+
+    // same as mix() in sRGB for opaque colors
+    let b = Color::from_rgb(0.0, 0.4, 1.0);
+    for t in [0.0, 0.3, 0.5, 1.0] {
+        assert_eq!(
+            a.mix_weighted(&b, t).rgba_u8(),
+            a.mix(&b, t, ColorSpace::Srgb).rgba_u8()
+        );
+    }
+}
+
+#[test]
+fn mix_with_weight() {
+    let a = Color::from_rgb(1.0, 0.0, 0.0);
+    let b = Color::from_rgba(0.0, 0.0, 1.0, 0.5);
+
+    assert_eq!(a.mix_with_weight(&b, 1.0, 1.0), a.interpolate_rgb(&b, 0.5));
+    assert_eq!(a.mix_with_weight(&b, 3.0, 1.0), a.interpolate_rgb(&b, 0.25));
+    assert_eq!(a.mix_with_weight(&b, 0.4, 0.6), a.interpolate_rgb(&b, 0.6));
+    assert_eq!(
+        a.mix_with_weight(&b, 40.0, 60.0),
+        a.interpolate_rgb(&b, 0.6)
+    );
+    assert_eq!(a.mix_with_weight(&b, 1.0, 0.0), a);
+    assert_eq!(a.mix_with_weight(&b, 0.0, 2.0), b);
+    assert_eq!(a.mix_with_weight(&b, -1.0, 1.0), b);
+    assert_eq!(a.mix_with_weight(&b, 0.0, 0.0), a);
+
+    // not scaled by the sum, unlike color-mix() percentages below 100%
+    assert_eq!(a.mix_with_weight(&a, 0.1, 0.1).a, 1.0);
+}
+
+#[test]
+fn from_css4_hex() {
+    let data = [
+        ("#f00", (255, 0, 0, 255)),
+        ("#f008", (255, 0, 0, 136)),
+        ("#ff0000", (255, 0, 0, 255)),
+        ("#FF000080", (255, 0, 0, 128)),
+        ("0f0", (0, 255, 0, 255)),
+        (" #0000ff ", (0, 0, 255, 255)),
+    ];
+    for (s, rgba) in data {
+        assert_eq!(Color::from_css4_hex(s).unwrap().rgba_u8(), rgba, "{}", s);
+    }
+
+    let data = [
+        ("#", 0),
+        ("#f", 1),
+        ("#ff", 2),
+        ("#ff000", 5),
+        ("#ff00000", 7),
+        ("#ff0000000", 9),
+        ("#ff00000000", 10),
+    ];
+    for (s, n) in data {
+        let err = Color::from_css4_hex(s).unwrap_err();
+        assert_eq!(err, ParseColorError::InvalidHexLength(n));
+        assert_eq!(
+            err.to_string(),
+            format!("Invalid hex length: {} digits, expected 3, 4, 6 or 8.", n)
+        );
+    }
+
+    for s in ["#ggg", "#12345x", "#f\u{a3}f", "rgb(0,0,0)"] {
+        assert!(Color::from_css4_hex(s).is_err(), "{}", s);
+    }
+    assert_eq!(
+        Color::from_css4_hex("#f\u{a3}f"),
+        Err(ParseColorError::InvalidHex)
+    );
+
+    // invalid digits keep the integer parse error as the source
+    let err = Color::from_css4_hex("#ff00zz").unwrap_err();
+    assert!(matches!(err, ParseColorError::InvalidInt(_)));
+    let source = err.source().unwrap();
+    assert!(source.downcast_ref::<std::num::ParseIntError>().is_some());
+    assert_eq!(err.to_string(), format!("Invalid integer: {}.", source));
+}
+
+#[test]
+fn to_css_alpha_hex() {
+    let data = [
+        (Color::from_rgb(1.0, 0.0, 0.0), "#ff0000ff"),
+        (Color::from_rgba_u8(0, 128, 255, 128), "#0080ff80"),
+        (Color::from_rgba(0.0, 0.0, 0.0, 0.0), "#00000000"),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_css_alpha_hex(), s);
+        assert_eq!(csscolorparser::parse(s).unwrap(), c);
+    }
+}
+
+#[test]
+fn to_rgb_percent_string() {
+    let data = [
+        (Color::from_rgb(1.0, 0.0, 0.5), "rgb(100%,0%,50%)"),
+        (Color::from_rgba(1.0, 0.0, 0.0, 0.5), "rgba(100%,0%,0%,0.5)"),
+        (Color::from_rgb(0.123456, 0.5, 1.0), "rgb(12.35%,50%,100%)"),
+        (Color::from_rgba(0.0, 0.0, 0.0, 0.0), "rgba(0%,0%,0%,0)"),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_rgb_percent_string(), s);
+    }
+
+    for s in ["#ff8000", "#12345680", "#00000000", "#c0ffee"] {
+        let c = csscolorparser::parse(s).unwrap();
+        let c2 = csscolorparser::parse(&c.to_rgb_percent_string()).unwrap();
+        assert_eq!(c.rgba_u8(), c2.rgba_u8());
+    }
+}
+
+#[test]
+fn to_modern_css_string() {
+    let data = [
+        (Color::from_rgb(1.0, 0.0, 0.0), "rgb(255 0 0)"),
+        (Color::from_rgba_u8(0, 128, 255, 255), "rgb(0 128 255)"),
+        (Color::from_rgba(1.0, 0.5, 0.0, 0.5), "rgb(255 128 0 / 0.5)"),
+        (Color::from_rgba(0.0, 0.0, 0.0, 0.0), "rgb(0 0 0 / 0)"),
+        (
+            Color::from_rgba(0.2, 0.4, 0.6, 1.0 / 3.0),
+            "rgb(51 102 153 / 0.333)",
+        ),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_modern_css_string(), s);
+        assert_eq!(csscolorparser::parse(s).unwrap().rgba_u8(), c.rgba_u8());
+    }
+}
+
+#[test]
+fn to_hsl_modern_string() {
+    let data = [
+        (Color::from_rgb(1.0, 0.0, 0.0), "hsl(0 100% 50%)"),
+        (
+            Color::from_rgba(1.0, 0.0, 0.0, 0.5),
+            "hsl(0 100% 50% / 0.5)",
+        ),
+        (Color::from_rgb(0.0, 0.0, 1.0), "hsl(240 100% 50%)"),
+        (Color::from_rgb(0.5, 0.5, 0.5), "hsl(0 0% 50%)"),
+        (Color::from_rgba(1.0, 1.0, 1.0, 0.0), "hsl(0 0% 100% / 0)"),
+        (
+            Color::from_rgba(0.2, 0.4, 0.6, 1.0 / 3.0),
+            "hsl(210 50% 40% / 0.333)",
+        ),
+    ];
+    for (c, s) in data {
+        assert_eq!(c.to_hsl_modern_string(), s);
+    }
+
+    for s in ["#ff8000", "#12345680", "#c0ffee", "#808080", "#00000000"] {
+        let c = csscolorparser::parse(s).unwrap();
+        let hsl = c.to_hsl_modern_string();
+        assert!(hsl.starts_with("hsl(") && !hsl.contains(','));
+        assert_eq!(
+            hsl.contains(" / "),
+            c.to_modern_css_string().contains(" / ")
+        );
+        assert_eq!(hsl.contains(" / "), c.to_rgb_string().starts_with("rgba("));
+        let c2 = csscolorparser::parse(&hsl).unwrap();
+        assert_eq!(c2.rgba_u8(), c.rgba_u8());
+        assert_eq!(c2.to_modern_css_string(), c.to_modern_css_string());
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn print_debug() {
+    Color::from_rgba(1.0, 0.5, 0.0, 0.5).print_debug();
+}
+
+#[test]
+fn parse_from_bytes() {
+    let data: [&[u8]; 4] = [b"#f00", b"rgb(255, 0, 0)", b" hsl(0 100% 50%) ", b"ff0000"];
+    for s in data {
+        let c = Color::parse_from_bytes(s).unwrap();
+        assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+    }
+
+    let data: [&[u8]; 6] = [
+        b"",
+        b"#f0\xff",
+        b"\xc3\x28",
+        b"rgb(255,0,0\x80)",
+        "x\u{a3}".as_bytes(),
+        b"rgb(255,0)",
+    ];
+    for s in data {
+        assert!(Color::parse_from_bytes(s).is_err());
+    }
+}
+
+#[test]
+fn from_rgba_f16_array() {
+    let c = Color::from_rgba_f16_array([0x3c00, 0x0000, 0x3c00, 0x3c00]);
+    assert_eq!(c.rgba_u8(), (255, 0, 255, 255));
+
+    // 0.5 linear, negative zero, subnormal
+    let c = Color::from_rgba_f16_array([0x3800, 0x8000, 0x0001, 0x3800]);
+    assert_eq!(c.rgba_u8(), (188, 0, 0, 128));
+    assert!(c.b > 0.0);
+
+    // values are not clamped
+    let c = Color::from_rgba_f16_array([0x4000, 0xbc00, 0x0000, 0x3c00]);
+    assert_eq!(c.a, 1.0);
+    assert!(c.r > 1.0);
+    assert!(c.g < 0.0);
+
+    // infinity
+    let c = Color::from_rgba_f16_array([0x7c00, 0x0000, 0x0000, 0x3c00]);
+    assert!(c.r.is_infinite());
+}
+
+#[test]
+fn to_rgba_f16_array() {
+    let data = [
+        [0x3c00, 0x0000, 0x3c00, 0x3c00],
+        [0x3800, 0x8000, 0x0001, 0x3800],
+        [0x4000, 0xbc00, 0x03ff, 0x0400],
+        [0x32da, 0x2e66, 0x7bff, 0x7c00],
+    ];
+    for arr in data {
+        assert_eq!(Color::from_rgba_f16_array(arr).to_rgba_f16_array(), arr);
+    }
+
+    let c = Color::from_linear_rgba(0.1, 65520.0, -1e-9, 1.0 / 3.0);
+    assert_eq!(c.to_rgba_f16_array(), [0x2e66, 0x7c00, 0x8000, 0x3555]);
+}
+
+#[test]
+fn f16_array() {
+    let red = Color::from_rgb(1.0, 0.0, 0.0);
+    assert_eq!(red.to_f16_array(), [0x3c00, 0x0000, 0x0000, 0x3c00]);
+    assert_eq!(Color::from_f16_array(red.to_f16_array()), red);
+
+    let c = Color::from_rgba(0.2, 0.4, 0.6, 0.8);
+    let c2 = Color::from_f16_array(c.to_f16_array());
+    for (a, b) in c.to_array().iter().zip(c2.to_array().iter()) {
+        assert!((a - b).abs() < 1e-3);
+    }
+
+    // not converted to linear sRGB
+    let c = Color::from_rgba(0.5, 0.5, 0.5, 0.5);
+    assert_eq!(c.to_f16_array(), [0x3800; 4]);
+    assert_ne!(c.to_f16_array(), c.to_rgba_f16_array());
+
+    let data = [
+        [0x3c00, 0x0000, 0x3800, 0x3c00],
+        [0x0001, 0x8000, 0x03ff, 0x0400],
+        [0x32da, 0x2e66, 0x7bff, 0x3555],
+    ];
+    for arr in data {
+        assert_eq!(Color::from_f16_array(arr).to_f16_array(), arr);
+    }
+
+    // infinity and NaN
+    let c = Color::from_rgba(f32::INFINITY, f32::NEG_INFINITY, f32::NAN, 1e6);
+    let arr = c.to_f16_array();
+    assert_eq!(arr[0], 0x7c00);
+    assert_eq!(arr[1], 0xfc00);
+    assert_eq!(arr[2] & 0x7c00, 0x7c00);
+    assert_ne!(arr[2] & 0x3ff, 0);
+    assert_eq!(arr[3], 0x7c00);
+
+    let c = Color::from_f16_array(arr);
+    assert_eq!(c.r, f32::INFINITY);
+    assert_eq!(c.g, f32::NEG_INFINITY);
+    assert!(c.b.is_nan());
+    assert_eq!(c.a, f32::INFINITY);
+}
+
+#[test]
+fn into_array() {
+    let arr = [0.1, 0.2, 0.3, 0.4];
+    let c = Color::from(arr);
+    assert_eq!(<[f32; 4]>::from(c.clone()), arr);
+    assert_eq!(Color::from(<[f32; 4]>::from(c.clone())), c);
+
+    let arr = [12u8, 34, 56, 78];
+    let c = Color::from(arr);
+    let out: [u8; 4] = c.clone().into();
+    assert_eq!(out, arr);
+    assert_eq!(Color::from(out), c);
+
+    let c = Color::from_rgba(1.0, 0.5, 0.0, 1.0);
+    let out: [u8; 4] = c.into();
+    assert_eq!(out, [255, 128, 0, 255]);
+}
+
+#[test]
+fn as_slice() {
+    let mut c = Color::from_rgba(0.1, 0.2, 0.3, 0.4);
+    assert_eq!(c.as_ref(), &[0.1, 0.2, 0.3, 0.4]);
+
+    c.as_mut()[0] = 1.0;
+    c.as_mut()[3] = 0.5;
+    assert_eq!(c, Color::from_rgba(1.0, 0.2, 0.3, 0.5));
+
+    fn sum(v: impl AsRef<[f32]>) -> f32 {
+        v.as_ref().iter().sum()
+    }
+    assert_eq!(sum(&c), 2.0);
+}
+
+#[test]
+fn blend_alpha_compositing() {
+    let backdrop = Color::from_rgb(0.25, 0.5, 0.75);
+    let gray = Color::from_rgb(0.5, 0.5, 0.5);
+
+    // opaque colors: the result is the blend function itself
+    let data = [
+        (BlendMode::Normal, (128, 128, 128, 255)),
+        (BlendMode::Multiply, (32, 64, 96, 255)),
+        (BlendMode::Screen, (159, 191, 223, 255)),
+        (BlendMode::Darken, (64, 128, 128, 255)),
+        (BlendMode::Lighten, (128, 128, 191, 255)),
+        (BlendMode::Difference, (64, 0, 64, 255)),
+        (BlendMode::Exclusion, (128, 128, 128, 255)),
+        (BlendMode::Luminosity, (76, 140, 203, 255)),
+    ];
+    for (mode, rgba) in data {
+        let c = Color::blend_alpha_compositing(&gray, &backdrop, mode);
+        assert_eq!(c.rgba_u8(), rgba, "{:?}", mode);
+    }
+
+    // matches the existing soft light method for opaque colors
+    let src = Color::from_rgb(0.9, 0.2, 0.4);
+    assert_eq!(
+        Color::blend_alpha_compositing(&src, &backdrop, BlendMode::SoftLight),
+        src.blend_soft_light(&backdrop)
+    );
+
+    // hue, saturation and color keep the backdrop luminosity
+    for mode in [BlendMode::Hue, BlendMode::Saturation, BlendMode::Color] {
+        let c = Color::blend_alpha_compositing(&src, &backdrop, mode);
+        let lum = |c: &Color| 0.3 * c.r + 0.59 * c.g + 0.11 * c.b;
+        assert!((lum(&c) - lum(&backdrop)).abs() < 1e-5, "{:?}", mode);
+    }
+
+    // transparent source leaves the backdrop unchanged
+    let transparent = Color::from_rgba(1.0, 0.0, 0.0, 0.0);
+    for mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Hue] {
+        let c = Color::blend_alpha_compositing(&transparent, &backdrop, mode);
+        assert_eq!(c.rgba_u8(), backdrop.rgba_u8());
+    }
+
+    // transparent backdrop: the source is returned as-is
+    let src = Color::from_rgba(1.0, 0.0, 0.0, 0.5);
+    let c = Color::blend_alpha_compositing(
+        &src,
+        &Color::from_rgba(0.0, 0.0, 1.0, 0.0),
+        BlendMode::Multiply,
+    );
+    assert_eq!(c.rgba_u8(), (255, 0, 0, 128));
+
+    // both transparent
+    let c = Color::blend_alpha_compositing(&transparent, &transparent, BlendMode::Screen);
+    assert_eq!(c.rgba_u8(), (0, 0, 0, 0));
+
+    // alpha follows Porter-Duff source-over
+    let dst = Color::from_rgba(0.0, 0.0, 1.0, 0.5);
+    let c = Color::blend_alpha_compositing(&src, &dst, BlendMode::Normal);
+    assert_eq!(c.a, 0.75);
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn ansi_truecolor() {
+    let c = Color::from_rgb_u8(255, 0, 0);
+    assert_eq!(c.to_ansi_truecolor_string(true), "\x1b[38;2;255;0;0m");
+    assert_eq!(c.to_ansi_truecolor_string(false), "\x1b[48;2;255;0;0m");
+
+    let c = Color::from_rgba_u8(12, 34, 56, 0);
+    assert_eq!(c.to_ansi_truecolor_string(true), "\x1b[38;2;12;34;56m");
+
+    const RESET: &str = csscolorparser::ansi_reset();
+    assert_eq!(RESET, "\x1b[0m");
+}
+
+#[test]
+fn css_string_with_options() {
+    let opts = |format| CssStringOptions {
+        format,
+        ..Default::default()
+    };
+
+    let c = Color::from_rgb(1., 0., 0.);
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Hex)),
+        "#ff0000"
+    );
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Rgb)),
+        "rgb(255,0,0)"
+    );
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Hsl)),
+        "hsl(0,100%,50%)"
+    );
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Oklab)),
+        "oklab(0.628 0.225 0.126)"
+    );
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Oklch)),
+        "oklch(0.628 0.258 29.221)"
+    );
+
+    let c = Color::from_rgba(0., 0., 1., 0.5);
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Hex)),
+        "#0000ff80"
+    );
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Rgb)),
+        "rgba(0,0,255,0.5)"
+    );
+    assert_eq!(
+        c.to_css_string_with_options(opts(CssFormat::Hsl)),
+        "hsla(240,100%,50%,0.5)"
+    );
+
+    let c = Color::from_rgb_u8(171, 205, 239);
+    let o = CssStringOptions {
+        uppercase_hex: true,
+        always_include_alpha: true,
+        ..Default::default()
+    };
+    assert_eq!(c.to_css_string_with_options(o), "#ABCDEFFF");
+
+    let o = CssStringOptions {
+        format: CssFormat::Hsl,
+        precision: 1,
+        always_include_alpha: true,
+        ..Default::default()
+    };
+    assert_eq!(c.to_css_string_with_options(o), "hsla(210,68%,80.4%,1)");
+
+    let o = CssStringOptions {
+        format: CssFormat::Oklab,
+        precision: 0,
+        always_include_alpha: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Color::from_rgb(1., 1., 1.).to_css_string_with_options(o),
+        "oklab(1 0 0 / 1)"
+    );
+}
+
+#[cfg(feature = "terminal")]
+#[test]
+fn ansi_256() {
+    assert_eq!(Color::from_ansi_256(196).to_hex_string(), "#ff0000");
+    assert_eq!(Color::from_ansi_256(0).to_hex_string(), "#000000");
+    assert_eq!(Color::from_ansi_256(9).to_hex_string(), "#ff0000");
+    assert_eq!(Color::from_ansi_256(15).to_hex_string(), "#ffffff");
+    assert_eq!(Color::from_ansi_256(16).to_hex_string(), "#000000");
+    assert_eq!(Color::from_ansi_256(21).to_hex_string(), "#0000ff");
+    assert_eq!(Color::from_ansi_256(110).to_hex_string(), "#87afd7");
+    assert_eq!(Color::from_ansi_256(231).to_hex_string(), "#ffffff");
+    assert_eq!(Color::from_ansi_256(232).to_hex_string(), "#080808");
+    assert_eq!(Color::from_ansi_256(255).to_hex_string(), "#eeeeee");
+
+    assert_eq!(Color::from_rgb(1., 0., 0.).to_ansi_256_index(), 196);
+    assert_eq!(Color::from_rgb(0., 0., 0.).to_ansi_256_index(), 16);
+    assert_eq!(Color::from_rgb_u8(128, 128, 128).to_ansi_256_index(), 244);
+    assert_eq!(Color::from_rgb_u8(130, 170, 210).to_ansi_256_index(), 110);
+
+    // the basic colors are searched too, the color cube wins on ties
+    assert_eq!(Color::from_rgb_u8(205, 0, 0).to_ansi_256_index(), 1);
+    assert_eq!(Color::from_rgb_u8(0, 0, 238).to_ansi_256_index(), 4);
+    assert_eq!(Color::from_rgb_u8(92, 92, 250).to_ansi_256_index(), 12);
+    assert_eq!(Color::from_rgb(1., 1., 1.).to_ansi_256_index(), 231);
+
+    for i in 16..=255 {
+        assert_eq!(Color::from_ansi_256(i).to_ansi_256_index(), i);
+    }
+}
+
+#[test]
+fn convert_to() {
+    fn assert_approx(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) {
+        let d = (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs() + (a.3 - b.3).abs();
+        assert!(d < 0.002, "{:?} != {:?}", a, b);
+    }
+
+    let data = vec![
+        Color::from_rgb(1., 0., 0.),
+        Color::from_rgba(0.2, 0.7, 0.4, 0.5),
+        Color::from_rgb(0.5, 0.5, 0.5),
+    ];
+    for c in &data {
+        assert_eq!(c.convert_to(ColorSpace::Srgb), c.rgba());
+        assert_eq!(c.convert_to(ColorSpace::SrgbLinear), c.to_linear_rgba());
+        assert_eq!(c.convert_to(ColorSpace::Hsl), c.to_hsla());
+        assert_eq!(c.convert_to(ColorSpace::Hsv), c.to_hsva());
+        assert_eq!(c.convert_to(ColorSpace::Hwb), c.to_hwba());
+        assert_eq!(c.convert_to(ColorSpace::OkLab), c.to_oklaba());
+        #[cfg(feature = "lab")]
+        {
+            assert_eq!(c.convert_to(ColorSpace::Lab), c.to_lab());
+            assert_eq!(c.convert_to(ColorSpace::Lch), c.to_lch());
+        }
+    }
+
+    let red = Color::from_rgb(1., 0., 0.);
+    let white = Color::from_rgb(1., 1., 1.);
+
+    assert_approx(
+        red.convert_to(ColorSpace::OkLch),
+        (0.628, 0.2577, 29.22, 1.),
+    );
+    assert_approx(
+        white.convert_to(ColorSpace::XyzD65),
+        (0.9505, 1., 1.089, 1.),
+    );
+    assert_approx(
+        white.convert_to(ColorSpace::XyzD50),
+        (0.9642, 1., 0.8252, 1.),
+    );
+    assert_approx(
+        red.convert_to(ColorSpace::DisplayP3),
+        (0.9175, 0.2003, 0.1386, 1.),
+    );
+    assert_approx(
+        red.convert_to(ColorSpace::Rec2020),
+        (0.7920, 0.2310, 0.0738, 1.),
+    );
+    assert_approx(white.convert_to(ColorSpace::DisplayP3), (1., 1., 1., 1.));
+
+    let (h, s, l, a) = red.convert_to(ColorSpace::HslUv);
+    assert_approx(
+        (h / 100., s / 100., l / 100., a),
+        (0.12177, 1., 0.53237, 1.),
+    );
+    let (h, s, l, _) = Color::from_rgb_u8(0x33, 0x66, 0x99).convert_to(ColorSpace::HslUv);
+    assert_approx(
+        (h / 100., s / 100., l / 100., 1.),
+        (2.4694, 0.7845, 0.4201, 1.),
+    );
+    assert_approx(white.convert_to(ColorSpace::HslUv), (0., 0., 100., 1.));
+}
+
+#[test]
+fn interpolate_in() {
+    let spaces = vec![
+        ColorSpace::Srgb,
+        ColorSpace::SrgbLinear,
+        ColorSpace::Hsl,
+        ColorSpace::Hsv,
+        ColorSpace::Hwb,
+        ColorSpace::OkLab,
+        ColorSpace::OkLch,
+        #[cfg(feature = "lab")]
+        ColorSpace::Lab,
+        #[cfg(feature = "lab")]
+        ColorSpace::Lch,
+        ColorSpace::XyzD65,
+        ColorSpace::XyzD50,
+        ColorSpace::DisplayP3,
+        ColorSpace::HslUv,
+        ColorSpace::Rec2020,
+    ];
+
+    let a = Color::from_rgb(0., 1., 0.);
+    let b = Color::from_rgba(0., 0., 1., 0.5);
+
+    for space in spaces {
+        assert_eq!(
+            a.interpolate_in(space, &b, 0.0).rgba_u8(),
+            (0, 255, 0, 255),
+            "{:?}",
+            space
+        );
+        assert_eq!(
+            a.interpolate_in(space, &b, 1.0).rgba_u8(),
+            (0, 0, 255, 128),
+            "{:?}",
+            space
+        );
+        assert_eq!(a.interpolate_in(space, &b, 0.5).rgba_u8().3, 191);
+    }
+
+    assert_eq!(
+        a.interpolate_in(ColorSpace::OkLab, &b, 0.5),
+        a.interpolate_oklab(&b, 0.5)
+    );
+    assert_eq!(
+        a.interpolate_in(ColorSpace::Srgb, &b, 0.5),
+        a.interpolate_rgb(&b, 0.5)
+    );
+    assert_eq!(
+        a.interpolate_in(ColorSpace::Hsv, &b, 0.5),
+        a.interpolate_hsv(&b, 0.5)
+    );
+
+    // hue spaces use the shorter arc
+    let red = Color::from_hsl(10., 1., 0.5);
+    let magenta = Color::from_hsl(350., 1., 0.5);
+    assert_eq!(
+        red.interpolate_in(ColorSpace::Hsl, &magenta, 0.5).rgba_u8(),
+        (255, 0, 0, 255)
+    );
+    assert_eq!(
+        a.interpolate_in(ColorSpace::Hwb, &b, 0.5).rgba_u8(),
+        (0, 255, 255, 191)
+    );
+}
+
+#[test]
+fn chroma_oklab() {
+    for v in [0., 0.25, 0.5, 1.] {
+        assert!(Color::from_rgb(v, v, v).chroma_oklab() < 1e-3);
+    }
+
+    let red = Color::from_rgb(1., 0., 0.);
+    assert!((red.chroma_oklab() - 0.2577).abs() < 1e-3);
+    assert_eq!(red.chroma_oklab(), red.convert_to(ColorSpace::OkLch).1);
+
+    let muted = Color::from_rgb(0.6, 0.4, 0.4);
+    assert!(muted.chroma_oklab() < red.chroma_oklab());
+}
+
+#[test]
+fn hue_interpolation_mode() {
+    use HueInterpolationMode::*;
+
+    let hue = |c: Color| c.to_hsla().0.round();
+
+    let a = Color::from_hsl(10., 1., 0.5);
+    let b = Color::from_hsl(350., 1., 0.5);
+
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.5, Shorter)), 0.);
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.5, Longer)), 180.);
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.5, Increasing)), 180.);
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.5, Decreasing)), 0.);
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.25, Shorter)), 5.);
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.25, Longer)), 95.);
+    assert_eq!(hue(a.interpolate_hsl_with_mode(&b, 0.25, Decreasing)), 5.);
+
+    assert_eq!(hue(b.interpolate_hsl_with_mode(&a, 0.25, Increasing)), 355.);
+    assert_eq!(hue(b.interpolate_hsl_with_mode(&a, 0.25, Decreasing)), 265.);
+
+    assert_eq!(
+        a.interpolate_hsl(&b, 0.5),
+        a.interpolate_hsl_with_mode(&b, 0.5, Shorter)
+    );
+    assert_eq!(hue(a.interpolate_hsv_with_mode(&b, 0.5, Longer)), 180.);
+    assert_eq!(
+        a.interpolate_hsv_with_mode(&b, 0.5, Shorter),
+        a.interpolate_hsv(&b, 0.5)
+    );
+
+    let a = Color::from_rgb(1., 0., 0.);
+    let b = Color::from_rgb(0., 0., 1.);
+    let (_, _, h1, _) = a.convert_to(ColorSpace::OkLch);
+    let (_, _, h2, _) = b.convert_to(ColorSpace::OkLch);
+    let (_, _, h, _) = a.interpolate_oklch(&b, 0.5).convert_to(ColorSpace::OkLch);
+    let (_, _, h_long, _) = a
+        .interpolate_oklch_with_mode(&b, 0.5, Longer)
+        .convert_to(ColorSpace::OkLch);
+    assert!(h > h2 || h < h1);
+    assert!(h_long > h1 && h_long < h2);
+    assert_eq!(a.interpolate_oklch(&b, 0.0).rgba_u8(), (255, 0, 0, 255));
+    assert_eq!(a.interpolate_oklch(&b, 1.0).rgba_u8(), (0, 0, 255, 255));
+}
+
+#[test]
+fn interpolate_missing_components() {
+    let white = Color::from_rgb(1., 1., 1.);
+    let blue = Color::from_rgb(0., 0., 1.);
+
+    // the hue of an achromatic color is missing, so the hue of the other color is used
+    assert_eq!(
+        white.interpolate_in(ColorSpace::Hsl, &blue, 0.5).rgba_u8(),
+        (159, 159, 223, 255)
+    );
+    assert_eq!(
+        blue.interpolate_in(ColorSpace::Hsl, &white, 0.5).rgba_u8(),
+        (159, 159, 223, 255)
+    );
+    let (_, _, h, _) = white
+        .interpolate_in(ColorSpace::OkLch, &blue, 0.5)
+        .convert_to(ColorSpace::OkLch);
+    assert!((h - blue.convert_to(ColorSpace::OkLch).2).abs() < 0.1);
+
+    // NaN components are missing
+    let a = Color::from_rgba(f32::NAN, 0., 0., 1.);
+    let b = Color::from_rgb(1., 0., 1.);
+    assert_eq!(
+        a.interpolate_in(ColorSpace::Srgb, &b, 0.5).rgba_u8(),
+        (255, 0, 128, 255)
+    );
+    let a = Color::from_rgba(0., 0., 0., f32::NAN);
+    let b = Color::from_rgba(0., 0., 0., f32::NAN);
+    assert_eq!(
+        a.interpolate_in(ColorSpace::Srgb, &b, 0.5).rgba_u8(),
+        (0, 0, 0, 0)
+    );
+}
+
+#[cfg(feature = "named-colors")]
+#[test]
+fn close_to_named() {
+    let data = [
+        ((255, 0, 0), 0.0, "red"),
+        ((250, 5, 3), 0.05, "red"),
+        ((0, 255, 255), 0.0, "aqua"),
+        ((255, 0, 255), 0.0, "fuchsia"),
+        ((128, 128, 128), 0.0, "gray"),
+        ((102, 51, 153), 0.01, "rebeccapurple"),
+        ((1, 1, 1), 0.1, "black"),
+    ];
+
+    for ((r, g, b), threshold, name) in data {
+        let c = Color::from_rgb_u8(r, g, b);
+        let (n, named) = c.is_close_to_named(threshold).unwrap();
+        assert_eq!(n, name);
+        assert_eq!(named, csscolorparser::parse(name).unwrap());
+    }
+
+    // alpha is ignored, the named color is opaque
+    let c = Color::from_rgba_u8(255, 215, 0, 100);
+    let (n, named) = c.is_close_to_named(0.0).unwrap();
+    assert_eq!(n, "gold");
+    assert_eq!(named.a, 1.0);
+
+    assert_eq!(Color::from_rgb_u8(250, 5, 3).is_close_to_named(0.001), None);
+    assert_eq!(Color::from_rgb_u8(255, 0, 0).is_close_to_named(-1.0), None);
+}
+
+#[test]
+fn mix() {
+    let red = Color::from_rgb(1., 0., 0.);
+    let blue = Color::from_rgb(0., 0., 1.);
+
+    assert_eq!(
+        red.mix(&blue, 0.5, ColorSpace::OkLab),
+        Color::from_html("color-mix(in oklab, #f00, #00f)").unwrap()
+    );
+    assert_eq!(
+        red.mix(&blue, 0.3, ColorSpace::OkLch),
+        Color::from_html("color-mix(in oklch, #f00, #00f 30%)").unwrap()
+    );
+    assert_eq!(
+        red.mix(&blue, 0.5, ColorSpace::Srgb).rgba_u8(),
+        (128, 0, 128, 255)
+    );
+
+    // translucent colors are premultiplied, like color-mix()
+    let transparent = Color::from_rgba(0., 0., 0., 0.);
+    assert_eq!(
+        transparent.mix(&red, 0.5, ColorSpace::Srgb).rgba_u8(),
+        (255, 0, 0, 128)
+    );
+    let c = Color::from_rgba(1., 0., 0., 0.5).mix(&blue, 0.5, ColorSpace::Srgb);
+    assert_eq!(c.rgba_u8(), (85, 0, 170, 191));
+    assert_eq!(
+        c,
+        Color::from_html("color-mix(in srgb, rgb(255 0 0 / .5), #00f)").unwrap()
+    );
+
+    // fraction is clamped
+    assert_eq!(red.mix(&blue, -1.0, ColorSpace::Srgb), red);
+    assert_eq!(red.mix(&blue, 2.0, ColorSpace::Srgb), blue);
+    assert_eq!(
+        red.mix(&blue, 0.0, ColorSpace::Hsl).rgba_u8(),
+        (255, 0, 0, 255)
+    );
+    assert_eq!(
+        red.mix(&blue, 1.0, ColorSpace::Hsl).rgba_u8(),
+        (0, 0, 255, 255)
+    );
+}
+
+#[test]
+fn css_function_name() {
+    let data = [
+        ("rgb", vec![255., 102., 0.], "#ff6600"),
+        ("RGBA", vec![255., 102., 0., 0.5], "#ff660080"),
+        ("rgb", vec![300., -5., 0.], "#ff0000"),
+        ("hsl", vec![120., 1., 0.5], "#00ff00"),
+        ("hsla", vec![240., 1., 0.5, 1.], "#0000ff"),
+        ("hwb", vec![0., 0., 0.], "#ff0000"),
+        ("hwba", vec![0., 1., 0., 0.], "#ffffff00"),
+        ("hsv", vec![120., 1., 1.], "#00ff00"),
+        ("hsva", vec![0., 0., 0.5, 1.], "#808080"),
+        ("oklab", vec![1., 0., 0.], "#ffffff"),
+        ("oklch", vec![0., 0., 0.], "#000000"),
+    ];
+
+    for (name, args, hex) in data {
+        let c = Color::from_css_function_name(name, &args).unwrap();
+        assert_eq!(c.to_hex_string(), hex, "{}", name);
+    }
+
+    let red = Color::from_rgb(1., 0., 0.);
+    let (l, c, h, _) = red.convert_to(ColorSpace::OkLch);
+    let c = Color::from_css_function_name("oklch", &[l, c, h]).unwrap();
+    assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+
+    #[cfg(feature = "lab")]
+    {
+        let c = Color::from_css_function_name("lab", &[100., 0., 0.]).unwrap();
+        assert_eq!(c.rgba_u8(), (255, 255, 255, 255));
+        let c = Color::from_css_function_name("lch", &[0., 0., 90.]).unwrap();
+        assert_eq!(c.rgba_u8(), (0, 0, 0, 255));
+    }
+
+    let data = [
+        ("rgb", vec![], "Invalid rgb format."),
+        ("rgb", vec![1., 2.], "Invalid rgb format."),
+        ("hsl", vec![1., 2., 3., 4., 5.], "Invalid hsl format."),
+        ("oklch", vec![1.], "Invalid oklch format."),
+        ("cmyk", vec![0., 0., 0., 0.], "Invalid color function."),
+        ("", vec![0., 0., 0.], "Invalid color function."),
+    ];
+
+    for (name, args, msg) in data {
+        assert_eq!(
+            Color::from_css_function_name(name, &args)
+                .unwrap_err()
+                .to_string(),
+            msg
+        );
+    }
+}
+
+#[cfg(feature = "lab")]
+#[test]
+fn photoshop_lab() {
+    let data = [
+        ((0., 0., 0.), (0, 0, 0, 255)),
+        ((100., 0., 0.), (255, 255, 255, 255)),
+        ((-10., 0., 0.), (0, 0, 0, 255)),
+        ((150., 0., 0.), (255, 255, 255, 255)),
+    ];
+
+    for ((l, a, b), rgba) in data {
+        assert_eq!(Color::from_photoshop_lab(l, a, b).rgba_u8(), rgba);
+    }
+
+    // the values shown by Photoshop (D50) for sRGB colors
+    let data = [
+        ((54., 81., 70.), "#ff0000"),
+        ((88., -79., 81.), "#00ff00"),
+        ((30., 68., -112.), "#0000ff"),
+    ];
+    for ((l, a, b), hex) in data {
+        let c: Color = hex.parse().unwrap();
+        let (l2, a2, b2, _) = c.to_photoshop_lab();
+        assert_eq!((l2.round(), a2.round(), b2.round()), (l, a, b), "{}", hex);
+        let c2 = Color::from_photoshop_lab(l2, a2, b2);
+        assert_eq!(c2.to_hex_string(), hex);
+    }
+    assert_eq!(
+        Color::from_photoshop_lab(54.29, 80.8, 69.89).rgba_u8(),
+        (255, 0, 0, 255)
+    );
+    assert_eq!(
+        Color::from_photoshop_lab(50., 500., 0.),
+        Color::from_photoshop_lab(50., 127., 0.)
+    );
+    assert_eq!(
+        Color::from_photoshop_lab(50., 0., -500.),
+        Color::from_photoshop_lab(50., 0., -128.)
+    );
+
+    let c = Color::from_rgba(0., 0., 1., 0.5);
+    let (l, a, b, alpha) = c.to_photoshop_lab();
+    assert!((0.0..=100.0).contains(&l));
+    assert!((-128.0..=127.0).contains(&a));
+    assert!((-128.0..=127.0).contains(&b));
+    assert_eq!(alpha, 0.5);
+    assert_eq!(
+        Color::from_photoshop_lab(l, a, b).rgba_u8(),
+        (0, 0, 255, 255)
+    );
+}
+
+#[test]
+fn display_p3_string() {
+    let (r, g, b, _) = Color::from_rgb(1., 0., 0.).convert_to(ColorSpace::DisplayP3);
+    assert!((r - 0.9175).abs() < 1e-4);
+    assert!((g - 0.2003).abs() < 1e-4);
+    assert!((b - 0.1386).abs() < 1e-4);
+
+    let data = [
+        (
+            Color::from_rgb(1., 0., 0.),
+            "color(display-p3 0.9175 0.2003 0.1386)",
+        ),
+        (Color::from_rgb(0., 0., 0.), "color(display-p3 0 0 0)"),
+        (Color::from_rgb(1., 1., 1.), "color(display-p3 1 1 1)"),
+        (
+            Color::from_rgba(0., 0., 0., 0.5),
+            "color(display-p3 0 0 0 / 0.5)",
+        ),
+    ];
+
+    for (c, s) in data {
+        assert_eq!(c.to_display_p3_string(), s);
+        assert_eq!(
+            csscolorparser::parse(s).unwrap().rgba_u8(),
+            c.rgba_u8(),
+            "{}",
+            s
+        );
+    }
+
+    // outside of sRGB gamut, P3 components are in [0..1]
+    let c = csscolorparser::parse("color(display-p3 0 1 0)").unwrap();
+    assert!(c.r < 0.0);
+    assert_eq!(c.to_display_p3_string(), "color(display-p3 0 1 0)");
+
+    // outside of P3 gamut, components are printed as-is
+    let c = csscolorparser::parse("color(rec2020 0 1 0)").unwrap();
+    assert!(c.to_display_p3_string().starts_with("color(display-p3 -"));
+}
+
+#[cfg(feature = "wide-gamut")]
+#[test]
+fn a98_rgb() {
+    let data = [
+        Color::from_rgb(1.0, 0.0, 0.0),
+        Color::from_rgb(0.2, 0.6, 0.4),
+        Color::from_rgba(0.0, 0.0, 1.0, 0.5),
+    ];
+    for c in data {
+        let (r, g, b, a) = c.to_a98_rgb();
+        assert_eq!(Color::from_a98_rgba(r, g, b, a).rgba_u8(), c.rgba_u8());
+    }
+
+    assert_eq!(
+        Color::from_a98_rgb(1.0, 1.0, 1.0).rgba_u8(),
+        (255, 255, 255, 255)
+    );
+    assert_eq!(Color::from_a98_rgb(0.0, 0.0, 0.0).rgba_u8(), (0, 0, 0, 255));
+
+    // the Adobe RGB green primary is outside of the sRGB gamut
+    let c = Color::from_a98_rgb(0.0, 1.0, 0.0);
+    assert!(c.r < 0.0);
+    assert_eq!(Color::convert_to(&c, ColorSpace::A98Rgb).1.round(), 1.0);
+}
+
+#[test]
+fn nearest_in_palette_oklab() {
+    let palette = [
+        Color::from_rgb(1., 0., 0.),
+        Color::from_rgb(0., 1., 0.),
+        Color::from_rgb(0., 0., 1.),
+        Color::BLACK,
+        Color::WHITE,
+    ];
+
+    let data = [
+        ((250, 10, 5), 0),
+        ((10, 200, 30), 1),
+        ((20, 20, 230), 2),
+        ((30, 30, 30), 3),
+        ((240, 240, 250), 4),
+    ];
+
+    for ((r, g, b), i) in data {
+        let c = Color::from_rgb_u8(r, g, b);
+        let (idx, nearest) = c.nearest_in_palette_oklab(&palette).unwrap();
+        assert_eq!(idx, i);
+        assert!(std::ptr::eq(nearest, &palette[i]));
+    }
+
+    // alpha is ignored
+    let c = Color::from_rgba(0., 0., 1., 0.);
+    assert_eq!(c.nearest_in_palette_oklab(&palette).unwrap().0, 2);
+
+    // first entry wins on ties
+    let palette = [Color::WHITE, Color::BLACK, Color::WHITE];
+    assert_eq!(
+        Color::WHITE.nearest_in_palette_oklab(&palette).unwrap().0,
+        0
+    );
+
+    assert_eq!(Color::WHITE.nearest_in_palette_oklab(&[]), None);
+}
+
+#[test]
+fn srgb_linear_string() {
+    let data = [
+        (Color::from_rgb(1., 0., 0.), "color(srgb-linear 1 0 0)"),
+        (Color::from_rgb(0., 0., 0.), "color(srgb-linear 0 0 0)"),
+        (Color::from_rgb(1., 1., 1.), "color(srgb-linear 1 1 1)"),
+        (
+            Color::from_rgba(0., 1., 0., 0.25),
+            "color(srgb-linear 0 1 0 / 0.25)",
+        ),
+        (
+            Color::from_rgb_u8(128, 128, 128),
+            "color(srgb-linear 0.2159 0.2159 0.2159)",
+        ),
+    ];
+
+    for (c, s) in data {
+        assert_eq!(c.to_srgb_linear_string(), s);
+        assert_eq!(
+            csscolorparser::parse(s).unwrap().rgba_u8(),
+            c.rgba_u8(),
+            "{}",
+            s
+        );
+    }
+
+    let (r, _, _, _) = Color::from_rgb_u8(128, 128, 128).to_linear_rgba();
+    assert!((r - 0.2158).abs() < 1e-3);
+
+    // HDR values are not clamped
+    let c = Color::from_rgb(2., 0., 0.);
+    assert!(c
+        .to_srgb_linear_string()
+        .starts_with("color(srgb-linear 4."));
+}
+
+#[test]
+fn is_achromatic() {
+    for s in ["#808080", "#000000", "#ffffff", "#80808000"] {
+        let c = csscolorparser::parse(s).unwrap();
+        assert!(c.is_achromatic(0.0), "{}", s);
+    }
+
+    for s in ["#ff0000", "#ff8080", "#808081"] {
+        let c = csscolorparser::parse(s).unwrap();
+        assert!(!c.is_achromatic(1e-3), "{}", s);
+    }
+
+    let c = Color::from_rgb(0.5, 0.505, 0.495);
+    assert!(!c.is_achromatic(0.005));
+    assert!(c.is_achromatic(0.011));
+    assert!(csscolorparser::parse("#808081")
+        .unwrap()
+        .is_achromatic(1. / 255.));
+}
+
+#[test]
+fn complementary() {
+    let data = [
+        ("#ff0000", "#00ffff"),
+        ("#00ff00", "#ff00ff"),
+        ("#0000ff", "#ffff00"),
+        ("#ff8000", "#007fff"),
+        ("#336699", "#996633"),
+        ("#000000", "#000000"),
+        ("#ffffff", "#ffffff"),
+        ("#808080", "#808080"),
+        ("#ff000080", "#00ffff80"),
+    ];
+    for (s, expected) in data {
+        let c: Color = s.parse().unwrap();
+        assert_eq!(c.complementary().to_hex_string(), expected, "{}", s);
+        assert_eq!(
+            c.complementary().complementary().to_hex_string(),
+            s,
+            "{}",
+            s
+        );
+    }
+}
+
+#[test]
+fn triadic() {
+    let data = [
+        ("#ff0000", ["#00ff00", "#0000ff"]),
+        ("#00ff00", ["#0000ff", "#ff0000"]),
+        ("#ffff00", ["#00ffff", "#ff00ff"]),
+        ("#808080", ["#808080", "#808080"]),
+        ("#ff000080", ["#00ff0080", "#0000ff80"]),
+    ];
+    for (s, expected) in data {
+        let c: Color = s.parse().unwrap();
+        let [c1, c2] = c.triadic();
+        assert_eq!([c1.to_hex_string(), c2.to_hex_string()], expected, "{}", s);
+    }
+
+    let c = Color::from_hsla(50.0, 0.6, 0.4, 1.0);
+    let [c1, c2] = c.triadic();
+    let (h0, s0, l0, _) = c.to_hsla();
+    for (c, dh) in [(c1, 120.0), (c2, 240.0)] {
+        let (h, s, l, _) = c.to_hsla();
+        assert!((h - (h0 + dh)).abs() < 1e-3, "{} {}", h, h0);
+        assert!((s - s0).abs() < 1e-5);
+        assert!((l - l0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn tetradic() {
+    let data = [
+        ("#ff0000", ["#80ff00", "#00ffff", "#8000ff"]),
+        ("#0000ff", ["#ff0080", "#ffff00", "#00ff80"]),
+        ("#808080", ["#808080", "#808080", "#808080"]),
+        ("#ff000080", ["#80ff0080", "#00ffff80", "#8000ff80"]),
+    ];
+    for (s, expected) in data {
+        let c: Color = s.parse().unwrap();
+        let hex: Vec<_> = c.tetradic().iter().map(|c| c.to_hex_string()).collect();
+        assert_eq!(hex, expected, "{}", s);
+    }
+
+    let c = Color::from_hsla(300.0, 0.4, 0.6, 1.0);
+    let [c1, c2, c3] = c.tetradic();
+    for (c, h) in [(c1, 30.0), (c2, 120.0), (c3, 210.0)] {
+        let (h2, s, l, _) = c.to_hsla();
+        assert!((h2 - h).abs() < 1e-3, "{} {}", h2, h);
+        assert!((s - 0.4).abs() < 1e-5);
+        assert!((l - 0.6).abs() < 1e-5);
+    }
+
+    assert_eq!(
+        Color::from_rgb(1.0, 0.0, 0.0).tetradic()[1],
+        Color::from_rgb(1.0, 0.0, 0.0).complementary()
+    );
+}
+
+#[test]
+fn analogous() {
+    let data = [
+        ("#ff0000", 30.0, ["#ff0080", "#ff8000"]),
+        ("#ff0000", 120.0, ["#0000ff", "#00ff00"]),
+        ("#00ff00", 60.0, ["#ffff00", "#00ffff"]),
+        ("#808080", 30.0, ["#808080", "#808080"]),
+        // limiting cases
+        ("#ff0000", 0.0, ["#ff0000", "#ff0000"]),
+        ("#ff0000", -30.0, ["#ff0000", "#ff0000"]),
+        ("#ff0000", 180.0, ["#00ffff", "#00ffff"]),
+        ("#ff0000", 270.0, ["#00ffff", "#00ffff"]),
+    ];
+    for (s, spread, expected) in data {
+        let c: Color = s.parse().unwrap();
+        let [c1, c2] = c.analogous(spread);
+        assert_eq!(
+            [c1.to_hex_string(), c2.to_hex_string()],
+            expected,
+            "{} {}",
+            s,
+            spread
+        );
+    }
+
+    let c = Color::from_hsla(200.0, 0.7, 0.3, 0.5);
+    let [c1, c2] = c.analogous(25.0);
+    for (c, h) in [(c1, 175.0), (c2, 225.0)] {
+        let (h2, s, l, a) = c.to_hsla();
+        assert!((h2 - h).abs() < 1e-3, "{} {}", h2, h);
+        assert!((s - 0.7).abs() < 1e-5);
+        assert!((l - 0.3).abs() < 1e-5);
+        assert_eq!(a, 0.5);
+    }
+}
+
+#[test]
+fn split_complementary() {
+    let data = [
+        ("#ff0000", 30.0, ["#00ff80", "#0080ff"]),
+        ("#0000ff", 60.0, ["#ff0000", "#00ff00"]),
+        ("#808080", 30.0, ["#808080", "#808080"]),
+        ("#ff000080", 30.0, ["#00ff8080", "#0080ff80"]),
+        // limiting cases
+        ("#ff0000", 0.0, ["#00ffff", "#00ffff"]),
+        ("#ff0000", 180.0, ["#ff0000", "#ff0000"]),
+    ];
+    for (s, spread, expected) in data {
+        let c: Color = s.parse().unwrap();
+        let [c1, c2] = c.split_complementary(spread);
+        assert_eq!(
+            [c1.to_hex_string(), c2.to_hex_string()],
+            expected,
+            "{} {}",
+            s,
+            spread
+        );
+    }
+
+    for s in ["#ff0000", "#336699", "#ff8000"] {
+        let c: Color = s.parse().unwrap();
+        let complement = c.complementary().to_hex_string();
+        for spread in [15.0, 30.0, 45.0] {
+            let [c1, c2] = c.split_complementary(spread);
+            assert_ne!(c1.to_hex_string(), complement);
+            assert_ne!(c2.to_hex_string(), complement);
+            assert_eq!(
+                [c1.to_hex_string(), c2.to_hex_string()],
+                c.complementary()
+                    .analogous(spread)
+                    .map(|c| c.to_hex_string())
+            );
+        }
+    }
+}
+
+#[test]
+fn describe() {
+    let data = [
+        ("#ff0000", "vivid red"),
+        ("#cccccc", "light gray"),
+        ("#000000", "black"),
+        ("#ffffff", "white"),
+        ("#808080", "gray"),
+        ("#333333", "dark gray"),
+        ("#1a1a1a", "very dark gray"),
+        ("#f0f0f0", "very light gray"),
+        ("#800000", "dark vivid red"),
+        ("#ffa500", "vivid orange"),
+        ("#ffff00", "vivid yellow"),
+        ("#00ff00", "vivid green"),
+        ("#228b22", "dark green"),
+        ("#00ffff", "vivid cyan"),
+        ("#87ceeb", "light blue"),
+        ("#0000ff", "vivid blue"),
+        ("#000080", "dark vivid blue"),
+        ("#8a2be2", "violet"),
+        ("#ff00ff", "vivid magenta"),
+        ("#ff1493", "vivid magenta"),
+        ("#ffc0cb", "very light vivid red"),
+        ("#8b7d6b", "muted orange"),
+        ("#4682b4", "blue"),
+        ("#2f4f4f", "dark muted cyan"),
+        ("#ff000000", "vivid red"),
+    ];
+    for (s, expected) in data {
+        let c: Color = s.parse().unwrap();
+        assert_eq!(c.describe(), expected, "{}", s);
+    }
+}
+
+#[test]
+fn set_oklch_components() {
+    for s in ["#ff0000", "#3366cc80", "#c0ffee", "#804000"] {
+        let c: Color = s.parse().unwrap();
+        let (l, ch, h, alpha) = c.convert_to(ColorSpace::OkLch);
+
+        let (l2, ch2, h2, alpha2) = c.set_oklch_lightness(0.5).convert_to(ColorSpace::OkLch);
+        assert!((l2 - 0.5).abs() < 1e-3, "{}", s);
+        assert!((ch2 - ch).abs() < 1e-3, "{}", s);
+        assert!((h2 - h).abs() < 0.1, "{}", s);
+        assert_eq!(alpha2, alpha);
+
+        let (l2, ch2, h2, _) = c.set_oklch_chroma(0.05).convert_to(ColorSpace::OkLch);
+        assert!((l2 - l).abs() < 1e-3, "{}", s);
+        assert!((ch2 - 0.05).abs() < 1e-3, "{}", s);
+        assert!((h2 - h).abs() < 0.1, "{}", s);
+
+        let (l2, ch2, h2, _) = c.set_oklch_hue(200.0).convert_to(ColorSpace::OkLch);
+        assert!((l2 - l).abs() < 1e-3, "{}", s);
+        assert!((ch2 - ch).abs() < 1e-3, "{}", s);
+        assert!((h2 - 200.0).abs() < 0.1, "{}", s);
+    }
+
+    let c = Color::from_rgb(1.0, 0.0, 0.0);
+    assert_eq!(
+        c.set_oklch_lightness(1.5).rgba_u8(),
+        c.set_oklch_lightness(1.0).rgba_u8()
+    );
+    assert_eq!(
+        c.set_oklch_lightness(-1.0).rgba_u8(),
+        c.set_oklch_lightness(0.0).rgba_u8()
+    );
+    assert!(c.set_oklch_chroma(-1.0).chroma_oklab() < 1e-4);
+    assert_eq!(
+        c.set_oklch_hue(400.0).rgba_u8(),
+        c.set_oklch_hue(40.0).rgba_u8()
+    );
+    assert_eq!(
+        c.set_oklch_hue(-90.0).rgba_u8(),
+        c.set_oklch_hue(270.0).rgba_u8()
+    );
+}
+
+#[test]
+fn set_hsl_components() {
+    let red = Color::from_rgb(1.0, 0.0, 0.0);
+    assert_eq!(red.set_hsl_hue(240.0).to_hex_string(), "#0000ff");
+    assert_eq!(red.set_hsl_hue(120.0).to_hex_string(), "#00ff00");
+    assert_eq!(red.set_hsl_hue(-120.0).to_hex_string(), "#0000ff");
+    assert_eq!(red.set_hsl_hue(480.0).to_hex_string(), "#00ff00");
+    assert_eq!(red.set_hsl_lightness(0.2).to_hex_string(), "#660000");
+    assert_eq!(red.set_hsl_lightness(2.0).to_hex_string(), "#ffffff");
+    assert_eq!(red.set_hsl_lightness(-1.0).to_hex_string(), "#000000");
+    assert_eq!(red.set_hsl_saturation(0.5).to_hex_string(), "#bf4040");
+    assert_eq!(
+        red.set_hsl_saturation(2.0).rgba_u8(),
+        red.set_hsl_saturation(1.0).rgba_u8()
+    );
+
+    for s in ["#ff0000", "#3366cc80", "#c0ffee", "#804000", "#808080"] {
+        let c: Color = s.parse().unwrap();
+        let (h, sat, l, a) = c.to_hsla();
+
+        let c2 = c.set_hsl_saturation(0.0);
+        assert!(c2.is_achromatic(1e-6), "{}", s);
+        assert_eq!(c2.a, a);
+
+        let (h2, sat2, l2, a2) = c.set_hsl_lightness(0.3).to_hsla();
+        assert!((h2 - h).abs() < 0.1, "{}", s);
+        assert!((sat2 - sat).abs() < 1e-3, "{}", s);
+        assert!((l2 - 0.3).abs() < 1e-3, "{}", s);
+        assert_eq!(a2, a);
+
+        let (_, sat2, l2, _) = c.set_hsl_hue(200.0).to_hsla();
+        assert!((sat2 - sat).abs() < 1e-3, "{}", s);
+        assert!((l2 - l).abs() < 1e-3, "{}", s);
+    }
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let in_range = |v: f32| (0.0..1.0).contains(&v);
+
+    let colors: Vec<Color> = (0..1000).map(|_| rng.gen()).collect();
+    for c in &colors {
+        assert!(in_range(c.r) && in_range(c.g) && in_range(c.b));
+        assert_eq!(c.a, 1.0);
+    }
+    let mean = colors.iter().map(|c| c.r + c.g + c.b).sum::<f32>() / 3000.0;
+    assert!((mean - 0.5).abs() < 0.05);
+
+    let colors: Vec<Color> = (0..1000)
+        .map(|_| Color::random_with_alpha(&mut rng))
+        .collect();
+    for c in &colors {
+        assert!(in_range(c.r) && in_range(c.g) && in_range(c.b) && in_range(c.a));
+    }
+    let mean = colors.iter().map(|c| c.a).sum::<f32>() / 1000.0;
+    assert!((mean - 0.5).abs() < 0.05);
+
+    let mut rng = rand::thread_rng();
+    let a = Color::random(&mut rng);
+    let b = Color::random(&mut rng);
+    assert_ne!(a, b);
+    assert_eq!(a.a, 1.0);
+
+    let mut rng1 = StdRng::seed_from_u64(7);
+    let mut rng2 = StdRng::seed_from_u64(7);
+    assert_eq!(Color::random(&mut rng1), rng2.gen::<Color>());
+}
+
+#[test]
+fn oklch_hue_category() {
+    let data = [
+        ("#f00", "red"),
+        ("#ff8000", "orange"),
+        ("#ff0", "yellow"),
+        ("#80ff00", "chartreuse"),
+        ("#0f0", "green"),
+        ("#00ff80", "teal"),
+        ("#0ff", "cyan"),
+        ("#0080ff", "azure"),
+        ("#00f", "blue"),
+        ("#8000ff", "violet"),
+        ("#f0f", "magenta"),
+        ("#ff0080", "rose"),
+        ("#800000", "red"),
+        ("#ffa500", "orange"),
+        ("#008080", "cyan"),
+        ("#663399", "violet"),
+        ("#ffc0cb", "rose"),
+    ];
+
+    for (s, category) in data {
+        let c = csscolorparser::parse(s).unwrap();
+        assert_eq!(c.oklch_hue_category(), category, "{}", s);
+    }
+}
+
+#[test]
+fn alpha_predicates() {
+    let data = [
+        (0.0, false, true, false),
+        (f32::EPSILON, false, true, false),
+        (0.001, false, false, true),
+        (0.5, false, false, true),
+        (0.999, false, false, true),
+        (1.0 - f32::EPSILON, true, false, false),
+        (1.0, true, false, false),
+    ];
+
+    for (a, opaque, transparent, translucent) in data {
+        let c = Color::from_rgba(1., 0., 0., a);
+        assert_eq!(c.is_opaque(), opaque, "{}", a);
+        assert_eq!(c.is_transparent(), transparent, "{}", a);
+        assert_eq!(c.is_translucent(), translucent, "{}", a);
+    }
+
+    assert!(csscolorparser::parse("#f00").unwrap().is_opaque());
+    assert!(csscolorparser::parse("transparent")
+        .unwrap()
+        .is_transparent());
+    assert!(csscolorparser::parse("#f008").unwrap().is_translucent());
+}
+
+#[test]
+fn luv() {
+    let c = Color::from_luv(100., 0., 0., 1.);
+    assert_eq!(c.rgba_u8(), (255, 255, 255, 255));
+    assert_eq!(Color::from_luv(0., 0., 0., 1.), Color::BLACK);
+
+    let data = [
+        ("#ff0000", (53.24, 175.01, 37.76)),
+        ("#00ff00", (87.73, -83.07, 107.4)),
+        ("#0000ff", (32.3, -9.4, -130.34)),
+        ("#ffffff", (100., 0., 0.)),
+        ("#000000", (0., 0., 0.)),
+    ];
+
+    for (s, (l, u, v)) in data {
+        let c = csscolorparser::parse(s).unwrap();
+        let (l2, u2, v2, a) = c.to_luv();
+        assert!((l - l2).abs() < 0.05, "{} {}", s, l2);
+        assert!((u - u2).abs() < 0.05, "{} {}", s, u2);
+        assert!((v - v2).abs() < 0.05, "{} {}", s, v2);
+        assert_eq!(a, 1.);
+        assert_eq!(Color::from_luv(l2, u2, v2, a).rgba_u8(), c.rgba_u8());
+    }
+
+    let c = Color::from_rgba(0.2, 0.4, 0.6, 0.5);
+    let (l, u, v, a) = c.to_luv();
+    assert_eq!(a, 0.5);
+    assert_eq!(Color::from_luv(l, u, v, a).rgba_u8(), c.rgba_u8());
+}
+
+#[test]
+fn premultiplied_alpha() {
+    let data = [
+        ((0.5, 0.25, 0.0, 0.5), (1.0, 0.5, 0.0, 0.5)),
+        ((1.0, 1.0, 1.0, 1.0), (1.0, 1.0, 1.0, 1.0)),
+        ((0.2, 0.0, 0.1, 0.4), (0.5, 0.0, 0.25, 0.4)),
+        ((0.0, 0.0, 0.0, 0.0), (0.0, 0.0, 0.0, 0.0)),
+        ((0.3, 0.3, 0.3, 0.0), (0.0, 0.0, 0.0, 0.0)),
+        ((0.3, 0.3, 0.3, -1.0), (0.0, 0.0, 0.0, 0.0)),
+    ];
+
+    for ((r, g, b, a), rgba) in data {
+        let c = Color::from_rgba_with_premultiplied_alpha(r, g, b, a);
+        assert_eq!(c.rgba(), rgba);
+    }
+}
+
+#[test]
+fn srgb_transfer_functions() {
+    use csscolorparser::{srgb_compression, srgb_expansion};
+
+    assert_eq!(srgb_compression(0.0), 0.0);
+    assert!((srgb_compression(1.0) - 1.0).abs() < 1e-6);
+    assert_eq!(srgb_expansion(0.0), 0.0);
+    assert!((srgb_expansion(1.0) - 1.0).abs() < 1e-6);
+
+    // linear segment
+    assert_eq!(srgb_compression(0.001), 0.001 * 12.92);
+    assert_eq!(srgb_expansion(0.04), 0.04 / 12.92);
+
+    for i in 0..=255 {
+        let x = i as f32 / 255.0;
+        assert!((srgb_compression(srgb_expansion(x)) - x).abs() < 1e-5);
+    }
+
+    let c = Color::from_rgb(0.1, 0.5, 0.9);
+    let (r, g, b, _) = c.to_linear_rgba();
+    assert_eq!(
+        (r, g, b),
+        (
+            srgb_expansion(0.1),
+            srgb_expansion(0.5),
+            srgb_expansion(0.9)
+        )
+    );
+    assert_eq!(
+        Color::from_linear_rgb(r, g, b).rgba(),
+        (
+            srgb_compression(r),
+            srgb_compression(g),
+            srgb_compression(b),
+            1.
+        )
+    );
+}
+
+#[test]
+fn adjust_lightness_oklab() {
+    let red = Color::from_rgb(1., 0., 0.);
+    let (l, a, b, _) = red.to_oklaba();
+
+    let c = red.adjust_lightness_oklab(0.2);
+    let (l2, a2, b2, alpha) = c.to_oklaba();
+    assert!((l2 - (l + 0.2)).abs() < 1e-3);
+    assert!((a2 - a).abs() < 1e-3);
+    assert!((b2 - b).abs() < 1e-3);
+    assert_eq!(alpha, 1.);
+
+    // HSL lightening desaturates towards white and shifts the Oklch hue
+    let (h, s, hl, _) = red.to_hsla();
+    let hsl = Color::from_hsla(h, s, hl + 0.2, 1.);
+    let hue = |c: &Color| c.convert_to(ColorSpace::OkLch).2;
+    assert!((hue(&c) - hue(&red)).abs() < 0.1);
+    assert!((hue(&hsl) - hue(&red)).abs() > 1.0);
+    assert!(c.chroma_oklab() > hsl.chroma_oklab());
+
+    // lightness is clamped
+    let (l, _, _, _) = red.adjust_lightness_oklab(5.).to_oklaba();
+    assert!((l - 1.).abs() < 1e-4);
+    assert_eq!(
+        Color::WHITE.adjust_lightness_oklab(-2.).rgba_u8(),
+        (0, 0, 0, 255)
+    );
+
+    assert_eq!(red.adjust_lightness_oklab(0.).rgba_u8(), (255, 0, 0, 255));
+    assert_eq!(
+        Color::from_rgba(0., 0., 1., 0.5)
+            .adjust_lightness_oklab(0.1)
+            .a,
+        0.5
+    );
+}
+
+#[test]
+fn scale_chroma_oklch() {
+    let colors = [
+        Color::from_rgb(1., 0., 0.),
+        Color::from_rgb(0.2, 0.6, 0.3),
+        Color::from_rgba(0.1, 0.2, 0.9, 0.5),
+    ];
+
+    for c in colors {
+        let (l, _, _, alpha) = c.to_oklaba();
+        let (_, chroma, hue, _) = c.convert_to(ColorSpace::OkLch);
+
+        // gray with the same lightness
+        let gray = c.scale_chroma_oklch(0.);
+        let (l2, _, _, alpha2) = gray.to_oklaba();
+        assert!(gray.chroma_oklab() < 1e-3);
+        assert!(gray.is_achromatic(1e-3));
+        assert!((l - l2).abs() < 1e-3);
+        assert_eq!(alpha, alpha2);
+
+        // negative factor is clamped
+        assert_eq!(c.scale_chroma_oklch(-1.), gray);
+
+        let half = c.scale_chroma_oklch(0.5);
+        let (l3, chroma3, hue3, _) = half.convert_to(ColorSpace::OkLch);
+        assert!((l - l3).abs() < 1e-3);
+        assert!((chroma / 2. - chroma3).abs() < 1e-3);
+        assert!((hue - hue3).abs() < 0.1);
+
+        let same = c.scale_chroma_oklch(1.);
+        assert!((same.r - c.r).abs() < 1e-4);
+        assert!((same.g - c.g).abs() < 1e-4);
+        assert!((same.b - c.b).abs() < 1e-4);
+    }
+
+    let c = Color::from_rgb(0.6, 0.5, 0.5).scale_chroma_oklch(2.);
+    assert!(c.chroma_oklab() > Color::from_rgb(0.6, 0.5, 0.5).chroma_oklab());
+}
+
+#[test]
+fn contrast() {
+    assert_eq!(Color::BLACK.relative_luminance(), 0.);
+    assert!((Color::WHITE.relative_luminance() - 1.).abs() < 1e-6);
+    assert!((Color::BLACK.contrast_ratio(&Color::WHITE) - 21.).abs() < 1e-4);
+    assert!((Color::WHITE.contrast_ratio(&Color::BLACK) - 21.).abs() < 1e-4);
+    assert_eq!(Color::WHITE.contrast_ratio(&Color::WHITE), 1.);
+
+    let red = Color::from_rgb(1., 0., 0.);
+    assert!((red.relative_luminance() - 0.2126).abs() < 1e-6);
+    assert!((red.contrast_ratio(&Color::WHITE) - 3.998).abs() < 1e-3);
+    let gray = Color::from_rgb_u8(0x76, 0x76, 0x76);
+    assert!((gray.contrast_ratio(&Color::WHITE) - 4.54).abs() < 0.01);
+}
+
+#[test]
+fn wcag_compliant() {
+    let white = Color::WHITE;
+    let gray = |v| Color::from_rgb_u8(v, v, v);
+
+    // (gray level, contrast ratio on white): AA normal, AA large, AAA normal, AAA large
+    let data = [
+        (0x00, [true, true, true, true]),     // 21:1
+        (0x59, [true, true, true, true]),     // 7.005:1
+        (0x5a, [true, true, false, true]),    // 6.897:1
+        (0x76, [true, true, false, true]),    // 4.542:1
+        (0x77, [false, true, false, false]),  // 4.478:1
+        (0x94, [false, true, false, false]),  // 3.033:1
+        (0x95, [false, false, false, false]), // 2.995:1
+        (0xff, [false, false, false, false]), // 1:1
+    ];
+    for (v, [aa, aa_large, aaa, aaa_large]) in data {
+        let c = gray(v);
+        assert_eq!(c.wcag_aa_compliant(&white, false), aa, "{:x}", v);
+        assert_eq!(c.wcag_aa_compliant(&white, true), aa_large, "{:x}", v);
+        assert_eq!(c.wcag_aaa_compliant(&white, false), aaa, "{:x}", v);
+        assert_eq!(c.wcag_aaa_compliant(&white, true), aaa_large, "{:x}", v);
+
+        // symmetric
+        assert_eq!(white.wcag_aa_compliant(&c, false), aa);
+        assert_eq!(white.wcag_aaa_compliant(&c, true), aaa_large);
+    }
+}
+
+#[test]
+fn oklch_lightness_adjust_to_contrast() {
+    let bg = [
+        Color::WHITE,
+        Color::BLACK,
+        Color::from_rgb(0.9, 0.9, 0.8),
+        Color::from_rgb(0.1, 0.1, 0.3),
+    ];
+    let fg = [
+        Color::from_rgb(0.6, 0.6, 1.),
+        Color::from_rgb(1., 0., 0.),
+        Color::from_rgb(0.5, 0.5, 0.5),
+        Color::from_rgb(0.2, 0.7, 0.3),
+    ];
+
+    for background in &bg {
+        for c in &fg {
+            for target in [3., 4.5, 7.] {
+                let before = c.contrast_ratio(background);
+                let adjusted = c
+                    .oklch_lightness_adjust_to_contrast(background, target)
+                    .unwrap();
+                let after = adjusted.contrast_ratio(background);
+                assert!(after >= target);
+
+                if before >= target {
+                    assert_eq!(&adjusted, c);
+                } else {
+                    // stops close to the target
+                    assert!(after < target + 0.05, "{} {}", after, target);
+
+                    // hue is kept
+                    let hue = |c: &Color| c.convert_to(ColorSpace::OkLch).2;
+                    if c.chroma_oklab() > 0.05 && adjusted.chroma_oklab() > 0.05 {
+                        assert!((hue(c) - hue(&adjusted)).abs() < 5.);
+                    }
+                }
+            }
+        }
+    }
+
+    let mid = Color::from_rgb_u8(0x77, 0x77, 0x77);
+    assert_eq!(
+        Color::from_rgb(0.5, 0.5, 0.5).oklch_lightness_adjust_to_contrast(&mid, 10.),
+        None
+    );
+    assert_eq!(
+        Color::from_rgb(0.6, 0.6, 1.).oklch_lightness_adjust_to_contrast(&Color::WHITE, 22.),
+        None
+    );
+}
+
+#[test]
+fn interpolate_cubic() {
+    let a = Color::from_rgba(0.0, 1.0, 0.0, 1.0);
+    let b = Color::from_rgba(0.0, 0.0, 1.0, 0.5);
+
+    // evenly spaced control points are linear
+    for i in 0..=20 {
+        let t = i as f32 / 20.0;
+        let c1 = a.interpolate_cubic(&b, t, 1.0 / 3.0, 2.0 / 3.0);
+        let c2 = a.interpolate_rgb(&b, t);
+        for (x, y) in c1.to_array().iter().zip(c2.to_array()) {
+            assert!((x - y).abs() < 1e-6, "{}", t);
+        }
+    }
+
+    // the end points are fixed
+    for (p1, p2) in [(0.0, 0.0), (1.0, 1.0), (0.42, 1.0), (-0.5, 1.5)] {
+        assert_eq!(a.interpolate_cubic(&b, 0.0, p1, p2), a);
+        assert_eq!(a.interpolate_cubic(&b, 1.0, p1, p2), b);
+    }
+
+    let black = Color::BLACK;
+    let white = Color::WHITE;
+    let data = [
+        (0.5, 0.0, 0.5, 80),   // ease in
+        (0.5, 0.5, 1.0, 175),  // ease out
+        (0.5, 0.0, 1.0, 128),  // ease in-out
+        (0.25, 0.0, 1.0, 40),  // ease in-out, slow start
+        (0.75, 0.0, 1.0, 215), // ease in-out, slow end
+        (0.5, 0.5, 0.5, 128),
+    ];
+    for (t, p1, p2, v) in data {
+        let c = black.interpolate_cubic(&white, t, p1, p2);
+        assert_eq!(c.rgba_u8(), (v, v, v, 255), "{} {} {}", t, p1, p2);
+    }
+
+    // overshoot
+    let c = black.interpolate_cubic(&white, 0.5, -1.0, 0.0);
+    assert!(c.r < 0.0);
+}
+
+#[test]
+fn interpolate_many() {
+    let red = Color::from_rgb(1., 0., 0.);
+    let lime = Color::from_rgb(0., 1., 0.);
+    let blue = Color::from_rgb(0., 0., 1.);
+
+    let stops = [(0., red.clone()), (0.5, lime.clone()), (1., blue.clone())];
+
+    let data = [
+        (-1., (1., 0., 0., 1.)),
+        (0., (1., 0., 0., 1.)),
+        (0.25, (0.5, 0.5, 0., 1.)),
+        (0.5, (0., 1., 0., 1.)),
+        (0.75, (0., 0.5, 0.5, 1.)),
+        (1., (0., 0., 1., 1.)),
+        (2., (0., 0., 1., 1.)),
+    ];
+
+    for (t, rgba) in data {
+        assert_eq!(Color::interpolate_many(&stops, t).rgba(), rgba, "{}", t);
+    }
+
+    // two stops match interpolate_rgb
+    let stops = [(0., red.clone()), (1., blue.clone())];
+    for i in 0..=10 {
+        let t = i as f32 / 10.;
+        assert_eq!(
+            Color::interpolate_many(&stops, t),
+            red.interpolate_rgb(&blue, t)
+        );
+    }
+
+    // single stop, hard stop, positions outside of [0..1]
+    assert_eq!(Color::interpolate_many(&[(0.5, lime.clone())], 0.), lime);
+    let stops = [
+        (0., red.clone()),
+        (0.5, red.clone()),
+        (0.5, blue.clone()),
+        (1., blue.clone()),
+    ];
+    assert_eq!(Color::interpolate_many(&stops, 0.49), red);
+    assert_eq!(Color::interpolate_many(&stops, 0.5), blue);
+    let stops = [(-1., red.clone()), (3., blue.clone())];
+    assert_eq!(
+        Color::interpolate_many(&stops, 1.),
+        red.interpolate_rgb(&blue, 0.5)
+    );
+}
+
+#[test]
+#[should_panic]
+fn interpolate_many_empty() {
+    Color::interpolate_many(&[], 0.5);
+}
+
+#[test]
+#[should_panic]
+fn interpolate_many_unsorted() {
+    let stops = [(1., Color::BLACK), (0., Color::WHITE)];
+    Color::interpolate_many(&stops, 0.5);
+}
+
+#[test]
+fn array() {
+    let arr = [0.1, 0.2, 0.3, 0.4];
+    let c = Color::from_array(arr);
+    assert_eq!(c.rgba(), (0.1, 0.2, 0.3, 0.4));
+    assert_eq!(c.to_array(), arr);
+    assert_eq!(Color::from_array(c.to_array()), c);
+    assert_eq!(Color::from(arr), c);
+    assert_eq!(c.to_rgb_array(), [0.1, 0.2, 0.3]);
+    assert_eq!(c.to_rgb_array()[..], c.to_array()[..3]);
+    assert_eq!(Color::WHITE.to_array(), [1., 1., 1., 1.]);
+}
+
+#[test]
+fn sort_colors() {
+    fn hex(colors: &[Color]) -> Vec<String> {
+        colors.iter().map(|c| c.to_hex_string()).collect()
+    }
+
+    let mut colors: Vec<Color> = [
+        "#00f", "#fff", "#ff0", "#f0f", "#f00", "#000", "#0ff", "#808080", "#0f0", "#ff8000",
+    ]
+    .iter()
+    .map(|s| s.parse().unwrap())
+    .collect();
+
+    sort_by_hue(&mut colors);
+    assert_eq!(
+        hex(&colors),
+        [
+            "#000000", "#808080", "#ffffff", "#ff0000", "#ff8000", "#ffff00", "#00ff00", "#00ffff",
+            "#0000ff", "#ff00ff",
+        ]
+    );
+
+    sort_by_lightness(&mut colors);
+    // equal HSL lightness keeps the previous order
+    assert_eq!(
+        hex(&colors),
+        [
+            "#000000", "#ff0000", "#ff8000", "#ffff00", "#00ff00", "#00ffff", "#0000ff", "#ff00ff",
+            "#808080", "#ffffff",
+        ]
+    );
+
+    sort_by_oklab_lightness(&mut colors);
+    assert_eq!(
+        hex(&colors),
+        [
+            "#000000", "#0000ff", "#808080", "#ff0000", "#ff00ff", "#ff8000", "#00ff00", "#00ffff",
+            "#ffff00", "#ffffff",
+        ]
+    );
+
+    let mut empty: [Color; 0] = [];
+    sort_by_hue(&mut empty);
+    sort_by_lightness(&mut empty);
+    sort_by_oklab_lightness(&mut empty);
+}
+
+#[test]
+fn gpl() {
+    let data = [
+        ((0, 0, 0), "Black", "  0   0   0\tBlack"),
+        ((255, 128, 0), "Orange", "255 128   0\tOrange"),
+        ((12, 200, 7), "", " 12 200   7\t"),
+        (
+            (79, 138, 179),
+            "Steel blue #2",
+            " 79 138 179\tSteel blue #2",
+        ),
+    ];
+    for ((r, g, b), name, line) in data {
+        let c = Color::from_rgb_u8(r, g, b);
+        assert_eq!(c.to_gpl_line(name), line);
+        assert_eq!(Color::from_gpl_line(line), Ok((name.to_string(), c)));
+    }
+
+    // alpha is dropped
+    let c = Color::from_rgba_u8(255, 0, 0, 128);
+    assert_eq!(c.to_gpl_line("Red"), "255   0   0\tRed");
+
+    let (name, c) = Color::from_gpl_line("  1 2 3   Multiple  words ").unwrap();
+    assert_eq!(name, "Multiple  words");
+    assert_eq!(c.rgba_u8(), (1, 2, 3, 255));
+
+    assert_eq!(Color::from_gpl_line(""), Err(ParseColorError::InvalidGpl));
+    assert_eq!(
+        Color::from_gpl_line("1 2"),
+        Err(ParseColorError::InvalidGpl)
+    );
+    for s in ["1 2 256\tRed", "1 2 x\tRed", "-1 2 3"] {
+        assert!(matches!(
+            Color::from_gpl_line(s),
+            Err(ParseColorError::InvalidInt(_))
+        ));
+    }
+
+    let file = "GIMP Palette
+Name: Test
+Columns: 2
+# comment
+255   0   0\tRed
+
+  0 255   0\tGreen
+  0   0 255
+";
+    let colors = Color::from_gpl_file(file).unwrap();
+    assert_eq!(
+        colors,
+        vec![
+            ("Red".to_string(), Color::from_rgb_u8(255, 0, 0)),
+            ("Green".to_string(), Color::from_rgb_u8(0, 255, 0)),
+            ("".to_string(), Color::from_rgb_u8(0, 0, 255)),
+        ]
+    );
+
+    assert_eq!(Color::from_gpl_file("GIMP Palette\n"), Ok(vec![]));
+    assert_eq!(Color::from_gpl_file(""), Err(ParseColorError::InvalidGpl));
+    assert_eq!(
+        Color::from_gpl_file("255 0 0\tRed"),
+        Err(ParseColorError::InvalidGpl)
+    );
+    assert!(Color::from_gpl_file("GIMP Palette\n255 0\n").is_err());
+}
+
+#[cfg(feature = "ase")]
+#[test]
+fn ase_swatch() {
+    let c = Color::from_rgb(1.0, 0.0, 0.0);
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x01, // color entry
+        0x00, 0x00, 0x00, 0x1c, // block length
+        0x00, 0x04, // name length, including the terminator
+        0x00, b'R', 0x00, b'e', 0x00, b'd', 0x00, 0x00,
+        b'R', b'G', b'B', b' ',
+        0x3f, 0x80, 0x00, 0x00, // 1.0
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x02, // normal color
+    ];
+    assert_eq!(c.to_ase_swatch_bytes("Red"), expected);
+
+    let data = [
+        ("", "#000000"),
+        ("Orange", "#ff8000"),
+        ("Blåbær ✓", "#4f8ab3"),
+    ];
+    for (name, hex) in data {
+        let c: Color = hex.parse().unwrap();
+        let (name2, c2) = Color::from_ase_swatch_bytes(&c.to_ase_swatch_bytes(name)).unwrap();
+        assert_eq!(name2, name);
+        assert_eq!(c2.to_hex_string(), hex);
+    }
+
+    // alpha is dropped
+    let c = Color::from_rgba(0.0, 0.0, 1.0, 0.5);
+    let (_, c) = Color::from_ase_swatch_bytes(&c.to_ase_swatch_bytes("Blue")).unwrap();
+    assert_eq!(c.rgba_u8(), (0, 0, 255, 255));
+
+    // gray and CMYK
+    #[rustfmt::skip]
+    let gray = [
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x10,
+        0x00, 0x02, 0x00, b'G', 0x00, 0x00,
+        b'G', b'r', b'a', b'y',
+        0x3f, 0x00, 0x00, 0x00, // 0.5
+        0x00, 0x00,
+    ];
+    let (name, c) = Color::from_ase_swatch_bytes(&gray).unwrap();
+    assert_eq!(name, "G");
+    assert_eq!(c.rgba_u8(), (128, 128, 128, 255));
+
+    #[rustfmt::skip]
+    let cmyk = [
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x1a,
+        0x00, 0x01, 0x00, 0x00,
+        b'C', b'M', b'Y', b'K',
+        0x00, 0x00, 0x00, 0x00,
+        0x3f, 0x80, 0x00, 0x00,
+        0x3f, 0x80, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+    let (name, c) = Color::from_ase_swatch_bytes(&cmyk).unwrap();
+    assert_eq!(name, "");
+    assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+
+    // the name length, counted in UTF-16 code units with the terminator, must fit in a u16
+    let name = "x".repeat(65534);
+    let bytes = Color::BLACK.to_ase_swatch_bytes(&name);
+    assert_eq!(Color::from_ase_swatch_bytes(&bytes).unwrap().0, name);
+
+    let bytes = Color::from_rgb(1.0, 0.0, 0.0).to_ase_swatch_bytes("Red");
+    let mut wrong_model = bytes.clone();
+    wrong_model[16..20].copy_from_slice(b"LAB ");
+    let mut group = bytes.clone();
+    group[1] = 0x02;
+    for data in [
+        &[][..],
+        &bytes[..20],
+        &bytes[..bytes.len() - 1],
+        &wrong_model,
+        &group,
+    ] {
+        assert_eq!(
+            Color::from_ase_swatch_bytes(data),
+            Err(ParseColorError::InvalidAse)
+        );
+    }
+}
+
+#[cfg(feature = "ase")]
+#[test]
+#[should_panic]
+fn ase_swatch_name_too_long() {
+    // 65536 UTF-16 code units with the terminator
+    Color::BLACK.to_ase_swatch_bytes(&"\u{1f600}".repeat(32768));
+}
+
+#[cfg(feature = "okhsl")]
+#[test]
+fn okhsl() {
+    // reference values from https://bottosson.github.io/misc/colorpicker/
+    let data = [
+        ("#ff0000", (29.234, 1.0, 0.568)),
+        ("#00ff00", (142.495, 1.0, 0.845)),
+        ("#0000ff", (264.052, 1.0, 0.367)),
+        ("#ffff00", (109.769, 1.0, 0.963)),
+        ("#00ffff", (194.769, 1.0, 0.890)),
+        ("#ff00ff", (328.363, 1.0, 0.653)),
+        ("#336699", (250.433, 0.617, 0.420)),
+        ("#808080", (0.0, 0.0, 0.536)),
+        ("#ffffff", (0.0, 0.0, 1.0)),
+        ("#000000", (0.0, 0.0, 0.0)),
+    ];
+    for (s, (h, sat, l)) in data {
+        let c: Color = s.parse().unwrap();
+        let (h2, sat2, l2) = c.to_okhsl();
+        assert!((h2 - h).abs() < 0.01, "{} {}", s, h2);
+        assert!((sat2 - sat).abs() < 0.001, "{} {}", s, sat2);
+        assert!((l2 - l).abs() < 0.001, "{} {}", s, l2);
+
+        assert_eq!(Color::from_okhsl(h2, sat2, l2).to_hex_string(), s);
+    }
+
+    for s in [
+        "#010101", "#fefefe", "#fdfeff", "#7f3fbf", "#ff8000", "#123456", "#c0ffee",
+    ] {
+        let c: Color = s.parse().unwrap();
+        let (h, sat, l) = c.to_okhsl();
+        assert_eq!(Color::from_okhsl(h, sat, l).to_hex_string(), s);
+    }
+
+    // equal lightness steps are perceptually uniform across hues, unlike HSL
+    let yellow = Color::from_okhsl(110.0, 1.0, 0.5);
+    let blue = Color::from_okhsl(264.0, 1.0, 0.5);
+    let (ly, _, _, _) = yellow.to_oklaba();
+    let (lb, _, _, _) = blue.to_oklaba();
+    assert!((ly - lb).abs() < 0.01);
+
+    // out of range values
+    assert_eq!(
+        Color::from_okhsl(29.234 + 360.0, 1.0, 0.568).to_hex_string(),
+        "#ff0000"
+    );
+    assert_eq!(Color::from_okhsl(0.0, 2.0, 2.0).to_hex_string(), "#ffffff");
+    assert_eq!(
+        Color::from_okhsl(0.0, -1.0, -1.0).to_hex_string(),
+        "#000000"
+    );
+    assert_eq!(
+        Color::from_okhsla(0.0, 0.0, 1.0, 0.5).rgba_u8(),
+        (255, 255, 255, 128)
+    );
+}
+
+#[test]
+fn color64() {
+    let c = Color::from_rgba(1.0, 0.5, 0.25, 0.75);
+    let c64 = c.to_color64();
+    assert_eq!(c64, Color64::from_rgba(1.0, 0.5, 0.25, 0.75));
+    assert_eq!(c64.rgba(), (1.0, 0.5, 0.25, 0.75));
+    assert_eq!(c64.to_color(), c);
+    assert_eq!(Color64::from(c.clone()), c64);
+    assert_eq!(Color::from(c64.clone()), c);
+
+    assert_eq!(Color64::default(), Color64::BLACK);
+    assert_eq!(Color64::WHITE.to_color(), Color::WHITE);
+    assert_eq!(c64.to_string(), "RGBA(1,0.5,0.25,0.75)");
+
+    let c: Color64 = "#ff8000".parse().unwrap();
+    assert_eq!(c.rgba_u8(), (255, 128, 0, 255));
+    assert_eq!(c.to_hex_string(), "#ff8000");
+    assert_eq!(
+        Color64::from_rgba_u8(255, 128, 0, 255).to_hex_string(),
+        "#ff8000"
+    );
+    assert!("#ff800".parse::<Color64>().is_err());
+
+    // conversions agree with Color
+    for s in [
+        "#ff0000", "#00ff00", "#0000ff", "#336699", "#c0ffee", "#808080",
+    ] {
+        let c: Color = s.parse().unwrap();
+        let c64 = c.to_color64();
+
+        let (l, a, b, _) = c.to_oklaba();
+        let (l64, a64, b64, _) = c64.to_oklaba();
+        assert!((l as f64 - l64).abs() < 1e-5, "{}", s);
+        assert!((a as f64 - a64).abs() < 1e-5, "{}", s);
+        assert!((b as f64 - b64).abs() < 1e-5, "{}", s);
+
+        let (r, g, b, _) = c64.to_linear_rgba();
+        let c2 = Color64::from_linear_rgba(r, g, b, 1.0);
+        assert!((c2.r - c64.r).abs() < 1e-12);
+        assert!((c2.g - c64.g).abs() < 1e-12);
+        assert!((c2.b - c64.b).abs() < 1e-12);
+
+        let c2 = Color64::from_oklaba(l64, a64, b64, 1.0);
+        assert_eq!(c2.to_hex_string(), s);
+
+        let close = |x: (f32, f32, f32, f32), y: (f64, f64, f64, f64)| {
+            (x.0 as f64 - y.0).abs() < 1e-3
+                && (x.1 as f64 - y.1).abs() < 1e-5
+                && (x.2 as f64 - y.2).abs() < 1e-5
+        };
+        assert!(close(c.to_hsla(), c64.to_hsla()), "{}", s);
+        assert!(close(c.to_hsva(), c64.to_hsva()), "{}", s);
+        assert!(close(c.to_hwba(), c64.to_hwba()), "{}", s);
+
+        let (h, sat, l, _) = c64.to_hsla();
+        assert_eq!(Color64::from_hsla(h, sat, l, 1.0).to_hex_string(), s);
+        let (h, sat, v, _) = c64.to_hsva();
+        assert_eq!(Color64::from_hsva(h, sat, v, 1.0).to_hex_string(), s);
+        let (h, w, b, _) = c64.to_hwba();
+        assert_eq!(Color64::from_hwba(h, w, b, 1.0).to_hex_string(), s);
+
+        assert_eq!(c64.to_rgb_string(), c.to_rgb_string());
+        let ratio = c64.contrast_ratio(&Color64::WHITE);
+        assert!(
+            (ratio - c.contrast_ratio(&Color::WHITE) as f64).abs() < 1e-4,
+            "{}",
+            s
+        );
+    }
+
+    assert!((Color64::BLACK.contrast_ratio(&Color64::WHITE) - 21.0).abs() < 1e-12);
+    assert_eq!(
+        Color64::BLACK.mix_with_weight(&Color64::WHITE, 3.0, 1.0),
+        Color64::from_rgb(0.25, 0.25, 0.25)
+    );
+    assert_eq!(
+        Color64::WHITE.mix_with_weight(&Color64::BLACK, 0.0, 0.0),
+        Color64::WHITE
+    );
+
+    let a = Color64::from_rgb(1.0, 0.0, 0.0);
+    let b = Color64::from_rgb(0.0, 0.0, 1.0);
+    assert_eq!(a.interpolate_rgb(&b, 0.5), Color64::from_rgb(0.5, 0.0, 0.5));
+    for t in [0.0, 0.3, 0.5, 1.0] {
+        let c1 = a.interpolate_linear_rgb(&b, t).to_color();
+        let c2 = a.to_color().interpolate_linear_rgb(&b.to_color(), t as f32);
+        assert_eq!(c1.rgba_u8(), c2.rgba_u8());
+        let c1 = a.interpolate_oklab(&b, t).to_color();
+        let c2 = a.to_color().interpolate_oklab(&b.to_color(), t as f32);
+        assert_eq!(c1.rgba_u8(), c2.rgba_u8());
+    }
+
+    // double precision survives many round trips
+    let mut c = Color64::from_rgb(0.1, 0.2, 0.3);
+    for _ in 0..1000 {
+        let (r, g, b, a) = c.to_linear_rgba();
+        c = Color64::from_linear_rgba(r, g, b, a);
+    }
+    assert!((c.r - 0.1).abs() < 1e-12);
+    assert!((c.g - 0.2).abs() < 1e-12);
+    assert!((c.b - 0.3).abs() < 1e-12);
+}
+
+#[cfg(feature = "okhsv")]
+#[test]
+fn okhsv() {
+    // reference values from https://bottosson.github.io/misc/colorpicker/
+    let data = [
+        ("#ff0000", (29.234, 1.0, 1.0)),
+        ("#00ff00", (142.495, 1.0, 1.0)),
+        ("#0000ff", (264.052, 1.0, 1.0)),
+        ("#ffff00", (109.769, 1.0, 1.0)),
+        ("#336699", (250.433, 0.708, 0.616)),
+        ("#7f3fbf", (303.139, 0.820, 0.757)),
+        ("#808080", (0.0, 0.0, 0.536)),
+        ("#ffffff", (0.0, 0.0, 1.0)),
+        ("#000000", (0.0, 0.0, 0.0)),
+    ];
+    for (s, (h, sat, v)) in data {
+        let c: Color = s.parse().unwrap();
+        let (h2, sat2, v2) = c.to_okhsv();
+        assert!((h2 - h).abs() < 0.01, "{} {}", s, h2);
+        assert!((sat2 - sat).abs() < 0.001, "{} {}", s, sat2);
+        assert!((v2 - v).abs() < 0.001, "{} {}", s, v2);
+
+        assert_eq!(Color::from_okhsv(h2, sat2, v2).to_hex_string(), s);
+    }
+
+    assert_eq!(
+        Color::from_okhsv(29.234, 1.0, 1.0).to_hex_string(),
+        "#ff0000"
+    );
+    assert_eq!(
+        Color::from_okhsv(0.0, 1.0, 1.0).oklch_hue_category(),
+        "rose"
+    );
+
+    // every (h, s, v) is in the sRGB gamut, so it round trips without clipping
+    for h in (0..360).step_by(10) {
+        for s in 1..=10 {
+            for v in 1..=10 {
+                let (h, s, v) = (h as f32, s as f32 / 10.0, v as f32 / 10.0);
+                let (h2, s2, v2) = Color::from_okhsv(h, s, v).to_okhsv();
+                let dh = ((h2 - h + 540.0) % 360.0 - 180.0).abs();
+                assert!(dh * s * v < 0.1, "{} {} {}", h, s, v);
+                assert!((s2 - s).abs() < 1e-3, "{} {} {}", h, s, v);
+                assert!((v2 - v).abs() < 1e-3, "{} {} {}", h, s, v);
+            }
+        }
+    }
+
+    // out of range values
+    assert_eq!(
+        Color::from_okhsv(29.234 - 360.0, 1.0, 1.0).to_hex_string(),
+        "#ff0000"
+    );
+    assert_eq!(Color::from_okhsv(0.0, -1.0, 2.0).to_hex_string(), "#ffffff");
+    assert_eq!(Color::from_okhsv(0.0, 1.0, 0.0).to_hex_string(), "#000000");
+    assert_eq!(
+        Color::from_okhsva(0.0, 0.0, 1.0, 0.5).rgba_u8(),
+        (255, 255, 255, 128)
+    );
+}
@@ -1,4 +1,8 @@
-use csscolorparser::{parse, Color};
+use csscolorparser::{
+    parse, parse_all, parse_css_function, parse_gradient_stops, parse_svg_color_attribute, Color,
+    ColorParser, ColorSpace, CssParseOptions, GradientStop, HueInterpolationMode, ParseColorError,
+    SvgColor,
+};
 
 #[test]
 fn parser() {
@@ -251,3 +255,1181 @@ fn invalid_format() {
         assert_eq!(c.unwrap_err().to_string(), err_msg);
     }
 }
+
+#[test]
+fn parse_with_options() {
+    let default = CssParseOptions::default();
+    for s in [
+        "#0f0",
+        "0f0f",
+        "rgb(0 255 0)",
+        "hwb(120 0% 0%)",
+        "hsv(120 1 1)",
+    ] {
+        assert_eq!(Color::from_css_string_with_options(s, default), parse(s));
+    }
+
+    let css3 = CssParseOptions {
+        allow_css4: false,
+        ..Default::default()
+    };
+    for s in [
+        "#0f0",
+        "#00ff00",
+        "0f0",
+        "00ff00",
+        "rgb(0,255,0)",
+        "rgba(0, 255, 0, 1)",
+        "hsl(120,100%,50%)",
+    ] {
+        let c = Color::from_css_string_with_options(s, css3).unwrap();
+        assert_eq!(c.rgba_u8(), (0, 255, 0, 255));
+    }
+    for s in [
+        "#0f0f",
+        "#00ff00ff",
+        "0f0f",
+        "00ff00ff",
+        "rgb(0 255 0)",
+        "rgb(0,255,0 / 1)",
+        "hsl(120 100% 50%)",
+        "hwb(120,0%,0%)",
+    ] {
+        assert!(Color::from_css_string_with_options(s, css3).is_err());
+    }
+
+    let no_named = CssParseOptions {
+        allow_named: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("transparent", no_named).is_err());
+    assert!(Color::from_css_string_with_options("#f00", no_named).is_ok());
+
+    let no_hex = CssParseOptions {
+        allow_hex: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("#f00", no_hex).is_err());
+    assert!(Color::from_css_string_with_options("ff0000", no_hex).is_err());
+    assert!(Color::from_css_string_with_options("rgb(255,0,0)", no_hex).is_ok());
+
+    let strict = CssParseOptions {
+        strict_percentages: true,
+        ..Default::default()
+    };
+    for s in [
+        "rgb(255,0,0)",
+        "rgb(100%,0%,0%)",
+        "hsl(0,100%,50%)",
+        "hsv(0 100% 100%)",
+    ] {
+        let c = Color::from_css_string_with_options(s, strict).unwrap();
+        assert_eq!(c.rgba_u8(), (255, 0, 0, 255));
+    }
+    for s in ["rgb(100%,0,0)", "hsl(0,1,0.5)", "hwb(0 0 0%)"] {
+        assert!(Color::from_css_string_with_options(s, strict).is_err());
+    }
+}
+
+#[cfg(feature = "named-colors")]
+#[test]
+fn parse_with_options_named() {
+    let no_named = CssParseOptions {
+        allow_named: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("red", no_named).is_err());
+
+    let css3 = CssParseOptions {
+        allow_css4: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("red", css3).is_ok());
+}
+
+#[test]
+fn hex_digit_pairs() {
+    let c = Color::from_css_hex_digit_pairs(&[(0xf, 0xf), (0x6, 0x6), (0x0, 0x0)]).unwrap();
+    assert_eq!(c.rgba_u8(), (255, 102, 0, 255));
+
+    let c = Color::from_css_hex_digit_pairs(&[(0x1, 0x2), (0x3, 0x4), (0x5, 0x6), (0x7, 0x8)]);
+    assert_eq!(c.unwrap().rgba_u8(), (0x12, 0x34, 0x56, 0x78));
+
+    let c = Color::from_css_hex_digit_pairs(&[(0, 0), (0, 0), (0, 0), (0, 0)]).unwrap();
+    assert_eq!(c.rgba_u8(), (0, 0, 0, 0));
+
+    assert!(Color::from_css_hex_digit_pairs(&[]).is_err());
+    assert!(Color::from_css_hex_digit_pairs(&[(0, 0), (0, 0)]).is_err());
+    assert!(Color::from_css_hex_digit_pairs(&[(0, 0); 5]).is_err());
+    assert!(Color::from_css_hex_digit_pairs(&[(0, 0), (16, 0), (0, 0)]).is_err());
+    assert_eq!(
+        Color::from_css_hex_digit_pairs(&[(0, 0), (0, 0), (0, 0xf0)])
+            .unwrap_err()
+            .to_string(),
+        "Invalid hex format."
+    );
+}
+
+#[test]
+fn none_keyword() {
+    let data = vec![
+        ("rgb(none 255 none)", (0, 255, 0, 255)),
+        ("rgb(0 255 0 / none)", (0, 255, 0, 0)),
+        ("hsl(none 0% 50%)", (128, 128, 128, 255)),
+        ("hsl(none 100% 50%)", (255, 0, 0, 255)),
+        ("hsl(120 none 50%)", (128, 128, 128, 255)),
+        ("hwb(none 0% 100%)", (0, 0, 0, 255)),
+        ("hsv(0 0% none)", (0, 0, 0, 255)),
+        ("oklab(1 none none)", (255, 255, 255, 255)),
+        ("oklch(1 none none)", (255, 255, 255, 255)),
+        ("oklch(0 0 none)", (0, 0, 0, 255)),
+        ("color(srgb none 1 none)", (0, 255, 0, 255)),
+    ];
+
+    for (s, expected) in data {
+        assert_eq!(parse(s).unwrap().rgba_u8(), expected, "{}", s);
+    }
+
+    let css3 = CssParseOptions {
+        allow_css4: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("hsl(none,0%,50%)", css3).is_err());
+
+    assert!(parse("hsl(nones 0% 50%)").is_err());
+    assert!(parse("rgb(0 0 -none)").is_err());
+
+    // non-finite literals are not missing components
+    for s in [
+        "rgb(nan 0 0)",
+        "rgb(0 0 0 / nan)",
+        "hsl(nan, 50%, 50%)",
+        "hsl(0 nan% 50%)",
+        "hwb(infdeg 0% 0%)",
+        "oklab(inf 0 0)",
+        "oklch(0.5 0.1 -infinity)",
+        "color(srgb 1 nan 0)",
+        "rgb(1e39 0 0)",
+        "rgb(from red nan g b)",
+    ] {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+    let err = parse("rgb(nan 0 0)").unwrap_err();
+    assert_eq!(err, ParseColorError::InvalidRgb(None));
+}
+
+#[test]
+fn oklab_oklch() {
+    let data = vec![
+        ("oklab(0 0 0)", (0, 0, 0, 255)),
+        ("oklab(1 0 0)", (255, 255, 255, 255)),
+        ("oklab(100% 0% 0%)", (255, 255, 255, 255)),
+        ("oklab(0.628 0.2249 0.1258)", (255, 0, 0, 255)),
+        ("oklab(62.8% 56.225% 31.45%)", (255, 0, 0, 255)),
+        ("oklab(0.86644 -0.23392 0.17942 / 0.5)", (0, 255, 0, 128)),
+        ("oklch(0.628 0.2577 29.23)", (255, 0, 0, 255)),
+        ("oklch(62.8% 64.425% 29.22deg)", (255, 0, 0, 255)),
+        ("oklch(0.45203 0.3133 264.068 / 50%)", (0, 0, 255, 128)),
+        ("oklch(0.45203 0.3133 -95.932)", (0, 0, 255, 255)),
+    ];
+
+    for (s, expected) in data {
+        assert_eq!(parse(s).unwrap().rgba_u8(), expected, "{}", s);
+    }
+
+    let data = vec![
+        ("oklab(1 0)", "Invalid oklab format."),
+        ("oklab(1 0 x)", "Invalid oklab format."),
+        ("oklch(1 0 0 0 0)", "Invalid oklch format."),
+        ("oklch(1 0 xdeg)", "Invalid oklch format."),
+    ];
+
+    for (s, err_msg) in data {
+        assert_eq!(parse(s).unwrap_err().to_string(), err_msg);
+    }
+}
+
+#[test]
+fn color_function() {
+    let data = vec![
+        ("color(srgb 1 0 0)", (255, 0, 0, 255)),
+        ("color(srgb 100% 0% 50% / 50%)", (255, 0, 128, 128)),
+        ("color(srgb-linear 1 0.2158 0)", (255, 128, 0, 255)),
+        ("color(display-p3 0.9175 0.2003 0.1386)", (255, 0, 0, 255)),
+        ("color(rec2020 0.792 0.231 0.0738)", (255, 0, 0, 255)),
+        ("color(xyz 0.9505 1 1.089)", (255, 255, 255, 255)),
+        ("color(xyz-d65 0.4124 0.2126 0.0193)", (255, 0, 0, 255)),
+        ("color(xyz-d50 0.9642 1 0.8252)", (255, 255, 255, 255)),
+    ];
+
+    for (s, expected) in data {
+        assert_eq!(parse(s).unwrap().rgba_u8(), expected, "{}", s);
+    }
+
+    let data = vec![
+        "color(srgb 1 0)",
+        "color(1 0 0)",
+        "color(cmyk 1 0 0)",
+        "color(srgb 1 0 x)",
+        "color(srgb 1 0 0 1 1)",
+    ];
+
+    for s in data {
+        assert_eq!(parse(s).unwrap_err().to_string(), "Invalid color() format.");
+    }
+
+    #[cfg(feature = "wide-gamut")]
+    {
+        let data = vec![
+            ("color(a98-rgb 0.859 0 0)", (255, 0, 0, 255)),
+            ("color(a98-rgb 1 1 1 / 0.5)", (255, 255, 255, 128)),
+        ];
+        for (s, expected) in data {
+            assert_eq!(parse(s).unwrap().rgba_u8(), expected, "{}", s);
+        }
+
+        let c = parse("color-mix(in a98-rgb, #000, #fff)").unwrap();
+        assert_eq!(c.rgba_u8(), (129, 129, 129, 255));
+    }
+    #[cfg(not(feature = "wide-gamut"))]
+    assert!(parse("color(a98-rgb 1 0 0)").is_err());
+}
+
+#[test]
+fn four_digit_hex() {
+    let data = vec![
+        ("#8fff", (136, 255, 255, 255)),
+        ("8fff", (136, 255, 255, 255)),
+        ("#0000", (0, 0, 0, 0)),
+        ("#ffff", (255, 255, 255, 255)),
+        ("#FFFF", (255, 255, 255, 255)),
+        ("#000f", (0, 0, 0, 255)),
+        ("#fff0", (255, 255, 255, 0)),
+        ("#1234", (17, 34, 51, 68)),
+        ("#aBcD", (170, 187, 204, 221)),
+        ("#f008", (255, 0, 0, 136)),
+        (" #0f07 ", (0, 255, 0, 119)),
+    ];
+
+    for (s, expected) in data {
+        let c = Color::from_css_4_digit_hex(s).unwrap();
+        assert_eq!(c.rgba_u8(), expected, "{}", s);
+        assert_eq!(c, parse(s).unwrap());
+    }
+
+    let c = Color::from_css_4_digit_hex("#8fff").unwrap();
+    assert!((c.r - 0.533).abs() < 0.001);
+    assert_eq!((c.g, c.b, c.a), (1., 1., 1.));
+
+    let data = vec![
+        "",
+        "#",
+        "#fff",
+        "#fffff",
+        "#ffffffff",
+        "#fffg",
+        "##fff",
+        "#ff f",
+        "#ff£",
+        "#-fff",
+        "ffff#",
+    ];
+
+    for s in data {
+        assert_eq!(
+            Color::from_css_4_digit_hex(s).unwrap_err().to_string(),
+            "Invalid hex format.",
+            "{}",
+            s
+        );
+    }
+}
+
+#[test]
+fn relative_color() {
+    let data = [
+        ("rgb(from #f00 r g b)", (255, 0, 0, 255)),
+        ("rgb(from #ff0000 r g b / 50%)", (255, 0, 0, 128)),
+        (
+            "rgb(from #f00 calc(r - 55) calc(g + 20) b)",
+            (200, 20, 0, 255),
+        ),
+        ("rgb(from #f00 calc(r + 20) g b)", (255, 0, 0, 255)),
+        ("rgb(from rgb(10 20 30) b g r)", (30, 20, 10, 255)),
+        (
+            "rgb(from #0000ff none 100% b / calc(alpha / 2))",
+            (0, 255, 255, 128),
+        ),
+        ("rgb(from #f00 r g b / 0.2)", (255, 0, 0, 51)),
+        ("hsl(from #f00 calc(h + 120) s l)", (0, 255, 0, 255)),
+        ("hsl(from #f00 240deg s l)", (0, 0, 255, 255)),
+        ("hwb(from #f00 h 100% b)", (255, 255, 255, 255)),
+        ("hsv(from #f00 h s calc(v / 2))", (128, 0, 0, 255)),
+        ("oklab(from #f00 l a b)", (255, 0, 0, 255)),
+        ("oklch(from #0f0 l c h)", (0, 255, 0, 255)),
+        ("oklch(from #f00 l 0 h)", (136, 136, 136, 255)),
+    ];
+
+    for (s, rgba) in data {
+        assert_eq!(parse(s).unwrap().rgba_u8(), rgba, "{}", s);
+    }
+
+    let data = [
+        "rgb(from)",
+        "rgb(from red)",
+        "rgb(from red r g)",
+        "rgb(from red r g b a)",
+        "rgb(from red r g x)",
+        "rgb(from red r g calc(b +))",
+        "rgb(from red r g calc(b + (2))",
+        "rgb(from nope r g b)",
+        "hsl(from red r g b)",
+        "rgb(from red r g b /)",
+        "foo(from red r g b)",
+    ];
+
+    for s in data {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+
+    // deeply nested calc() expressions are rejected instead of overflowing the stack
+    let nested = |open: &str, n: usize| {
+        let close = if open == "(" { ")" } else { "" };
+        format!(
+            "rgb(from #f00 r g calc({}b{}))",
+            open.repeat(n),
+            close.repeat(n)
+        )
+    };
+    for open in ["(", "-", "+"] {
+        assert!(parse(&nested(open, 100_000)).is_err(), "{}", open);
+        assert!(parse(&nested(open, 100)).is_err(), "{}", open);
+        assert!(parse(&nested(open, 10)).is_ok(), "{}", open);
+    }
+
+    let opts = CssParseOptions {
+        allow_css4: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("rgb(from #f00 r g b)", opts).is_err());
+}
+
+#[test]
+fn rgba_hex_pair() {
+    let data = [
+        ("ff6600", "80", (255, 102, 0, 128)),
+        ("#ff6600", "ff", (255, 102, 0, 255)),
+        (" 00FF00 ", "00", (0, 255, 0, 0)),
+        ("abcdef", "7f", (171, 205, 239, 127)),
+    ];
+
+    for (rgb, alpha, rgba) in data {
+        let c = Color::from_rgba_hex_pair(rgb, alpha).unwrap();
+        assert_eq!(c.rgba_u8(), rgba);
+    }
+
+    let data = [
+        ("", "80"),
+        ("ff6600", ""),
+        ("f60", "80"),
+        ("ff6600", "8"),
+        ("ff6600", "#80"),
+        ("ff6600ff", "80"),
+        ("ff66gg", "80"),
+        ("+f6600", "80"),
+        ("ff6600", "+8"),
+        ("ff££", "80"),
+    ];
+
+    for (rgb, alpha) in data {
+        assert_eq!(
+            Color::from_rgba_hex_pair(rgb, alpha)
+                .unwrap_err()
+                .to_string(),
+            "Invalid hex format."
+        );
+    }
+}
+
+#[test]
+fn color_mix() {
+    let red = Color::from_rgb(1., 0., 0.);
+    let blue = Color::from_rgb(0., 0., 1.);
+
+    let data = [
+        ("color-mix(in srgb, #f00, #00f)", (128, 0, 128, 255)),
+        ("color-mix(in srgb, #f00 50%, #00f 50%)", (128, 0, 128, 255)),
+        ("color-mix(in srgb, #f00 25%, #00f)", (64, 0, 191, 255)),
+        ("color-mix(in srgb, #f00, 75% #00f)", (64, 0, 191, 255)),
+        ("color-mix(in srgb, #f00 100%, #00f)", (255, 0, 0, 255)),
+        ("color-mix(in srgb, #f00 0%, #00f)", (0, 0, 255, 255)),
+        ("color-mix(in srgb, #f00 30%, #00f 30%)", (128, 0, 128, 153)),
+        ("color-mix(in srgb, #f00 60%, #00f 60%)", (128, 0, 128, 255)),
+        ("COLOR-MIX(IN SRGB, #F00, #00F)", (128, 0, 128, 255)),
+        ("color-mix( in  srgb ,#f00,#00f )", (128, 0, 128, 255)),
+        ("color-mix(in hsl, #f00, #0f0)", (255, 255, 0, 255)),
+        ("color-mix(in hsl longer hue, #f00, #0f0)", (0, 0, 255, 255)),
+        ("color-mix(in hsl, #fff, #f00)", (223, 159, 159, 255)),
+        // colors are premultiplied by alpha
+        ("color-mix(in srgb, transparent, #f00)", (255, 0, 0, 128)),
+        (
+            "color-mix(in srgb, #f00, transparent 25%)",
+            (255, 0, 0, 191),
+        ),
+        (
+            "color-mix(in srgb, rgb(255 0 0 / .5), #00f)",
+            (85, 0, 170, 191),
+        ),
+        ("color-mix(in oklab, transparent, #00f)", (0, 0, 255, 128)),
+        ("color-mix(in hsl, transparent, #0f0)", (0, 255, 0, 128)),
+        ("color-mix(in srgb, transparent, transparent)", (0, 0, 0, 0)),
+    ];
+
+    for (s, rgba) in data {
+        assert_eq!(parse(s).unwrap().rgba_u8(), rgba, "{}", s);
+    }
+
+    let data = [
+        ("color-mix(in oklab, #f00, #00f)", ColorSpace::OkLab, 0.5),
+        (
+            "color-mix(in oklab, #f00 40%, #00f)",
+            ColorSpace::OkLab,
+            0.6,
+        ),
+        ("color-mix(in oklch, #f00, #00f)", ColorSpace::OkLch, 0.5),
+        (
+            "color-mix(in srgb-linear, #f00, #00f)",
+            ColorSpace::SrgbLinear,
+            0.5,
+        ),
+        ("color-mix(in xyz, #f00, #00f 20%)", ColorSpace::XyzD65, 0.2),
+        ("color-mix(in xyz-d50, #f00, #00f)", ColorSpace::XyzD50, 0.5),
+        (
+            "color-mix(in display-p3, #f00, #00f)",
+            ColorSpace::DisplayP3,
+            0.5,
+        ),
+        ("color-mix(in hwb, #f00, #00f)", ColorSpace::Hwb, 0.5),
+    ];
+
+    for (s, space, t) in data {
+        assert_eq!(
+            parse(s).unwrap().rgba_u8(),
+            red.interpolate_in(space, &blue, t).rgba_u8(),
+            "{}",
+            s
+        );
+    }
+
+    let c = parse("color-mix(in oklch decreasing hue, #f00, #00f)").unwrap();
+    let d = red.interpolate_in_with_mode(
+        ColorSpace::OkLch,
+        &blue,
+        0.5,
+        HueInterpolationMode::Decreasing,
+    );
+    assert_eq!(c.rgba_u8(), d.rgba_u8());
+
+    let data = [
+        "color-mix()",
+        "color-mix(in srgb)",
+        "color-mix(in srgb, #f00)",
+        "color-mix(in srgb, #f00, #00f, #0f0)",
+        "color-mix(srgb, #f00, #00f)",
+        "color-mix(in nope, #f00, #00f)",
+        "color-mix(in srgb longer hue, #f00, #00f)",
+        "color-mix(in hsl sideways hue, #f00, #00f)",
+        "color-mix(in hsl longer, #f00, #00f)",
+        "color-mix(in srgb, #f00 0%, #00f 0%)",
+        "color-mix(in srgb, #f00 -10%, #00f)",
+        "color-mix(in srgb, #f00 110%, #00f)",
+        "color-mix(in srgb, #f00 50, #00f)",
+        "color-mix(in srgb, #f00 10% 10%, #00f)",
+        "color-mix(in srgb, #ggg, #00f)",
+        "color-mix(in srgb, , #00f)",
+    ];
+
+    for s in data {
+        assert_eq!(
+            parse(s).unwrap_err().to_string(),
+            "Invalid color-mix() format.",
+            "{}",
+            s
+        );
+    }
+
+    let opts = CssParseOptions {
+        allow_css4: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("color-mix(in srgb, #f00, #00f)", opts).is_err());
+}
+
+#[test]
+fn color_parser() {
+    let data = [
+        "#f00",
+        "#ff0000",
+        "f00",
+        "ff0000",
+        "rgb(255,0,0)",
+        "rgb(100%,0%,0%)",
+        "rgb(255 0% 0)",
+        "rgba(255,0,0,100%)",
+        "hsl(0,100%,50%)",
+        "hsl(0 1 0.5)",
+        "hsv(0,100%,100%)",
+        "hwba(0,0%,0%)",
+    ];
+
+    // default behaves like parse()
+    let parser = ColorParser::default();
+    assert_eq!(parser, ColorParser::new());
+
+    for s in data {
+        assert_eq!(parser.parse(s), parse(s), "{}", s);
+    }
+
+    let parser = ColorParser::strict();
+
+    for s in [
+        "#f00",
+        "#ff0000",
+        "rgb(255,0,0)",
+        "rgb(100%,0%,0%)",
+        "rgba(255,0,0,1)",
+        "rgb(255 0 0 / 50%)",
+        "hsl(0,100%,50%)",
+        "hwb(0 0% 0%)",
+    ] {
+        assert!(parser.parse(s).is_ok(), "{}", s);
+    }
+
+    for s in [
+        "f00",
+        "ff0000",
+        "rgb(255 0% 0)",
+        "rgba(255,0,0,100%)",
+        "hsla(0,100%,50%,50%)",
+        "hsl(0 1 0.5)",
+        "hsv(0,100%,100%)",
+        "hsva(0,100%,100%,1)",
+        "hwba(0,0%,0%)",
+        "hsv(from #f00 h s v)",
+        "255 0 0",
+    ] {
+        assert!(parser.parse(s).is_err(), "{}", s);
+    }
+
+    assert_eq!(
+        parser.parse("hsv(0,100%,100%)").unwrap_err().to_string(),
+        "Invalid color function."
+    );
+
+    let parser = ColorParser::lenient();
+
+    for s in data {
+        assert_eq!(parser.parse(s), parse(s), "{}", s);
+    }
+
+    let data = [
+        ("255 0 0", (255, 0, 0, 255)),
+        ("255,0,0", (255, 0, 0, 255)),
+        ("0 255 0 / 50%", (0, 255, 0, 128)),
+        ("100%, 0%, 100%, 0.5", (255, 0, 255, 128)),
+        (".5 0 0", (1, 0, 0, 255)),
+        (" 10 20 30 ", (10, 20, 30, 255)),
+    ];
+
+    for (s, rgba) in data {
+        assert_eq!(parser.parse(s).unwrap().rgba_u8(), rgba, "{}", s);
+        assert!(parse(s).is_err(), "{}", s);
+    }
+
+    for s in ["255 0", "255 0 0 0 0", "a 0 0", "-"] {
+        assert!(parser.parse(s).is_err(), "{}", s);
+    }
+
+    // builder
+    let parser = ColorParser::new()
+        .allow_bare_hex_without_hash(false)
+        .allow_legacy_rgba_percentages(false);
+    assert!(parser.parse("ff0000").is_err());
+    assert!(parser.parse("rgba(255,0,0,50%)").is_err());
+    assert!(parser.parse("rgba(255,0,0,0.5)").is_ok());
+    assert!(parser.parse("rgb(255 0 0 / 50%)").is_ok());
+    assert!(parser.parse("hsv(0,100%,100%)").is_ok());
+
+    let parser = ColorParser::strict()
+        .strict_level_4_mode(false)
+        .allow_mixed_percentages(true)
+        .allow_bare_numbers(true);
+    assert!(parser.parse("hsv(0,100%,100%)").is_ok());
+    assert!(parser.parse("rgb(255 0% 0)").is_ok());
+    assert!(parser.parse("255 0 0").is_ok());
+    assert!(parser.parse("ff0000").is_err());
+
+    // thin wrapper around CssParseOptions
+    let opts = ColorParser::strict().options();
+    assert!(!opts.allow_bare_hex && opts.strict_percentages && !opts.allow_nonstandard);
+    let css3 = CssParseOptions {
+        allow_css4: false,
+        ..Default::default()
+    };
+    let parser = ColorParser::from(css3);
+    assert_eq!(parser.options(), css3);
+    assert!(parser.parse("rgb(0 255 0)").is_err());
+}
+
+#[cfg(feature = "system-colors")]
+#[test]
+fn system_colors() {
+    use csscolorparser::SystemColorResolver;
+
+    let data = [
+        ("Canvas", "#ffffff"),
+        ("CanvasText", "#000000"),
+        ("ButtonFace", "#efefef"),
+        ("LinkText", "#0000ee"),
+        ("VisitedText", "#551a8b"),
+        ("AccentColor", "#0075ff"),
+        ("graytext", "#808080"),
+        (" HIGHLIGHT ", "#b5d5ff"),
+    ];
+
+    for (s, hex) in data {
+        let c = Color::from_css_system_color(s).unwrap();
+        assert_eq!(c.to_hex_string(), hex, "{}", s);
+        assert_eq!(parse(s).unwrap(), c, "{}", s);
+    }
+
+    assert_eq!(Color::from_css_system_color("Canvas"), Some(Color::WHITE));
+    assert_eq!(Color::from_css_system_color("red"), None);
+    assert_eq!(Color::from_css_system_color(""), None);
+    assert_eq!(Color::from_css_system_color("Background"), None);
+
+    struct Dark;
+
+    impl SystemColorResolver for Dark {
+        fn resolve(&self, name: &str) -> Option<Color> {
+            match name {
+                "canvas" => Some(Color::BLACK),
+                "canvastext" => Some(Color::WHITE),
+                _ => None,
+            }
+        }
+    }
+
+    assert_eq!(
+        Color::from_css_system_color_with("Canvas", &Dark),
+        Some(Color::BLACK)
+    );
+    assert_eq!(
+        Color::from_css_system_color_with("CANVASTEXT", &Dark),
+        Some(Color::WHITE)
+    );
+    assert_eq!(
+        Color::from_css_system_color_with("ButtonFace", &Dark),
+        Color::from_css_system_color("ButtonFace")
+    );
+    assert_eq!(Color::from_css_system_color_with("foo", &Dark), None);
+
+    let opts = CssParseOptions {
+        allow_named: false,
+        ..Default::default()
+    };
+    assert!(Color::from_css_string_with_options("Canvas", opts).is_err());
+}
+
+#[cfg(feature = "named-colors")]
+#[test]
+fn named_css_version() {
+    use csscolorparser::{
+        parse_named, parse_named_css1, parse_named_css3, parse_named_css4, CssVersion,
+    };
+
+    let data = [
+        ("red", [true, true, true, true]),
+        ("Navy", [true, true, true, true]),
+        (" teal ", [true, true, true, true]),
+        ("orange", [false, true, true, true]),
+        ("aliceblue", [false, false, true, true]),
+        ("darkgrey", [false, false, true, true]),
+        ("rebeccapurple", [false, false, false, true]),
+        ("transparent", [false, false, false, false]),
+        ("nope", [false, false, false, false]),
+    ];
+
+    let versions = [
+        CssVersion::Css1,
+        CssVersion::Css2,
+        CssVersion::Css3,
+        CssVersion::Css4,
+    ];
+
+    for (name, valid) in data {
+        for (version, valid) in versions.iter().zip(valid) {
+            let c = parse_named(name, *version);
+            assert_eq!(c.is_some(), valid, "{} {:?}", name, version);
+            if let Some(c) = c {
+                assert_eq!(c, parse(name).unwrap());
+            }
+        }
+        assert_eq!(parse_named_css1(name), parse_named(name, CssVersion::Css1));
+        assert_eq!(parse_named_css3(name), parse_named(name, CssVersion::Css3));
+        assert_eq!(parse_named_css4(name), parse_named(name, CssVersion::Css4));
+    }
+
+    assert!(CssVersion::Css1 < CssVersion::Css4);
+}
+
+#[cfg(feature = "system-colors")]
+#[test]
+fn system_keyword() {
+    use csscolorparser::CssSystemColor;
+
+    let data = [
+        ("ButtonFace", "ButtonFace"),
+        ("buttonface", "ButtonFace"),
+        (" GRAYTEXT ", "GrayText"),
+        ("highlight", "Highlight"),
+        ("Canvas", "Canvas"),
+    ];
+
+    for (s, name) in data {
+        assert_eq!(
+            Color::from_css_system_keyword(s).unwrap(),
+            CssSystemColor::System(name.into())
+        );
+    }
+
+    let data = ["#ff0000", "rgb(255 0 0)", "hsl(0, 100%, 50%)"];
+
+    for s in data {
+        assert_eq!(
+            Color::from_css_system_keyword(s).unwrap(),
+            CssSystemColor::Resolved(parse(s).unwrap())
+        );
+    }
+
+    assert!(Color::from_css_system_keyword("nope").is_err());
+    assert!(Color::from_css_system_keyword("").is_err());
+}
+
+#[test]
+fn error_context() {
+    use std::error::Error;
+
+    let c = Color::from_html_with_error_context("#f00", "color property").unwrap();
+    assert_eq!(c, parse("#f00").unwrap());
+
+    let err =
+        Color::from_html_with_error_context("bad_value", "background-color property").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Failed to parse color 'bad_value' in background-color property"
+    );
+    assert_eq!(err.value, "bad_value");
+    assert_eq!(err.context, "background-color property");
+    assert_eq!(err.kind, parse("bad_value").unwrap_err());
+    assert_eq!(err.source().unwrap().to_string(), "Invalid unknown format.");
+
+    let err = Color::from_html_with_error_context("rgb(1,2)", "border-color").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Failed to parse color 'rgb(1,2)' in border-color"
+    );
+    assert_eq!(err.kind.to_string(), "Invalid rgb format.");
+}
+
+#[test]
+fn number_errors() {
+    use csscolorparser::ParseColorError;
+    use std::error::Error;
+
+    fn parse_alpha(s: &str) -> Result<f32, ParseColorError> {
+        Ok(s.parse::<f32>()?)
+    }
+
+    fn parse_channel(s: &str) -> Result<u8, ParseColorError> {
+        Ok(s.parse::<u8>()?)
+    }
+
+    let err = parse_alpha("foo").unwrap_err();
+    assert!(matches!(err, ParseColorError::InvalidFloat(_)));
+    assert_eq!(err.to_string(), "Invalid number: invalid float literal.");
+    assert_eq!(err.source().unwrap().to_string(), "invalid float literal");
+    assert_eq!(
+        err.source()
+            .unwrap()
+            .downcast_ref::<std::num::ParseFloatError>(),
+        Some(&"foo".parse::<f32>().unwrap_err())
+    );
+
+    let err = parse_channel("256").unwrap_err();
+    assert!(matches!(err, ParseColorError::InvalidInt(_)));
+    assert_eq!(
+        err.source()
+            .unwrap()
+            .downcast_ref::<std::num::ParseIntError>()
+            .unwrap()
+            .kind(),
+        &std::num::IntErrorKind::PosOverflow
+    );
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "number too large to fit in target type"
+    );
+
+    assert_eq!(parse_alpha("0.5"), Ok(0.5));
+    assert_eq!(parse_channel("255"), Ok(255));
+
+    // invalid numbers in color functions keep the float parse error as the source
+    let err = parse("rgb(foo, 0, 0)").unwrap_err();
+    assert!(matches!(err, ParseColorError::InvalidRgb(Some(_))));
+    assert_eq!(err.to_string(), "Invalid rgb format.");
+    assert!(err
+        .source()
+        .unwrap()
+        .downcast_ref::<std::num::ParseFloatError>()
+        .is_some());
+    assert_eq!(err.source().unwrap().to_string(), "invalid float literal");
+    assert!(matches!(
+        parse("color(srgb 1 0 x)"),
+        Err(ParseColorError::InvalidColor(Some(_)))
+    ));
+    assert_eq!(parse("rgb(255, 0)"), Err(ParseColorError::InvalidRgb(None)));
+    assert!(parse("rgb(255, 0)").unwrap_err().source().is_none());
+    assert_eq!(
+        parse("hsl(0, 50%, %)")
+            .unwrap_err()
+            .source()
+            .unwrap()
+            .to_string(),
+        "cannot parse float from empty string"
+    );
+}
+
+#[test]
+fn parse_all_list() {
+    let c = parse_all("  #f00,rgb(0, 255, 0) , oklch(0.5 0.2 180),  hsl(240, 100%, 50%)  ");
+    let c = c
+        .into_iter()
+        .map(|c| c.unwrap().rgba_u8())
+        .collect::<Vec<_>>();
+    assert_eq!(c[0], (255, 0, 0, 255));
+    assert_eq!(c[1], (0, 255, 0, 255));
+    assert_eq!(c[2], parse("oklch(0.5 0.2 180)").unwrap().rgba_u8());
+    assert_eq!(c[3], (0, 0, 255, 255));
+
+    // nested commas
+    let c = parse_all("color-mix(in srgb, #f00, #00f), rgb(from #f00 r g b)");
+    assert_eq!(c.len(), 2);
+    assert_eq!(c[0].as_ref().unwrap().rgba_u8(), (128, 0, 128, 255));
+    assert_eq!(c[1].as_ref().unwrap().rgba_u8(), (255, 0, 0, 255));
+
+    // mixed success and failure
+    let c = parse_all("#00f, rgb(255,0), , #0f0");
+    assert_eq!(c.len(), 4);
+    assert!(c[0].is_ok());
+    assert_eq!(
+        c[1].as_ref().unwrap_err().to_string(),
+        "Invalid rgb format."
+    );
+    assert!(c[2].is_err());
+    assert!(c[3].is_ok());
+
+    // all failure
+    let c = parse_all("foo, bar(1, 2), #zzz");
+    assert_eq!(c.len(), 3);
+    assert!(c.iter().all(|c| c.is_err()));
+
+    assert!(parse_all("").is_empty());
+    assert!(parse_all("  ").is_empty());
+}
+
+#[test]
+fn css_function() {
+    let data = [
+        ("rgb", "255,0,0"),
+        ("RGBA", " 255 0 0 / 100% "),
+        ("hsl", "0deg 100% 50%"),
+        ("hwb", "0 0% 0%"),
+        ("oklab", "0.628 0.225 0.126"),
+        ("oklch", "0.628 0.2577 29.23"),
+        ("color", "srgb 1 0 0"),
+        ("color-mix", "in srgb, #f00, #f00"),
+        ("rgb", "from #f00 r g b"),
+    ];
+    for (name, args) in data {
+        let c = parse_css_function(name, args).unwrap();
+        assert_eq!(c.rgba_u8(), (255, 0, 0, 255), "{}({})", name, args);
+        assert_eq!(
+            Ok(c),
+            parse(&format!("{}({})", name, args)),
+            "{}({})",
+            name,
+            args
+        );
+    }
+
+    let data = [
+        ("rgb", "255,0", "Invalid rgb format."),
+        ("hsl", "", "Invalid hsl format."),
+        ("cmyk", "0 0 0 0", "Invalid color function."),
+        ("", "1 2 3", "Invalid color function."),
+    ];
+    for (name, args, err) in data {
+        assert_eq!(parse_css_function(name, args).unwrap_err().to_string(), err);
+    }
+}
+
+#[test]
+fn extended_named_colors() {
+    // CSS named colors are always available with `named-colors`
+    #[cfg(feature = "named-colors")]
+    assert_eq!(
+        Color::from_html("Chartreuse").unwrap().rgba_u8(),
+        (127, 255, 0, 255)
+    );
+
+    let data = [
+        ("aquamarine1", (127, 255, 212, 255)),
+        ("DodgerBlue1", (30, 144, 255, 255)),
+        ("dodgerblue4", (16, 78, 139, 255)),
+        ("chartreuse3", (102, 205, 0, 255)),
+        ("gray0", (0, 0, 0, 255)),
+        ("grey50", (127, 127, 127, 255)),
+        ("GRAY100", (255, 255, 255, 255)),
+        ("navyblue", (0, 0, 128, 255)),
+    ];
+    for (s, rgba) in data {
+        #[cfg(feature = "extended-named-colors")]
+        assert_eq!(Color::from_html(s).unwrap().rgba_u8(), rgba, "{}", s);
+        #[cfg(not(feature = "extended-named-colors"))]
+        assert!(Color::from_html(s).is_err(), "{} {:?}", s, rgba);
+    }
+
+    // CSS values take precedence over X11 values
+    #[cfg(feature = "extended-named-colors")]
+    {
+        assert_eq!(
+            Color::from_html("gray").unwrap().rgba_u8(),
+            (128, 128, 128, 255)
+        );
+        assert_eq!(
+            Color::from_html("green").unwrap().rgba_u8(),
+            (0, 128, 0, 255)
+        );
+    }
+}
+
+#[cfg(feature = "css5")]
+#[test]
+fn css5_relative() {
+    let base = Color::from_rgb(1.0, 0.0, 0.0);
+    let data = [
+        ("rgb(r g b)", (255, 0, 0, 255)),
+        ("RGB(b g r)", (0, 0, 255, 255)),
+        ("rgb(calc(r / 2) calc(g + 51) b / 0.5)", (128, 51, 0, 128)),
+        ("hsl(calc(h + 120) s l)", (0, 255, 0, 255)),
+        ("hsl(h s calc(l * 0.8))", (204, 0, 0, 255)),
+        ("hwb(h 100% b)", (255, 255, 255, 255)),
+        ("hsv(h s calc(v / 2))", (128, 0, 0, 255)),
+        ("oklch(l 0 h)", (136, 136, 136, 255)),
+    ];
+    for (s, rgba) in data {
+        let c = Color::from_css_color_level5_relative(&base, s).unwrap();
+        assert_eq!(c.rgba_u8(), rgba, "{}", s);
+
+        // same as the relative color syntax with `from`
+        let (fname, args) = s.split_once('(').unwrap();
+        let full = format!("{}(from #f00 {}", fname, args);
+        assert_eq!(parse(&full).unwrap().rgba_u8(), rgba, "{}", full);
+    }
+
+    let base = Color::from_rgba(0.0, 0.0, 1.0, 0.5);
+    let c = Color::from_css_color_level5_relative(&base, "rgb(r g b / calc(alpha * 2))").unwrap();
+    assert_eq!(c.rgba_u8(), (0, 0, 255, 255));
+
+    let data = [
+        ("rgb(r g)", "Invalid rgb format."),
+        ("rgb(r g x)", "Invalid rgb format."),
+        ("hsl(r g b)", "Invalid hsl format."),
+        ("rgb(r g calc(b +))", "Invalid rgb format."),
+        ("foo(r g b)", "Invalid color function."),
+        ("r g b", "Invalid unknown format."),
+        ("", "Invalid unknown format."),
+    ];
+    for (s, err) in data {
+        let e = Color::from_css_color_level5_relative(&base, s).unwrap_err();
+        assert_eq!(e.to_string(), err, "{}", s);
+    }
+}
+
+#[test]
+fn angle_units() {
+    let data = [
+        [
+            "hsl(180, 100%, 50%)",
+            "hsl(180deg, 100%, 50%)",
+            "hsl(0.5turn, 100%, 50%)",
+            "hsl(200grad, 100%, 50%)",
+            "hsl(3.14159265rad, 100%, 50%)",
+        ],
+        [
+            "hwb(90 10% 20%)",
+            "hwb(90deg 10% 20%)",
+            "hwb(0.25turn 10% 20%)",
+            "hwb(100grad 10% 20%)",
+            "hwb(1.5707963rad 10% 20%)",
+        ],
+        [
+            "hsv(270 50% 50%)",
+            "hsv(270deg 50% 50%)",
+            "hsv(0.75turn 50% 50%)",
+            "hsv(300grad 50% 50%)",
+            "hsv(4.712389rad 50% 50%)",
+        ],
+        [
+            "oklch(0.7 0.15 90)",
+            "oklch(0.7 0.15 90deg)",
+            "oklch(0.7 0.15 0.25turn)",
+            "oklch(0.7 0.15 100grad)",
+            "oklch(0.7 0.15 1.5707963rad)",
+        ],
+        [
+            "hsl(from #f00 calc(h + 180) s l)",
+            "hsl(from #f00 calc(h + 180deg) s l)",
+            "hsl(from #f00 calc(h + 0.5turn) s l)",
+            "hsl(from #f00 calc(h + 200grad) s l)",
+            "hsl(from #f00 0.5turn s l)",
+        ],
+        [
+            "color-mix(in hsl, hsl(0 100% 50%), hsl(180 100% 50%))",
+            "color-mix(in hsl, hsl(0deg 100% 50%), hsl(180deg 100% 50%))",
+            "color-mix(in hsl, hsl(0turn 100% 50%), hsl(0.5turn 100% 50%))",
+            "color-mix(in hsl, hsl(0grad 100% 50%), hsl(200grad 100% 50%))",
+            "color-mix(in hsl, hsl(0rad 100% 50%), hsl(3.14159265rad 100% 50%))",
+        ],
+    ];
+    for group in data {
+        let expected = parse(group[0]).unwrap().rgba_u8();
+        for s in &group[1..] {
+            assert_eq!(parse(s).unwrap().rgba_u8(), expected, "{}", s);
+        }
+    }
+
+    #[cfg(feature = "lab")]
+    assert_eq!(
+        parse("lch(50% 40 0.5turn)").unwrap().rgba_u8(),
+        parse("lch(50% 40 180deg)").unwrap().rgba_u8()
+    );
+
+    for s in [
+        "hsl(from #f00 calc(h + 1px) s l)",
+        "hsl(from #f00 calc(h + 1 turn) s l)",
+    ] {
+        assert!(parse(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn gradient_stops() {
+    let red = Color::from_rgb(1.0, 0.0, 0.0);
+    let blue = Color::from_rgb(0.0, 0.0, 1.0);
+
+    let stops = parse_gradient_stops("#f00 10%, 25%, rgb(0 0 255) 40% 60%, 75%, #ff0000").unwrap();
+    assert_eq!(
+        stops,
+        vec![
+            GradientStop::Color(red.clone(), Some(10.0)),
+            GradientStop::Hint(25.0),
+            GradientStop::Color(blue.clone(), Some(40.0)),
+            GradientStop::Color(blue.clone(), Some(60.0)),
+            GradientStop::Hint(75.0),
+            GradientStop::Color(red.clone(), None),
+        ]
+    );
+
+    assert_eq!(
+        parse_gradient_stops(" #f00 , #00f 100% ").unwrap(),
+        vec![
+            GradientStop::Color(red.clone(), None),
+            GradientStop::Color(blue.clone(), Some(100.0)),
+        ]
+    );
+
+    // color hints must be between two colors
+    for s in [
+        "50%, #f00, #00f",
+        "#f00, #00f, 50%",
+        "#f00, 20%, 30%, #00f",
+        "#f00, #00f 10px",
+        "#f00, #00f 10% 20% 30%",
+        "#f00, , #00f",
+        "",
+    ] {
+        assert_eq!(
+            parse_gradient_stops(s),
+            Err(ParseColorError::InvalidGradient),
+            "{}",
+            s
+        );
+    }
+
+    // invalid colors return the color error
+    assert_eq!(
+        parse_gradient_stops("#f00, #ff 50%"),
+        Err(ParseColorError::InvalidHex)
+    );
+
+    assert_eq!(Color::from_css_image_color_hint("#00f 50%"), Ok(blue));
+    assert_eq!(
+        Color::from_css_image_color_hint("50%"),
+        Err(ParseColorError::UnexpectedColorHint)
+    );
+    assert_eq!(
+        Color::from_css_image_color_hint("5x%"),
+        Err(ParseColorError::InvalidGradient)
+    );
+}
+
+#[test]
+fn svg_color_attribute() {
+    let data = [
+        ("none", SvgColor::None),
+        (" NONE ", SvgColor::None),
+        ("inherit", SvgColor::Inherit),
+        ("Inherit", SvgColor::Inherit),
+        ("#f00", SvgColor::Color(Color::from_rgb(1.0, 0.0, 0.0))),
+        (
+            "rgb(0 0 255 / 50%)",
+            SvgColor::Color(Color::from_rgba(0.0, 0.0, 1.0, 0.5)),
+        ),
+    ];
+    for (s, expected) in data {
+        assert_eq!(parse_svg_color_attribute(s), Ok(expected), "{}", s);
+    }
+
+    for s in ["", "nonee", "#ff", "inherit(1)"] {
+        assert!(parse_svg_color_attribute(s).is_err(), "{}", s);
+    }
+
+    assert_eq!(
+        Color::from_svg_color_attribute("#0f0").unwrap().rgba_u8(),
+        (0, 255, 0, 255)
+    );
+    assert_eq!(
+        Color::from_svg_color_attribute("none").unwrap().rgba_u8(),
+        (0, 0, 0, 0)
+    );
+    assert_eq!(
+        Color::from_svg_color_attribute("inherit"),
+        Err(ParseColorError::InvalidUnknown)
+    );
+    assert_eq!(
+        Color::from_svg_color_attribute("#ff"),
+        Err(ParseColorError::InvalidHex)
+    );
+}